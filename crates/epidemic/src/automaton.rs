@@ -0,0 +1,59 @@
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
+use toolkit::{
+    lattice::universal_lattice::UniversalLattice, neighborhood::moore::MooreNeighborhoodBuilder,
+};
+
+#[cfg(feature = "wasm")]
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::population::population,
+    types::{BoundaryHandling, BoundaryHandlingLattice, CellularAutomaton, Lattice},
+};
+
+#[cfg(feature = "wasm")]
+use crate::utils::EpidemicPopulation;
+use crate::{rule::EpidemicRule, state::EpidemicState};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+toolkit::define_stochastic_automaton!(
+    EpidemicAutomaton,
+    EpidemicRule,
+    UniversalLattice<2, EpidemicState>,
+    MooreNeighborhoodBuilder
+);
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(EpidemicLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(EpidemicLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+type InnerLattice = UniversalLattice<2, EpidemicState>;
+#[cfg(feature = "wasm")]
+define_lattice_wrapper!(
+    EpidemicLattice,
+    EpidemicState,
+    EpidemicLatticePoint,
+    EpidemicLatticeSize,
+    EpidemicAutomaton,
+    InnerLattice,
+    InnerSize,
+    InnerPoint
+);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl EpidemicLattice {
+    #[wasm_bindgen(getter)]
+    pub fn population(&self) -> EpidemicPopulation {
+        population(&self.inner)
+    }
+}
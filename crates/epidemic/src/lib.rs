@@ -0,0 +1,4 @@
+pub mod state;
+pub mod rule;
+pub mod utils;
+pub mod automaton;
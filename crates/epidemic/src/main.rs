@@ -0,0 +1,39 @@
+use epidemic::{
+    automaton::EpidemicAutomaton, rule::EpidemicRule, state::EpidemicState,
+    utils::EpidemicPopulation,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::population::population,
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+pub fn main() {
+    let ca = EpidemicAutomaton::new(EpidemicRule::default());
+    let mut context = StepContext::new(42);
+
+    let mut lattice =
+        UniversalLattice::<2, EpidemicState>::from(UniversalLatticeSize::new([40, 20]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    lattice.set_state(
+        &UniversalLatticePoint::new([20, 10]),
+        &EpidemicState::Infected,
+    );
+
+    for _ in 0..60 {
+        let mut s = lattice.to_text(|state| match state {
+            EpidemicState::Susceptible => '.',
+            EpidemicState::Infected => '█',
+            EpidemicState::Recovered => '░',
+        });
+
+        let population: EpidemicPopulation = ca.run(&mut lattice, &mut context, 1, population)[0];
+        s += &format!(
+            "susceptible: {}, infected: {}, recovered: {}\n",
+            population.susceptible, population.infected, population.recovered
+        );
+
+        print!("{s}");
+    }
+}
@@ -0,0 +1,136 @@
+use rand::Rng;
+use toolkit::{
+    prelude::*,
+    utils::{clamp_probability, count_state},
+};
+
+use crate::state::EpidemicState;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Spatial SIR rule: every infected neighbor independently transmits with
+/// `infection_probability`, infected cells recover with
+/// `recovery_probability` and recovered cells lose immunity with
+/// `immunity_loss_probability` (zero gives the classic SIR model).
+/// Probabilities are clamped to `0..=1` and NaN counts as `0`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EpidemicRuleRepr"))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct EpidemicRule {
+    infection_probability: f64,
+    recovery_probability: f64,
+    immunity_loss_probability: f64,
+}
+
+impl Default for EpidemicRule {
+    fn default() -> Self {
+        Self {
+            infection_probability: 0.2,
+            recovery_probability: 0.1,
+            immunity_loss_probability: 0.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl EpidemicRule {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(
+        infection_probability: f64,
+        recovery_probability: f64,
+        immunity_loss_probability: f64,
+    ) -> Self {
+        Self {
+            infection_probability: clamp_probability(infection_probability),
+            recovery_probability: clamp_probability(recovery_probability),
+            immunity_loss_probability: clamp_probability(immunity_loss_probability),
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn infection_probability(&self) -> f64 {
+        self.infection_probability
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn recovery_probability(&self) -> f64 {
+        self.recovery_probability
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn immunity_loss_probability(&self) -> f64 {
+        self.immunity_loss_probability
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EpidemicRuleRepr {
+    infection_probability: f64,
+    recovery_probability: f64,
+    immunity_loss_probability: f64,
+}
+
+/// Probabilities outside `0..=1` are rejected rather than clamped.
+#[cfg(feature = "serde")]
+impl TryFrom<EpidemicRuleRepr> for EpidemicRule {
+    type Error = String;
+
+    fn try_from(repr: EpidemicRuleRepr) -> Result<Self, Self::Error> {
+        use toolkit::utils::check_probability;
+
+        Ok(Self {
+            infection_probability: check_probability(
+                "infection_probability",
+                repr.infection_probability,
+            )?,
+            recovery_probability: check_probability(
+                "recovery_probability",
+                repr.recovery_probability,
+            )?,
+            immunity_loss_probability: check_probability(
+                "immunity_loss_probability",
+                repr.immunity_loss_probability,
+            )?,
+        })
+    }
+}
+
+impl StochasticRule for EpidemicRule {
+    type State = EpidemicState;
+
+    fn apply_with_rng(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+        rng: &mut StepRng,
+    ) -> Self::State {
+        match current_state {
+            EpidemicState::Susceptible => {
+                let infected = count_state(neighbors, &EpidemicState::Infected) as i32;
+                let escape_probability = (1.0 - self.infection_probability).powi(infected);
+
+                if rng.gen_bool(1.0 - escape_probability) {
+                    EpidemicState::Infected
+                } else {
+                    EpidemicState::Susceptible
+                }
+            }
+            EpidemicState::Infected => {
+                if rng.gen_bool(self.recovery_probability) {
+                    EpidemicState::Recovered
+                } else {
+                    EpidemicState::Infected
+                }
+            }
+            EpidemicState::Recovered => {
+                if rng.gen_bool(self.immunity_loss_probability) {
+                    EpidemicState::Susceptible
+                } else {
+                    EpidemicState::Recovered
+                }
+            }
+        }
+    }
+}
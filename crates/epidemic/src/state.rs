@@ -0,0 +1,31 @@
+use toolkit::lattice::snapshot::SnapshotState;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum EpidemicState {
+    #[default]
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+impl SnapshotState for EpidemicState {
+    const WIDTH: usize = 1;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(Self::Susceptible),
+            [1] => Some(Self::Infected),
+            [2] => Some(Self::Recovered),
+            _ => None,
+        }
+    }
+}
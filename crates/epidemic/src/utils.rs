@@ -0,0 +1,23 @@
+use crate::state::EpidemicState;
+use toolkit::analysis::population::Population;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct EpidemicPopulation {
+    pub susceptible: usize,
+    pub infected: usize,
+    pub recovered: usize,
+}
+
+impl Population<EpidemicState> for EpidemicPopulation {
+    fn add(&mut self, state: &EpidemicState) {
+        match state {
+            EpidemicState::Susceptible => self.susceptible += 1,
+            EpidemicState::Infected => self.infected += 1,
+            EpidemicState::Recovered => self.recovered += 1,
+        }
+    }
+}
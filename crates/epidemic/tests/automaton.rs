@@ -0,0 +1,61 @@
+use epidemic::{
+    automaton::EpidemicAutomaton, rule::EpidemicRule, state::EpidemicState,
+    utils::EpidemicPopulation,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::population::population,
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+#[test]
+fn run_records_population_after_every_step() {
+    let automaton = EpidemicAutomaton::new(EpidemicRule::new(0.5, 0.2, 0.0));
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([20, 20]));
+    lattice.set_state(
+        &UniversalLatticePoint::new([10, 10]),
+        &EpidemicState::Infected,
+    );
+
+    let history: Vec<EpidemicPopulation> =
+        automaton.run(&mut lattice, &mut StepContext::new(5), 30, population);
+
+    assert_eq!(history.len(), 30);
+    assert_eq!(history[29], population(&lattice));
+    for pair in history.windows(2) {
+        let [before, after] = pair else {
+            unreachable!()
+        };
+
+        assert_eq!(after.susceptible + after.infected + after.recovered, 400);
+        // Without immunity loss nobody leaves the recovered compartment.
+        assert!(after.recovered >= before.recovered);
+        assert!(after.susceptible <= before.susceptible);
+    }
+    assert!(history[29].recovered > 0);
+}
+
+#[test]
+fn nan_probabilities_count_as_zero() {
+    let rule = EpidemicRule::new(f64::NAN, f64::NAN, f64::NAN);
+    assert_eq!(
+        (
+            rule.infection_probability(),
+            rule.recovery_probability(),
+            rule.immunity_loss_probability()
+        ),
+        (0.0, 0.0, 0.0)
+    );
+
+    let automaton = EpidemicAutomaton::new(rule).with_seed(1);
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([8, 8]));
+    lattice.set_state(
+        &UniversalLatticePoint::new([4, 4]),
+        &EpidemicState::Infected,
+    );
+    automaton.step(&mut lattice);
+
+    let counts: EpidemicPopulation = population(&lattice);
+    assert_eq!((counts.infected, counts.susceptible), (1, 63));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "serde")]
+
+use epidemic::{automaton::EpidemicAutomaton, rule::EpidemicRule};
+use toolkit::prelude::*;
+
+#[test]
+fn automaton_round_trips() {
+    let automaton = EpidemicAutomaton::new(EpidemicRule::new(0.3, 0.2, 0.1))
+        .with_update_scheme(UpdateScheme::Cyclic)
+        .with_seed(7);
+    let decoded: EpidemicAutomaton =
+        serde_json::from_str(&serde_json::to_string(&automaton).unwrap()).unwrap();
+
+    let rule = decoded.rule();
+    assert_eq!(
+        (
+            rule.infection_probability(),
+            rule.recovery_probability(),
+            rule.immunity_loss_probability()
+        ),
+        (0.3, 0.2, 0.1)
+    );
+    assert_eq!(decoded.update_scheme(), UpdateScheme::Cyclic);
+    assert_eq!(decoded.step_context().unwrap().get(), StepContext::new(7));
+}
+
+#[test]
+fn probabilities_outside_the_unit_interval_are_rejected() {
+    let error = serde_json::from_str::<EpidemicRule>(
+        r#"{"infection_probability": -0.1, "recovery_probability": 0.1, "immunity_loss_probability": 0.0}"#,
+    )
+    .unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .contains("`infection_probability` must lie in 0..=1, found -0.1")
+    );
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
+use toolkit::{
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+};
+
+#[cfg(feature = "wasm")]
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::population::population,
+    types::{BoundaryHandling, BoundaryHandlingLattice, CellularAutomaton, Lattice},
+};
+
+#[cfg(feature = "wasm")]
+use crate::utils::ForestFirePopulation;
+use crate::{rule::ForestFireRule, state::ForestFireState};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+toolkit::define_stochastic_automaton!(
+    ForestFireAutomaton,
+    ForestFireRule,
+    UniversalLattice<2, ForestFireState>,
+    VonNeumannNeighborhoodBuilder
+);
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(ForestFireLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(ForestFireLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+type InnerLattice = UniversalLattice<2, ForestFireState>;
+#[cfg(feature = "wasm")]
+define_lattice_wrapper!(
+    ForestFireLattice,
+    ForestFireState,
+    ForestFireLatticePoint,
+    ForestFireLatticeSize,
+    ForestFireAutomaton,
+    InnerLattice,
+    InnerSize,
+    InnerPoint
+);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl ForestFireLattice {
+    #[wasm_bindgen(getter)]
+    pub fn population(&self) -> ForestFirePopulation {
+        population(&self.inner)
+    }
+}
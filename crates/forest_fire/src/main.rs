@@ -0,0 +1,33 @@
+use forest_fire::{
+    automaton::ForestFireAutomaton, rule::ForestFireRule, state::ForestFireState,
+    utils::ForestFirePopulation,
+};
+use toolkit::{
+    aliases::UniversalLatticeSize, analysis::population::population,
+    lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+pub fn main() {
+    let ca = ForestFireAutomaton::new(ForestFireRule::new(0.05, 0.0005));
+    let mut context = StepContext::new(42);
+
+    let mut lattice =
+        UniversalLattice::<2, ForestFireState>::from(UniversalLatticeSize::new([40, 20]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    for _ in 0..100 {
+        let mut s = lattice.to_text(|state| match state {
+            ForestFireState::Empty => '.',
+            ForestFireState::Tree => '♣',
+            ForestFireState::Burning => '▲',
+        });
+
+        let population: ForestFirePopulation = ca.run(&mut lattice, &mut context, 1, population)[0];
+        s += &format!(
+            "trees: {}, burning: {}, empty: {}\n",
+            population.trees, population.burning, population.empty
+        );
+
+        print!("{s}");
+    }
+}
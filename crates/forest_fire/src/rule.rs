@@ -0,0 +1,108 @@
+use rand::Rng;
+use toolkit::{
+    prelude::*,
+    utils::{clamp_probability, count_state},
+};
+
+use crate::state::ForestFireState;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Drossel–Schwabl forest-fire rule: trees grow on empty cells with
+/// probability `growth_probability`, catch fire from a burning neighbor, or
+/// are struck by lightning with probability `lightning_probability`.
+/// Probabilities are clamped to `0..=1` and NaN counts as `0`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ForestFireRuleRepr"))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct ForestFireRule {
+    growth_probability: f64,
+    lightning_probability: f64,
+}
+
+impl Default for ForestFireRule {
+    fn default() -> Self {
+        Self {
+            growth_probability: 0.05,
+            lightning_probability: 0.0001,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl ForestFireRule {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(growth_probability: f64, lightning_probability: f64) -> Self {
+        Self {
+            growth_probability: clamp_probability(growth_probability),
+            lightning_probability: clamp_probability(lightning_probability),
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn growth_probability(&self) -> f64 {
+        self.growth_probability
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn lightning_probability(&self) -> f64 {
+        self.lightning_probability
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ForestFireRuleRepr {
+    growth_probability: f64,
+    lightning_probability: f64,
+}
+
+/// Probabilities outside `0..=1` are rejected rather than clamped.
+#[cfg(feature = "serde")]
+impl TryFrom<ForestFireRuleRepr> for ForestFireRule {
+    type Error = String;
+
+    fn try_from(repr: ForestFireRuleRepr) -> Result<Self, Self::Error> {
+        use toolkit::utils::check_probability;
+
+        Ok(Self {
+            growth_probability: check_probability("growth_probability", repr.growth_probability)?,
+            lightning_probability: check_probability(
+                "lightning_probability",
+                repr.lightning_probability,
+            )?,
+        })
+    }
+}
+
+impl StochasticRule for ForestFireRule {
+    type State = ForestFireState;
+
+    fn apply_with_rng(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+        rng: &mut StepRng,
+    ) -> Self::State {
+        match current_state {
+            ForestFireState::Burning => ForestFireState::Empty,
+            ForestFireState::Tree => {
+                if count_state(neighbors, &ForestFireState::Burning) > 0
+                    || rng.gen_bool(self.lightning_probability)
+                {
+                    ForestFireState::Burning
+                } else {
+                    ForestFireState::Tree
+                }
+            }
+            ForestFireState::Empty => {
+                if rng.gen_bool(self.growth_probability) {
+                    ForestFireState::Tree
+                } else {
+                    ForestFireState::Empty
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::state::ForestFireState;
+use toolkit::analysis::population::Population;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct ForestFirePopulation {
+    pub empty: usize,
+    pub trees: usize,
+    pub burning: usize,
+}
+
+impl Population<ForestFireState> for ForestFirePopulation {
+    fn add(&mut self, state: &ForestFireState) {
+        match state {
+            ForestFireState::Empty => self.empty += 1,
+            ForestFireState::Tree => self.trees += 1,
+            ForestFireState::Burning => self.burning += 1,
+        }
+    }
+}
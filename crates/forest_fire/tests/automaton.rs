@@ -0,0 +1,56 @@
+use forest_fire::{automaton::ForestFireAutomaton, rule::ForestFireRule, state::ForestFireState};
+use toolkit::{
+    aliases::UniversalLatticeSize, lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+fn trees(lattice: &UniversalLattice<2, ForestFireState>) -> usize {
+    lattice
+        .states()
+        .into_iter()
+        .filter(|state| *state == ForestFireState::Tree)
+        .count()
+}
+
+#[test]
+fn repeated_steps_draw_fresh_randomness() {
+    let automaton = ForestFireAutomaton::new(ForestFireRule::new(0.5, 0.0)).with_seed(3);
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([16, 16]));
+
+    automaton.step(&mut lattice);
+    let first = trees(&lattice);
+    automaton.step(&mut lattice);
+
+    // Replaying generation 0 would grow trees on exactly the same cells again.
+    assert!(first > 0);
+    assert!(trees(&lattice) > first);
+}
+
+#[test]
+fn step_matches_step_with_context() {
+    let automaton = ForestFireAutomaton::new(ForestFireRule::new(0.3, 0.01)).with_seed(11);
+    let mut stepped = UniversalLattice::from(UniversalLatticeSize::new([12, 12]));
+    let mut context = StepContext::new(11);
+    let mut with_context = stepped.clone();
+
+    for _ in 0..5 {
+        automaton.step(&mut stepped);
+        automaton.step_with_context(&mut with_context, &mut context);
+    }
+
+    assert_eq!(stepped.states(), with_context.states());
+}
+
+#[test]
+fn nan_probabilities_count_as_zero() {
+    let rule = ForestFireRule::new(f64::NAN, f64::NAN);
+    assert_eq!(
+        (rule.growth_probability(), rule.lightning_probability()),
+        (0.0, 0.0)
+    );
+
+    let automaton = ForestFireAutomaton::new(rule).with_seed(1);
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([8, 8]));
+    automaton.step(&mut lattice);
+
+    assert_eq!(trees(&lattice), 0);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "serde")]
+
+use forest_fire::rule::ForestFireRule;
+
+#[test]
+fn rule_round_trips() {
+    let rule = ForestFireRule::new(0.25, 0.5);
+    let decoded: ForestFireRule =
+        serde_json::from_str(&serde_json::to_string(&rule).unwrap()).unwrap();
+
+    assert_eq!(
+        (
+            decoded.growth_probability(),
+            decoded.lightning_probability()
+        ),
+        (0.25, 0.5)
+    );
+}
+
+#[test]
+fn probabilities_outside_the_unit_interval_are_rejected() {
+    let error = serde_json::from_str::<ForestFireRule>(
+        r#"{"growth_probability": 0.5, "lightning_probability": 1.5}"#,
+    )
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("`lightning_probability` must lie in 0..=1, found 1.5")
+    );
+
+    // JSON has no NaN, serde_json writes it as `null`.
+    let nan = serde_json::to_string(&f64::NAN).unwrap();
+    assert!(
+        serde_json::from_str::<ForestFireRule>(&format!(
+            r#"{{"growth_probability": {nan}, "lightning_probability": 0.0}}"#
+        ))
+        .is_err()
+    );
+}
@@ -1,11 +1,12 @@
+#[cfg(feature = "wasm")]
 use lattice_wrapper_macros::define_lattice_wrapper;
+#[cfg(feature = "wasm")]
 use lattice_wrapper_macros::define_point_wrapper;
+#[cfg(feature = "wasm")]
 use lattice_wrapper_macros::define_size_wrapper;
 
-#[cfg(feature = "wasm")]
 use toolkit::lattice::universal_lattice::UniversalLattice;
 #[cfg(feature = "wasm")]
-#[cfg(feature = "wasm")]
 use toolkit::aliases::{UniversalLatticeSize, UniversalLatticePoint};
 
 use toolkit::neighborhood::moore::MooreNeighborhoodBuilder;
@@ -13,6 +14,7 @@ use toolkit::neighborhood::moore::MooreNeighborhoodBuilder;
 #[cfg(feature = "wasm")]
 use toolkit::types::BoundaryHandling;
 use toolkit::{
+    automaton::update_scheme::UpdateScheme,
     types::CellularAutomaton,
 };
 
@@ -27,16 +29,32 @@ use crate::{rule::GameOfLifeRule, state::GameOfLifeState};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct GameOfLifeAutomaton {
     rule: GameOfLifeRule,
+    update_scheme: UpdateScheme,
 }
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GameOfLifeAutomaton {
     #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(rule: GameOfLifeRule) -> Self {
-        GameOfLifeAutomaton { rule }
+        GameOfLifeAutomaton {
+            rule,
+            update_scheme: UpdateScheme::default(),
+        }
+    }
+}
+
+impl GameOfLifeAutomaton {
+    pub fn with_update_scheme(mut self, update_scheme: UpdateScheme) -> Self {
+        self.update_scheme = update_scheme;
+        self
+    }
+
+    pub fn set_update_scheme(&mut self, update_scheme: UpdateScheme) {
+        self.update_scheme = update_scheme;
     }
 }
 
@@ -52,6 +70,10 @@ impl CellularAutomaton for GameOfLifeAutomaton {
     fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
         MooreNeighborhoodBuilder
     }
+
+    fn update_scheme(&self) -> UpdateScheme {
+        self.update_scheme
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -83,3 +105,21 @@ define_lattice_wrapper!(
     InnerSize,
     InnerPoint
 );
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameOfLifeLattice {
+    pub fn from_rle(text: &str) -> Result<GameOfLifeLattice, wasm_bindgen::JsError> {
+        let pattern = crate::formats::rle::read_rle(text)?;
+        let lattice: InnerLattice = pattern.to_lattice()?;
+
+        Ok(lattice.into())
+    }
+
+    pub fn to_rle(&self, rule: &GameOfLifeRule) -> String {
+        let pattern = crate::formats::Pattern::from_lattice(&self.inner)
+            .with_rule(rule.rulestring());
+
+        crate::formats::rle::write_rle(&pattern)
+    }
+}
@@ -0,0 +1,139 @@
+//! Catagolue apgcodes such as `xq4_153`: a type prefix (`xs` still life,
+//! `xp` oscillator, `xq` spaceship, followed by the population or period)
+//! and the pattern in extended Wechsler format.
+//!
+//! The pattern is cut into strips of five rows separated by `z`. Each strip
+//! is written column by column as a base-32 digit (`0`-`9`, `a`-`v`) with the
+//! top row in the lowest bit; `w`, `x` and `yN` abbreviate runs of 2, 3 and
+//! 4 + N empty columns.
+
+use toolkit::aliases::UniversalLatticePoint;
+
+use super::{FormatError, Pattern};
+
+const STRIP_HEIGHT: i128 = 5;
+
+pub fn read_apgcode(code: &str) -> Result<Pattern, FormatError> {
+    let code = code.trim();
+    let (prefix, body) = code
+        .split_once('_')
+        .ok_or_else(|| FormatError::InvalidHeader(code.to_string()))?;
+
+    if !is_valid_prefix(prefix) {
+        return Err(FormatError::InvalidHeader(prefix.to_string()));
+    }
+
+    let mut pattern = Pattern::default();
+    let (mut x, mut strip) = (0i128, 0i128);
+    let mut characters = body.chars();
+    let unexpected = |character| FormatError::UnexpectedCharacter { line: 1, character };
+
+    while let Some(character) = characters.next() {
+        match character {
+            'w' => x += 2,
+            'x' => x += 3,
+            'y' => {
+                let count = characters.next().ok_or(FormatError::UnterminatedPattern)?;
+                x += 4 + count.to_digit(36).ok_or(unexpected(count))? as i128;
+            }
+            'z' => {
+                x = 0;
+                strip += 1;
+            }
+            _ => {
+                let column = character
+                    .to_digit(32)
+                    .filter(|_| !character.is_ascii_uppercase())
+                    .ok_or(unexpected(character))?;
+
+                for row in 0..STRIP_HEIGHT {
+                    if column & (1 << row) != 0 {
+                        let point = UniversalLatticePoint::new([x, strip * STRIP_HEIGHT + row]);
+                        pattern.set(&point, 1);
+                    }
+                }
+
+                x += 1;
+            }
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Encodes the pattern as it is oriented, with the given type prefix.
+/// Catagolue's canonical codes additionally pick the smallest encoding over
+/// every phase and orientation, which needs the rule to be run and is left to
+/// the caller.
+pub fn write_apgcode(pattern: &Pattern, prefix: &str) -> String {
+    let [left, top] = pattern.origin().values();
+    let strips = (pattern.height() as i128 + STRIP_HEIGHT - 1) / STRIP_HEIGHT;
+
+    let encoded: Vec<String> = (0..strips)
+        .map(|strip| {
+            let columns: Vec<u32> = (0..pattern.width() as i128)
+                .map(|x| {
+                    (0..STRIP_HEIGHT).fold(0, |column, row| {
+                        let point = UniversalLatticePoint::new([
+                            left + x,
+                            top + strip * STRIP_HEIGHT + row,
+                        ]);
+                        column | u32::from(pattern.get(&point) != 0) << row
+                    })
+                })
+                .collect();
+
+            encode_strip(&columns)
+        })
+        .collect();
+
+    let body = encoded.join("z");
+    format!("{prefix}_{}", body.trim_end_matches('z'))
+}
+
+fn is_valid_prefix(prefix: &str) -> bool {
+    let mut characters = prefix.chars();
+
+    characters.next() == Some('x')
+        && matches!(characters.next(), Some('s' | 'p' | 'q'))
+        && characters.clone().next().is_some()
+        && characters.all(|character| character.is_ascii_digit())
+}
+
+fn encode_strip(columns: &[u32]) -> String {
+    let length = columns
+        .iter()
+        .rposition(|column| *column != 0)
+        .map_or(0, |last| last + 1);
+    let mut output = String::new();
+    let mut blank = 0;
+
+    for column in &columns[..length] {
+        if *column == 0 {
+            blank += 1;
+            continue;
+        }
+
+        push_blank(&mut output, blank);
+        blank = 0;
+        output.push(char::from_digit(*column, 32).unwrap_or('0'));
+    }
+
+    output
+}
+
+fn push_blank(output: &mut String, mut blank: u32) {
+    while blank >= 4 {
+        let run = blank.min(39);
+        output.push('y');
+        output.push(char::from_digit(run - 4, 36).unwrap_or('0'));
+        blank -= run;
+    }
+
+    match blank {
+        3 => output.push('x'),
+        2 => output.push('w'),
+        1 => output.push('0'),
+        _ => {}
+    }
+}
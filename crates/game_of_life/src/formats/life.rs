@@ -0,0 +1,135 @@
+//! Life 1.05 and Life 1.06 patterns.
+//!
+//! Life 1.05 places blocks of `.`/`*` rows at `#P x y` offsets, and Life 1.06
+//! lists one `x y` pair per alive cell. Both use coordinates relative to the
+//! pattern's centre, so the bounding box may start at negative coordinates.
+
+use toolkit::aliases::UniversalLatticePoint;
+
+use super::{FormatError, Pattern};
+use crate::rule::GameOfLifeRule;
+
+const LIFE_105_HEADER: &str = "#Life 1.05";
+const LIFE_106_HEADER: &str = "#Life 1.06";
+
+pub fn read_life_105(text: &str) -> Result<Pattern, FormatError> {
+    let mut lines = text.lines().map(str::trim_end).enumerate();
+
+    if lines.next().map(|(_, line)| line) != Some(LIFE_105_HEADER) {
+        return Err(FormatError::MissingHeader);
+    }
+
+    let mut pattern = Pattern::default();
+    let (mut left, mut y) = (0, 0);
+
+    for (index, line) in lines {
+        if let Some(comment) = line.strip_prefix("#D") {
+            pattern = pattern.with_comment(comment.trim_start());
+        } else if let Some(rule) = line.strip_prefix("#R") {
+            pattern.set_rule(Some(rule.trim().to_string()));
+        } else if let Some(position) = line.strip_prefix("#P") {
+            [left, y] = parse_coordinates(position)
+                .ok_or(FormatError::InvalidCoordinates { line: index + 1 })?;
+        } else if line.starts_with('#') {
+            continue;
+        } else {
+            for (x, character) in line.chars().enumerate() {
+                match character {
+                    '.' => {}
+                    '*' => pattern.set(&UniversalLatticePoint::new([left + x as i128, y]), 1),
+                    _ => {
+                        return Err(FormatError::UnexpectedCharacter {
+                            line: index + 1,
+                            character,
+                        });
+                    }
+                }
+            }
+
+            y += 1;
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Writes the pattern as a single `#P` block. A Life-like rule is written in
+/// the format's survival-first `#R` notation, no rule as the `#N` default.
+pub fn write_life_105(pattern: &Pattern) -> String {
+    let mut output = format!("{LIFE_105_HEADER}\n");
+
+    for comment in pattern.comments() {
+        output.push_str(&format!("#D {comment}\n"));
+    }
+
+    match pattern.rule() {
+        None => output.push_str("#N\n"),
+        Some(rule) => {
+            let rule = GameOfLifeRule::from_rulestring(rule)
+                .map(|rule| rule.rulestring())
+                .and_then(|rule| {
+                    let (birth, survival) = rule.split_once('/')?;
+                    Some(format!("{}/{}", &survival[1..], &birth[1..]))
+                })
+                .unwrap_or_else(|| rule.to_string());
+
+            output.push_str(&format!("#R {rule}\n"));
+        }
+    }
+
+    let [left, top] = pattern.origin().values();
+    output.push_str(&format!("#P {left} {top}\n"));
+
+    for row in pattern.rows() {
+        let line: String = row
+            .iter()
+            .map(|point| if pattern.get(point) == 0 { '.' } else { '*' })
+            .collect();
+        let line = line.trim_end_matches('.');
+
+        output.push_str(if line.is_empty() { "." } else { line });
+        output.push('\n');
+    }
+
+    output
+}
+
+pub fn read_life_106(text: &str) -> Result<Pattern, FormatError> {
+    let mut lines = text.lines().map(str::trim).enumerate();
+
+    if lines.next().map(|(_, line)| line) != Some(LIFE_106_HEADER) {
+        return Err(FormatError::MissingHeader);
+    }
+
+    let mut pattern = Pattern::default();
+
+    for (index, line) in lines {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let point =
+            parse_coordinates(line).ok_or(FormatError::InvalidCoordinates { line: index + 1 })?;
+        pattern.set(&UniversalLatticePoint::new(point), 1);
+    }
+
+    Ok(pattern)
+}
+
+pub fn write_life_106(pattern: &Pattern) -> String {
+    let mut output = format!("{LIFE_106_HEADER}\n");
+
+    for (point, _) in pattern.cells() {
+        let [x, y] = point.values();
+        output.push_str(&format!("{x} {y}\n"));
+    }
+
+    output
+}
+
+fn parse_coordinates(text: &str) -> Option<[i128; 2]> {
+    let mut values = text.split_whitespace().map(|value| value.parse().ok());
+    let point = [values.next()??, values.next()??];
+
+    values.next().is_none().then_some(point)
+}
@@ -0,0 +1,464 @@
+//! Golly's Macrocell (`.mc`) format: the pattern as a hash-consed quadtree,
+//! written bottom-up so every node only refers to nodes before it.
+//!
+//! Two-state files use 8×8 leaves written as `.`/`*` rows ending in `$`;
+//! multi-state files use `1 nw ne sw se` leaves holding states directly.
+//! Higher nodes are `level nw ne sw se` with 1-based node indices, `0` being
+//! the empty node. The last node is the root; its top-left corner is placed at
+//! the origin.
+//!
+//! [`Macrocell`] keeps the node table as read, so huge patterns with a lot of
+//! repetition are never expanded cell by cell unless asked to.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+use toolkit::{
+    aliases::UniversalLatticePoint, lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+use super::{FormatError, Pattern};
+
+const HEADER: &str = "[M2]";
+const LEAF_LEVEL: u32 = 3;
+const LEAF_SIDE: i128 = 1 << LEAF_LEVEL;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Node {
+    /// Two-state 8×8 leaf, bit `8 * y + x` set for an alive cell.
+    Leaf(u64),
+    /// Multi-state 2×2 leaf in `nw, ne, sw, se` order.
+    States([u8; 4]),
+    Branch {
+        level: u32,
+        children: [usize; 4],
+    },
+}
+
+impl Node {
+    fn level(&self) -> u32 {
+        match self {
+            Self::Leaf(_) => LEAF_LEVEL,
+            Self::States(_) => 1,
+            Self::Branch { level, .. } => *level,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Macrocell {
+    rule: Option<String>,
+    comments: Vec<String>,
+    nodes: Vec<Node>,
+}
+
+impl Macrocell {
+    /// Builds the quadtree from non-zero cells, sharing identical subtrees.
+    /// The cells are shifted so that their bounding box starts at the origin.
+    pub fn from_cells(cells: impl IntoIterator<Item = (UniversalLatticePoint<2>, u8)>) -> Self {
+        let mut cells: Vec<([i128; 2], u8)> = cells
+            .into_iter()
+            .filter(|(_, state)| *state != 0)
+            .map(|(point, state)| (point.values(), state))
+            .collect();
+
+        let mut builder = Builder::default();
+        if cells.is_empty() {
+            return builder.macrocell;
+        }
+
+        let left = cells.iter().map(|([x, _], _)| *x).min().unwrap_or(0);
+        let top = cells.iter().map(|([_, y], _)| *y).min().unwrap_or(0);
+        let extent = cells
+            .iter()
+            .map(|([x, y], _)| (x - left).max(y - top) + 1)
+            .max()
+            .unwrap_or(1);
+
+        cells
+            .iter_mut()
+            .for_each(|([x, y], _)| (*x, *y) = (*x - left, *y - top));
+
+        let multi_state = cells.iter().any(|(_, state)| *state > 1);
+        builder.leaf_level = if multi_state { 1 } else { LEAF_LEVEL };
+
+        let mut level = builder.leaf_level;
+        while (1i128 << level) < extent {
+            level += 1;
+        }
+
+        builder.build(level, [0, 0], &mut cells);
+        builder.macrocell
+    }
+
+    pub fn from_pattern(pattern: &Pattern) -> Self {
+        Self {
+            rule: pattern.rule().map(str::to_string),
+            comments: pattern.comments().to_vec(),
+            ..Self::from_cells(pattern.cells().map(|(point, state)| (*point, *state)))
+        }
+    }
+
+    pub fn from_lattice<S>(lattice: &UniversalLattice<2, S>) -> Self
+    where
+        S: Clone + Default + Into<u8>,
+    {
+        Self::from_cells(
+            lattice
+                .sparse_points()
+                .into_iter()
+                .map(|point| (point, lattice.get_state(&point).into())),
+        )
+    }
+
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comments.push(comment.into());
+        self
+    }
+
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Level of the root; the pattern fits in a square of side `2^level`.
+    pub fn level(&self) -> u32 {
+        self.nodes.last().map_or(0, Node::level)
+    }
+
+    /// Number of distinct quadtree nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of non-zero cells, or `None` when it does not fit in a `u128`,
+    /// which a full tree deeper than level 63 can reach.
+    pub fn population(&self) -> Option<u128> {
+        let mut populations: Vec<Option<u128>> = vec![Some(0)];
+
+        for node in &self.nodes {
+            let population = match node {
+                Node::Leaf(bits) => Some(bits.count_ones() as u128),
+                Node::States(states) => {
+                    Some(states.iter().filter(|state| **state != 0).count() as u128)
+                }
+                Node::Branch { children, .. } => children
+                    .iter()
+                    .try_fold(0u128, |sum, child| sum.checked_add(populations[*child]?)),
+            };
+
+            populations.push(population);
+        }
+
+        populations.last().copied().unwrap_or(Some(0))
+    }
+
+    /// Calls `visit` for every non-zero cell, descending only into non-empty
+    /// nodes.
+    pub fn for_each_cell(&self, mut visit: impl FnMut(UniversalLatticePoint<2>, u8)) {
+        if !self.nodes.is_empty() {
+            self.visit(self.nodes.len(), [0, 0], &mut visit);
+        }
+    }
+
+    fn visit(
+        &self,
+        index: usize,
+        [x, y]: [i128; 2],
+        visit: &mut impl FnMut(UniversalLatticePoint<2>, u8),
+    ) {
+        if index == 0 {
+            return;
+        }
+
+        match &self.nodes[index - 1] {
+            Node::Leaf(bits) => {
+                for bit in (0..64).filter(|bit| bits & (1 << bit) != 0) {
+                    let point = [x + bit % LEAF_SIDE, y + bit / LEAF_SIDE];
+                    visit(UniversalLatticePoint::new(point), 1);
+                }
+            }
+            Node::States(states) => {
+                for (quadrant, state) in states.iter().enumerate().filter(|(_, state)| **state != 0)
+                {
+                    visit(
+                        UniversalLatticePoint::new(offset([x, y], quadrant, 1)),
+                        *state,
+                    );
+                }
+            }
+            Node::Branch { level, children } => {
+                let half = 1 << (level - 1);
+
+                for (quadrant, child) in children.iter().enumerate() {
+                    self.visit(*child, offset([x, y], quadrant, half), visit);
+                }
+            }
+        }
+    }
+
+    pub fn to_pattern(&self) -> Pattern {
+        let mut pattern = Pattern::default();
+        self.for_each_cell(|point, state| pattern.set(&point, state));
+
+        if let Some(rule) = &self.rule {
+            pattern = pattern.with_rule(rule.clone());
+        }
+
+        self.comments.iter().fold(pattern, |pattern, comment| {
+            pattern.with_comment(comment.clone())
+        })
+    }
+
+    /// Writes the cells into an existing lattice with the root's top-left
+    /// corner at `origin`.
+    pub fn place<S>(
+        &self,
+        lattice: &mut UniversalLattice<2, S>,
+        origin: &UniversalLatticePoint<2>,
+    ) -> Result<(), FormatError>
+    where
+        S: Clone + Default + TryFrom<u8>,
+    {
+        let [origin_x, origin_y] = origin.values();
+        let mut result = Ok(());
+
+        self.for_each_cell(|point, state| {
+            let [x, y] = point.values();
+
+            match S::try_from(state) {
+                Ok(state) => lattice.set_state(
+                    &UniversalLatticePoint::new([origin_x + x, origin_y + y]),
+                    &state,
+                ),
+                Err(_) => result = Err(FormatError::UnsupportedState(state)),
+            }
+        });
+
+        result
+    }
+}
+
+pub fn read_macrocell(reader: impl BufRead) -> Result<Macrocell, FormatError> {
+    let mut lines = reader.lines().enumerate();
+    let mut macrocell = Macrocell::default();
+
+    let header = lines.next().map(|(_, line)| line).transpose().map_err(read_error)?;
+
+    if !header.is_some_and(|header| header.starts_with(HEADER)) {
+        return Err(FormatError::MissingHeader);
+    }
+
+    for (index, line) in lines {
+        let line = line.map_err(read_error)?;
+        let line = line.trim();
+        let line_number = index + 1;
+
+        if let Some(rule) = line.strip_prefix("#R") {
+            macrocell.rule = Some(rule.trim().to_string());
+        } else if let Some(comment) = line.strip_prefix("#C") {
+            macrocell.comments.push(comment.trim_start().to_string());
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else if line.starts_with(|character: char| character.is_ascii_digit()) {
+            let node = read_node(line, &macrocell.nodes)
+                .ok_or(FormatError::InvalidNode { line: line_number })?;
+            macrocell.nodes.push(node);
+        } else {
+            macrocell.nodes.push(read_leaf(line, line_number)?);
+        }
+    }
+
+    Ok(macrocell)
+}
+
+pub fn write_macrocell(macrocell: &Macrocell, writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, "{HEADER}")?;
+
+    if let Some(rule) = &macrocell.rule {
+        writeln!(writer, "#R {rule}")?;
+    }
+
+    for comment in &macrocell.comments {
+        writeln!(writer, "#C {comment}")?;
+    }
+
+    for node in &macrocell.nodes {
+        match node {
+            Node::Leaf(bits) => {
+                let rows = (0..LEAF_SIDE).map(|y| {
+                    let row: String = (0..LEAF_SIDE)
+                        .map(|x| {
+                            if bits & (1 << (y * LEAF_SIDE + x)) != 0 {
+                                '*'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect();
+                    row.trim_end_matches('.').to_string()
+                });
+                let rows: Vec<String> = rows.collect();
+                let last = rows
+                    .iter()
+                    .rposition(|row| !row.is_empty())
+                    .map_or(0, |last| last + 1);
+
+                writeln!(writer, "{}$", rows[..last].join("$"))?;
+            }
+            Node::States([nw, ne, sw, se]) => writeln!(writer, "1 {nw} {ne} {sw} {se}")?,
+            Node::Branch {
+                level,
+                children: [nw, ne, sw, se],
+            } => writeln!(writer, "{level} {nw} {ne} {sw} {se}")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn read_error(error: io::Error) -> FormatError {
+    FormatError::Io(error.kind())
+}
+
+fn read_node(line: &str, nodes: &[Node]) -> Option<Node> {
+    let values: Vec<usize> = line
+        .split_whitespace()
+        .map(|value| value.parse().ok())
+        .collect::<Option<_>>()?;
+    let [level, nw, ne, sw, se] = values[..] else {
+        return None;
+    };
+
+    if level == 1 {
+        let states = [nw, ne, sw, se].map(u8::try_from);
+        return match states {
+            [Ok(nw), Ok(ne), Ok(sw), Ok(se)] => Some(Node::States([nw, ne, sw, se])),
+            _ => None,
+        };
+    }
+
+    let level = u32::try_from(level)
+        .ok()
+        .filter(|level| (2..127).contains(level))?;
+    let children = [nw, ne, sw, se];
+    let valid = children.iter().all(|child| {
+        *child == 0
+            || nodes
+                .get(child - 1)
+                .is_some_and(|node| node.level() == level - 1)
+    });
+
+    valid.then_some(Node::Branch { level, children })
+}
+
+fn read_leaf(line: &str, line_number: usize) -> Result<Node, FormatError> {
+    let (mut x, mut y, mut bits) = (0, 0, 0u64);
+
+    for character in line.chars() {
+        match character {
+            '.' => x += 1,
+            '*' => {
+                if x >= LEAF_SIDE || y >= LEAF_SIDE {
+                    return Err(FormatError::InvalidNode { line: line_number });
+                }
+                bits |= 1 << (y * LEAF_SIDE + x);
+                x += 1;
+            }
+            '$' => {
+                x = 0;
+                y += 1;
+            }
+            _ => {
+                return Err(FormatError::UnexpectedCharacter {
+                    line: line_number,
+                    character,
+                });
+            }
+        }
+    }
+
+    Ok(Node::Leaf(bits))
+}
+
+/// Top-left corner of a quadrant in `nw, ne, sw, se` order.
+fn offset([x, y]: [i128; 2], quadrant: usize, half: i128) -> [i128; 2] {
+    [
+        x + half * (quadrant % 2) as i128,
+        y + half * (quadrant / 2) as i128,
+    ]
+}
+
+#[derive(Default)]
+struct Builder {
+    macrocell: Macrocell,
+    indices: HashMap<Node, usize>,
+    leaf_level: u32,
+}
+
+impl Builder {
+    /// Node index for the square of side `2^level` at `corner`, holding
+    /// `cells` in coordinates relative to the root.
+    fn build(&mut self, level: u32, corner: [i128; 2], cells: &mut [([i128; 2], u8)]) -> usize {
+        if cells.is_empty() {
+            return 0;
+        }
+
+        if level == self.leaf_level {
+            let node = if level == 1 {
+                let mut states = [0; 4];
+                for ([x, y], state) in cells.iter() {
+                    states[((y - corner[1]) * 2 + x - corner[0]) as usize] = *state;
+                }
+                Node::States(states)
+            } else {
+                Node::Leaf(cells.iter().fold(0, |bits, ([x, y], _)| {
+                    bits | 1 << ((y - corner[1]) * LEAF_SIDE + x - corner[0])
+                }))
+            };
+
+            return self.intern(node);
+        }
+
+        let half = 1i128 << (level - 1);
+        let quadrant = |[x, y]: [i128; 2]| {
+            usize::from(x - corner[0] >= half) + 2 * usize::from(y - corner[1] >= half)
+        };
+
+        cells.sort_unstable_by_key(|(point, _)| quadrant(*point));
+
+        let mut children = [0; 4];
+        let mut rest = cells;
+
+        for (index, child) in children.iter_mut().enumerate() {
+            let split = rest.partition_point(|(point, _)| quadrant(*point) == index);
+            let (inside, after) = rest.split_at_mut(split);
+
+            *child = self.build(level - 1, offset(corner, index, half), inside);
+            rest = after;
+        }
+
+        self.intern(Node::Branch { level, children })
+    }
+
+    fn intern(&mut self, node: Node) -> usize {
+        if let Some(index) = self.indices.get(&node) {
+            return *index;
+        }
+
+        self.macrocell.nodes.push(node.clone());
+        let index = self.macrocell.nodes.len();
+        self.indices.insert(node, index);
+        index
+    }
+}
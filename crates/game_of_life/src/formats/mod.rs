@@ -0,0 +1,253 @@
+use std::{collections::BTreeMap, fmt, io};
+
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+use crate::rule::GameOfLifeRule;
+
+pub mod apgcode;
+pub mod life;
+pub mod macrocell;
+pub mod plaintext;
+pub mod rle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    /// The pattern body starts before an `x = .., y = ..` header.
+    MissingHeader,
+    /// The header is present but cannot be read.
+    InvalidHeader(String),
+    /// The rule is not a Life-like rulestring.
+    InvalidRule(String),
+    /// A character that has no meaning at its position.
+    UnexpectedCharacter { line: usize, character: char },
+    /// A coordinate-list line that is not a pair of integers.
+    InvalidCoordinates { line: usize },
+    /// A quadtree node with the wrong arity, level or child indices.
+    InvalidNode { line: usize },
+    /// The input ended before the pattern terminator.
+    UnterminatedPattern,
+    /// A run that goes past the width or height given in the header.
+    OutOfBounds { line: usize },
+    /// The pattern holds more live cells than the reader stores.
+    TooManyCells(usize),
+    /// A cell state that the target lattice cannot hold.
+    UnsupportedState(u8),
+    /// Reading the input failed.
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "missing pattern header"),
+            Self::InvalidHeader(header) => write!(f, "invalid pattern header `{header}`"),
+            Self::InvalidRule(rule) => write!(f, "invalid rule `{rule}`"),
+            Self::UnexpectedCharacter { line, character } => {
+                write!(f, "unexpected character `{character}` on line {line}")
+            }
+            Self::InvalidCoordinates { line } => write!(f, "invalid coordinates on line {line}"),
+            Self::InvalidNode { line } => write!(f, "invalid quadtree node on line {line}"),
+            Self::UnterminatedPattern => write!(f, "pattern is not terminated"),
+            Self::OutOfBounds { line } => {
+                write!(f, "run on line {line} goes past the pattern size")
+            }
+            Self::TooManyCells(limit) => write!(f, "pattern has more than {limit} live cells"),
+            Self::UnsupportedState(state) => write!(f, "unsupported cell state {state}"),
+            Self::Io(kind) => write!(f, "failed to read input: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Format-independent pattern: a bounding box with the non-zero cells in it,
+/// plus the rule and comments carried by the file. The box's top-left corner
+/// is `origin`, which is negative for coordinate lists centred on zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    origin: UniversalLatticePoint<2>,
+    width: usize,
+    height: usize,
+    rule: Option<String>,
+    comments: Vec<String>,
+    cells: BTreeMap<UniversalLatticePoint<2>, u8>,
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl Pattern {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            origin: UniversalLatticePoint::new([0, 0]),
+            width,
+            height,
+            rule: None,
+            comments: Vec::new(),
+            cells: BTreeMap::new(),
+        }
+    }
+
+    /// Non-zero cells of a lattice; states are mapped with `Into<u8>`.
+    pub fn from_lattice<S>(lattice: &UniversalLattice<2, S>) -> Self
+    where
+        S: Clone + Default + Into<u8>,
+    {
+        let size = lattice.size();
+        let mut pattern = Self::new(size[0], size[1]);
+
+        for (point, state) in lattice.clone() {
+            pattern.set(&point, state.into());
+        }
+
+        pattern
+    }
+
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comments.push(comment.into());
+        self
+    }
+
+    pub fn origin(&self) -> UniversalLatticePoint<2> {
+        self.origin
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> UniversalLatticeSize<2> {
+        UniversalLatticeSize::new([self.width, self.height])
+    }
+
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    pub fn set_rule(&mut self, rule: Option<String>) {
+        self.rule = rule;
+    }
+
+    /// The pattern's rule as a Life-like rule, `B3/S23` when it has none.
+    pub fn life_rule(&self) -> Result<GameOfLifeRule, FormatError> {
+        match &self.rule {
+            Some(rule) => GameOfLifeRule::from_rulestring(rule)
+                .ok_or_else(|| FormatError::InvalidRule(rule.clone())),
+            None => Ok(GameOfLifeRule::default()),
+        }
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    pub fn get(&self, point: &UniversalLatticePoint<2>) -> u8 {
+        self.cells.get(point).copied().unwrap_or(0)
+    }
+
+    /// Sets a cell, growing the bounding box to contain it.
+    pub fn set(&mut self, point: &UniversalLatticePoint<2>, state: u8) {
+        if state == 0 {
+            self.cells.remove(point);
+            return;
+        }
+
+        let [x, y] = point.values();
+
+        if self.width == 0 || self.height == 0 {
+            self.origin = *point;
+            self.width = 1;
+            self.height = 1;
+        } else {
+            let [left, top] = self.origin.values();
+            let right = (left + self.width as i128).max(x + 1);
+            let bottom = (top + self.height as i128).max(y + 1);
+
+            self.origin = UniversalLatticePoint::new([left.min(x), top.min(y)]);
+            self.width = (right - self.origin[0]) as usize;
+            self.height = (bottom - self.origin[1]) as usize;
+        }
+
+        self.cells.insert(*point, state);
+    }
+
+    /// Points of the bounding box in row-major order.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<UniversalLatticePoint<2>>> + '_ {
+        let [left, top] = self.origin.values();
+
+        (0..self.height as i128).map(move |y| {
+            (0..self.width as i128)
+                .map(|x| UniversalLatticePoint::new([left + x, top + y]))
+                .collect()
+        })
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (&UniversalLatticePoint<2>, &u8)> {
+        self.cells.iter()
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Highest state plus one, at least two.
+    pub fn state_count(&self) -> usize {
+        self.cells
+            .values()
+            .max()
+            .map_or(2, |state| (*state as usize + 1).max(2))
+    }
+
+    /// A lattice of exactly the pattern's size.
+    pub fn to_lattice<S>(&self) -> Result<UniversalLattice<2, S>, FormatError>
+    where
+        S: Clone + Default + TryFrom<u8>,
+    {
+        let mut lattice = UniversalLattice::from(self.size());
+        self.place(&mut lattice, &UniversalLatticePoint::new([0, 0]))?;
+        Ok(lattice)
+    }
+
+    /// Writes the cells into an existing lattice with the bounding box's
+    /// top-left corner at `origin`. Points are passed through the lattice's boundary
+    /// handling, so a wrapping lattice wraps the pattern too.
+    pub fn place<S>(
+        &self,
+        lattice: &mut UniversalLattice<2, S>,
+        origin: &UniversalLatticePoint<2>,
+    ) -> Result<(), FormatError>
+    where
+        S: Clone + Default + TryFrom<u8>,
+    {
+        for (point, state) in &self.cells {
+            let converted =
+                S::try_from(*state).map_err(|_| FormatError::UnsupportedState(*state))?;
+            let [x, y] = point.values();
+            let [left, top] = self.origin.values();
+            let [origin_x, origin_y] = origin.values();
+
+            lattice.set_state(
+                &UniversalLatticePoint::new([origin_x + x - left, origin_y + y - top]),
+                &converted,
+            );
+        }
+
+        Ok(())
+    }
+}
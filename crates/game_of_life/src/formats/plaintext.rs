@@ -0,0 +1,60 @@
+//! Plaintext `.cells` patterns: `!` comment lines followed by rows of `.`
+//! for dead and `O` for alive cells.
+//!
+//! The format only has two states, so every non-zero state is written as `O`.
+
+use toolkit::aliases::UniversalLatticePoint;
+
+use super::{FormatError, Pattern};
+
+pub fn read_cells(text: &str) -> Result<Pattern, FormatError> {
+    let (comments, rows): (Vec<_>, Vec<_>) = text
+        .lines()
+        .map(str::trim_end)
+        .enumerate()
+        .partition(|(_, line)| line.starts_with('!'));
+
+    let width = rows.iter().map(|(_, row)| row.chars().count()).max();
+    let mut pattern = Pattern::new(width.unwrap_or(0), rows.len());
+
+    for (y, (index, row)) in rows.into_iter().enumerate() {
+        for (x, character) in row.chars().enumerate() {
+            match character {
+                '.' => {}
+                'O' | '*' => pattern.set(&UniversalLatticePoint::new([x as i128, y as i128]), 1),
+                _ => {
+                    return Err(FormatError::UnexpectedCharacter {
+                        line: index + 1,
+                        character,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(comments.into_iter().fold(pattern, |pattern, (_, comment)| {
+        pattern.with_comment(comment[1..].trim_start())
+    }))
+}
+
+pub fn write_cells(pattern: &Pattern) -> String {
+    let mut output = String::new();
+
+    for comment in pattern.comments() {
+        output.push('!');
+        output.push_str(comment);
+        output.push('\n');
+    }
+
+    for row in pattern.rows() {
+        let line: String = row
+            .iter()
+            .map(|point| if pattern.get(point) == 0 { '.' } else { 'O' })
+            .collect();
+
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
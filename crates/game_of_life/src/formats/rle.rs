@@ -0,0 +1,251 @@
+//! Run Length Encoded patterns as written by Golly and LifeWiki.
+//!
+//! Two-state bodies use `b`/`o`; multi-state bodies use `.` for state 0,
+//! `A`..`X` for 1..24 and a `p`..`y` prefix for every further block of 24.
+
+use toolkit::aliases::UniversalLatticePoint;
+
+use super::{FormatError, Pattern};
+
+const LINE_WIDTH: usize = 70;
+
+/// Most live cells [`read_rle`] stores. Runs are expanded cell by cell, so a
+/// short file could otherwise describe an arbitrarily large pattern.
+pub const MAX_LIVE_CELLS: usize = 1 << 24;
+
+pub fn read_rle(text: &str) -> Result<Pattern, FormatError> {
+    let mut lines = text.lines().enumerate();
+    let mut comments = Vec::new();
+    let mut legacy_rule = None;
+
+    let (mut pattern, rule) = loop {
+        let Some((_, line)) = lines.next() else {
+            return Err(FormatError::MissingHeader);
+        };
+        let line = line.trim();
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if let Some(rule) = comment.strip_prefix('r') {
+                legacy_rule = Some(rule.trim().to_string());
+            }
+            comments.push(comment.to_string());
+        } else if line.starts_with('x') {
+            break read_header(line)?;
+        } else if !line.is_empty() {
+            return Err(FormatError::MissingHeader);
+        }
+    };
+
+    pattern.set_rule(rule.or(legacy_rule));
+    for comment in comments {
+        pattern = pattern.with_comment(comment);
+    }
+
+    let (mut x, mut y) = (0i128, 0i128);
+    let mut count: Option<usize> = None;
+    let mut prefix: Option<char> = None;
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let unexpected = |character| FormatError::UnexpectedCharacter {
+            line: line_number,
+            character,
+        };
+
+        for character in line.chars() {
+            if let Some(high) = prefix.take() {
+                let state = multi_state(Some(high), character).ok_or(unexpected(character))?;
+                x = fill(&mut pattern, x, y, count.take(), state, line_number)?;
+                continue;
+            }
+
+            match character {
+                '0'..='9' => {
+                    let digit = character as usize - '0' as usize;
+                    count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+                }
+                'b' | '.' => x = fill(&mut pattern, x, y, count.take(), 0, line_number)?,
+                'o' => x = fill(&mut pattern, x, y, count.take(), 1, line_number)?,
+                'A'..='X' => {
+                    let state = multi_state(None, character).ok_or(unexpected(character))?;
+                    x = fill(&mut pattern, x, y, count.take(), state, line_number)?;
+                }
+                'p'..='y' => prefix = Some(character),
+                '$' => {
+                    y += count.take().unwrap_or(1) as i128;
+                    x = 0;
+                }
+                '!' => return Ok(pattern),
+                _ if character.is_whitespace() => {}
+                _ => return Err(unexpected(character)),
+            }
+        }
+    }
+
+    Err(FormatError::UnterminatedPattern)
+}
+
+pub fn write_rle(pattern: &Pattern) -> String {
+    let multi_state = pattern.state_count() > 2;
+    let mut output = String::new();
+
+    for comment in pattern.comments() {
+        output.push('#');
+        output.push_str(comment);
+        output.push('\n');
+    }
+
+    output.push_str(&format!(
+        "x = {}, y = {}",
+        pattern.width(),
+        pattern.height()
+    ));
+    if let Some(rule) = pattern.rule() {
+        output.push_str(&format!(", rule = {rule}"));
+    }
+    output.push('\n');
+
+    let mut writer = LineWriter::default();
+    let mut pending_rows = 0;
+
+    for points in pattern.rows() {
+        let row: Vec<u8> = points.iter().map(|point| pattern.get(point)).collect();
+        let length = row
+            .iter()
+            .rposition(|state| *state != 0)
+            .map_or(0, |last| last + 1);
+
+        if length > 0 && pending_rows > 0 {
+            writer.push(pending_rows, "$");
+            pending_rows = 0;
+        }
+
+        let mut start = 0;
+        while start < length {
+            let state = row[start];
+            let run = row[start..length]
+                .iter()
+                .take_while(|other| **other == state)
+                .count();
+
+            writer.push(run, &state_token(state, multi_state));
+            start += run;
+        }
+
+        pending_rows += 1;
+    }
+
+    writer.push(1, "!");
+    output.push_str(&writer.finish());
+    output.push('\n');
+    output
+}
+
+fn read_header(line: &str) -> Result<(Pattern, Option<String>), FormatError> {
+    let invalid = || FormatError::InvalidHeader(line.to_string());
+    let (mut width, mut height, mut rule) = (None, None, None);
+
+    for field in line.split(',') {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match key.trim() {
+            "x" => width = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            "rule" => rule = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((Pattern::new(width, height), rule)),
+        _ => Err(invalid()),
+    }
+}
+
+fn multi_state(high: Option<char>, low: char) -> Option<u8> {
+    if !low.is_ascii_uppercase() || low > 'X' {
+        return None;
+    }
+
+    let block = high.map_or(0, |high| high as u32 - 'p' as u32 + 1);
+    u8::try_from(block * 24 + low as u32 - 'A' as u32 + 1).ok()
+}
+
+fn state_token(state: u8, multi_state: bool) -> String {
+    match (state, multi_state) {
+        (0, false) => "b".to_string(),
+        (_, false) => "o".to_string(),
+        (0, true) => ".".to_string(),
+        (state, true) => {
+            let index = state as u32 - 1;
+            let low = char::from_u32('A' as u32 + index % 24).unwrap_or('A');
+
+            match index / 24 {
+                0 => low.to_string(),
+                block => {
+                    let high = char::from_u32('p' as u32 + block - 1).unwrap_or('p');
+                    format!("{high}{low}")
+                }
+            }
+        }
+    }
+}
+
+/// Sets `count` cells starting at `x` and returns the column after the run.
+/// Runs must stay inside the size given in the header.
+fn fill(
+    pattern: &mut Pattern,
+    x: i128,
+    y: i128,
+    count: Option<usize>,
+    state: u8,
+    line: usize,
+) -> Result<i128, FormatError> {
+    let count = count.unwrap_or(1);
+    let end = x + count as i128;
+
+    if end > pattern.width() as i128 || y >= pattern.height() as i128 {
+        return Err(FormatError::OutOfBounds { line });
+    }
+
+    if state != 0 {
+        if pattern.population().saturating_add(count) > MAX_LIVE_CELLS {
+            return Err(FormatError::TooManyCells(MAX_LIVE_CELLS));
+        }
+
+        for column in x..end {
+            pattern.set(&UniversalLatticePoint::new([column, y]), state);
+        }
+    }
+
+    Ok(end)
+}
+
+/// Joins run tokens into lines no longer than [`LINE_WIDTH`], never
+/// splitting a token from its count.
+#[derive(Default)]
+struct LineWriter {
+    lines: Vec<String>,
+    current: String,
+}
+
+impl LineWriter {
+    fn push(&mut self, count: usize, token: &str) {
+        let item = match count {
+            1 => token.to_string(),
+            count => format!("{count}{token}"),
+        };
+
+        if self.current.len() + item.len() > LINE_WIDTH {
+            self.lines.push(std::mem::take(&mut self.current));
+        }
+
+        self.current.push_str(&item);
+    }
+
+    fn finish(mut self) -> String {
+        self.lines.push(self.current);
+        self.lines.join("\n")
+    }
+}
@@ -2,3 +2,5 @@ pub mod state;
 pub mod rule;
 pub mod utils;
 pub mod automaton;
+pub mod formats;
+pub mod rule_table;
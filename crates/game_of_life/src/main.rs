@@ -1,53 +1,37 @@
 use game_of_life::{
-    automaton::{GameOfLifeAutomaton, GameOfLifeLattice, GameOfLifeLatticePoint, GameOfLifeLatticeSize},
-    rule::GameOfLifeRule,
-    state::GameOfLifeState,
+    automaton::GameOfLifeAutomaton, formats::rle::read_rle, state::GameOfLifeState,
 };
 use toolkit::{
-    types::BoundaryHandling,
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
 };
 
-pub fn main() {
-    let ca = GameOfLifeAutomaton::new(GameOfLifeRule::default());
-
-    let mut lattice2 = {
-        let mut states = GameOfLifeLattice::from_states(
-            vec![GameOfLifeState::Dead; 30 * 30],
-            GameOfLifeLatticeSize::new([30, 30].to_vec()),
-        );
-
-        states.set_state(&GameOfLifeLatticePoint::new([4, 0].to_vec()), GameOfLifeState::Alive);
-        states.set_state(&GameOfLifeLatticePoint::new([4, 1].to_vec()), GameOfLifeState::Alive);
-        states.set_state(&GameOfLifeLatticePoint::new([4, 2].to_vec()), GameOfLifeState::Alive);
-        states.set_state(&GameOfLifeLatticePoint::new([3, 2].to_vec()), GameOfLifeState::Alive);
-        states.set_state(&GameOfLifeLatticePoint::new([2, 1].to_vec()), GameOfLifeState::Alive);
-
-        states.set_boundary_handling(BoundaryHandling::Wrap);
+const GLIDER: &str = "\
+#N Glider
+x = 3, y = 3, rule = B3/S23
+2bo$obo$b2o!
+";
 
-        states
-    };
+pub fn main() {
+    let glider = read_rle(GLIDER).expect("glider pattern is valid");
+    let ca = GameOfLifeAutomaton::new(glider.life_rule().expect("glider rule is valid"));
 
-    let size = lattice2.size();
+    let mut lattice =
+        UniversalLattice::<2, GameOfLifeState>::from(UniversalLatticeSize::new([30, 30]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    glider
+        .place(&mut lattice, &UniversalLatticePoint::new([2, 0]))
+        .expect("glider states are two-state");
 
     for _ in 0..100 {
-        let mut s = String::new();
-
-        for y in 0..size.get(1).unwrap() {
-            for x in 0..size.get(0).unwrap() {
-                let state = lattice2.get_state(&GameOfLifeLatticePoint::new([x as isize, y as isize].to_vec()));
-
-                let ch = match state {
-                    GameOfLifeState::Alive => "█",
-                    GameOfLifeState::Dead => ".",
-                };
-
-                s += ch
-            }
-            s.push('\n');
-        }
+        let s = lattice.to_text(|state| match state {
+            GameOfLifeState::Alive => '█',
+            GameOfLifeState::Dead => '.',
+        });
 
         print!("{s}");
 
-        ca.step_wrapper(&mut lattice2);
+        ca.step(&mut lattice);
     }
 }
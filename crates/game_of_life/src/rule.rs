@@ -6,21 +6,18 @@ use crate::utils::{count_alive};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
-#[derive(Debug, Clone)]
+/// Outer-totalistic Life-like rule. Bit `n` of `birth` / `survival` is set
+/// when a cell with `n` alive neighbors is born / survives.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct GameOfLifeRule {
-    amount_to_underpopulation: u8,
-    amount_to_overpopulation: u8,
-    amount_to_reproduction: u8,
+    birth: u16,
+    survival: u16,
 }
 
 impl Default for GameOfLifeRule {
     fn default() -> Self {
-        Self {
-            amount_to_underpopulation: 2,
-            amount_to_overpopulation: 3,
-            amount_to_reproduction: 3,
-        }
+        Self::new(2, 3, 3)
     }
 }
 
@@ -32,12 +29,100 @@ impl GameOfLifeRule {
         amount_to_overpopulation: u8,
         amount_to_reproduction: u8,
     ) -> Self {
+        let survival = (amount_to_underpopulation..=amount_to_overpopulation)
+            .filter(|count| *count <= 8)
+            .fold(0, |mask, count| mask | 1 << count);
+
+        Self {
+            birth: 1u16.checked_shl(amount_to_reproduction as u32).unwrap_or(0) & 0x1FF,
+            survival,
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_masks(birth: u16, survival: u16) -> Self {
         Self {
-            amount_to_underpopulation,
-            amount_to_overpopulation,
-            amount_to_reproduction,
+            birth: birth & 0x1FF,
+            survival: survival & 0x1FF,
         }
     }
+
+    /// Parses `B3/S23`, `b3s23` or the classic survival-first `23/3` notation.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_rulestring(rulestring: &str) -> Option<GameOfLifeRule> {
+        let rulestring = rulestring.trim().to_ascii_uppercase();
+
+        let (birth, survival) = if rulestring.starts_with('B') || rulestring.starts_with('S') {
+            let mut birth = None;
+            let mut survival = None;
+            let mut current = None;
+
+            for ch in rulestring.chars() {
+                match ch {
+                    'B' => current = Some(birth.insert(0u16)),
+                    'S' => current = Some(survival.insert(0u16)),
+                    '/' | '_' => current = None,
+                    '0'..='8' => **current.as_mut()? |= 1 << ch.to_digit(10)?,
+                    _ => return None,
+                }
+            }
+
+            (birth?, survival?)
+        } else {
+            let (survival, birth) = rulestring.split_once('/')?;
+            (parse_counts(birth)?, parse_counts(survival)?)
+        };
+
+        Some(Self::from_masks(birth, survival))
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn birth(&self) -> u16 {
+        self.birth
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn survival(&self) -> u16 {
+        self.survival
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn rulestring(&self) -> String {
+        format!("B{}/S{}", format_counts(self.birth), format_counts(self.survival))
+    }
+}
+
+fn parse_counts(counts: &str) -> Option<u16> {
+    counts.chars().try_fold(0, |mask, ch| match ch {
+        '0'..='8' => Some(mask | 1 << ch.to_digit(10)?),
+        _ => None,
+    })
+}
+
+fn format_counts(mask: u16) -> String {
+    (0..=8)
+        .filter(|count| mask & (1 << count) != 0)
+        .map(|count| char::from(b'0' + count as u8))
+        .collect()
+}
+
+/// Serialized as its `B3/S23` rulestring.
+#[cfg(feature = "serde")]
+impl serde::Serialize for GameOfLifeRule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.rulestring())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GameOfLifeRule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rulestring = String::deserialize(deserializer)?;
+
+        Self::from_rulestring(&rulestring).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid Life-like rulestring `{rulestring}`"))
+        })
+    }
 }
 
 impl Rule for GameOfLifeRule {
@@ -48,25 +133,11 @@ impl Rule for GameOfLifeRule {
         current_state: &Self::State,
         neighbors: &impl Neighborhood<State = Self::State>,
     ) -> Self::State {
-        let alive_count = count_alive(neighbors) as u8;
+        let alive_count = count_alive(neighbors);
         let is_alive = *current_state == GameOfLifeState::Alive;
 
-        if is_alive {
-            if alive_count < self.amount_to_underpopulation {
-                return GameOfLifeState::Dead;
-            }
-
-            if alive_count > self.amount_to_overpopulation {
-                return GameOfLifeState::Dead;
-            }
-
-            return GameOfLifeState::Alive;
-        } else {
-            if alive_count == self.amount_to_reproduction {
-                return GameOfLifeState::Alive;
-            }
-        }
+        let mask = if is_alive { self.survival } else { self.birth };
 
-        return GameOfLifeState::Dead
+        GameOfLifeState::from(alive_count <= 8 && mask & (1 << alive_count) != 0)
     }
 }
@@ -0,0 +1,293 @@
+//! Golly `.rule` files: `@TABLE` transition tables with variables and
+//! symmetries, `@TREE` decision trees and `@COLORS`. Other sections such as
+//! `@ICONS` are skipped.
+
+use std::{collections::BTreeMap, fmt};
+
+use toolkit::{
+    automaton::update_scheme::UpdateScheme,
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::{
+        moore::{MooreNeighborhood, MooreNeighborhoodBuilder},
+        von_neumann::{VonNeumannNeighborhood, VonNeumannNeighborhoodBuilder},
+    },
+    prelude::*,
+};
+
+pub use table::Symmetry;
+use table::Table;
+use tree::Tree;
+
+mod table;
+mod tree;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleFileError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl RuleFileError {
+    fn new(line: usize, message: &str) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RuleFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on line {}", self.message, self.line)
+    }
+}
+
+impl std::error::Error for RuleFileError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableNeighborhood {
+    Moore,
+    VonNeumann,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Logic {
+    Table(Table),
+    Tree(Tree),
+}
+
+/// Rule loaded from a Golly `.rule` file over `u8` states. Neighbors are
+/// expected in the order of the toolkit's Moore or von Neumann builder, see
+/// [`TableRuleAutomaton`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRule {
+    name: String,
+    logic: Logic,
+    colors: BTreeMap<u8, [u8; 3]>,
+}
+
+impl TableRule {
+    pub fn parse(text: &str) -> Result<Self, RuleFileError> {
+        let mut name = String::new();
+        let mut sections: Vec<(&str, Vec<(usize, &str)>)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if let Some(header) = line.strip_prefix('@') {
+                let (section, argument) = header.split_once(' ').unwrap_or((header, ""));
+
+                if section == "RULE" {
+                    name = argument.trim().to_string();
+                }
+                sections.push((section, Vec::new()));
+            } else if let Some((_, lines)) = sections.last_mut().filter(|_| !line.is_empty()) {
+                lines.push((index + 1, line));
+            }
+        }
+
+        let section = |name: &str| {
+            sections
+                .iter()
+                .find(|(section, _)| *section == name)
+                .map(|(_, lines)| lines.as_slice())
+        };
+
+        let logic = match (section("TABLE"), section("TREE")) {
+            (Some(lines), _) => Logic::Table(Table::parse(lines)?),
+            (None, Some(lines)) => Logic::Tree(Tree::parse(lines)?),
+            (None, None) => {
+                return Err(RuleFileError::new(
+                    text.lines().count(),
+                    "no @TABLE or @TREE section",
+                ));
+            }
+        };
+
+        let mut rule = Self {
+            name,
+            logic,
+            colors: BTreeMap::new(),
+        };
+
+        if let Some(lines) = section("COLORS") {
+            rule.colors = parse_colors(lines, rule.states())?;
+        }
+
+        Ok(rule)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn states(&self) -> usize {
+        match &self.logic {
+            Logic::Table(table) => table.states,
+            Logic::Tree(tree) => tree.states,
+        }
+    }
+
+    pub fn neighborhood(&self) -> TableNeighborhood {
+        match &self.logic {
+            Logic::Table(table) => table.neighborhood,
+            Logic::Tree(tree) => tree.neighborhood,
+        }
+    }
+
+    /// Colors from `@COLORS`; states without one are absent.
+    pub fn colors(&self) -> &BTreeMap<u8, [u8; 3]> {
+        &self.colors
+    }
+}
+
+impl Rule for TableRule {
+    type State = u8;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        let mut states = [0u8; 8];
+        let mut count = 0;
+
+        for (slot, state) in states.iter_mut().zip(neighbors.iter_states()) {
+            *slot = *state;
+            count += 1;
+        }
+
+        let (expected, golly_order): (usize, &[usize]) = match self.neighborhood() {
+            TableNeighborhood::Moore => (8, &[1, 2, 4, 7, 6, 5, 3, 0]),
+            TableNeighborhood::VonNeumann => (4, &[2, 1, 3, 0]),
+        };
+
+        if count != expected {
+            return *current_state;
+        }
+
+        match &self.logic {
+            Logic::Table(table) => {
+                let ordered: Vec<u8> = golly_order.iter().map(|index| states[*index]).collect();
+                table.next(*current_state, &ordered)
+            }
+            Logic::Tree(tree) => tree.next(*current_state, &states[..count]),
+        }
+    }
+}
+
+/// `state r g b` lines, or `r1 g1 b1 r2 g2 b2` for a gradient over the live
+/// states.
+fn parse_colors(
+    lines: &[(usize, &str)],
+    states: usize,
+) -> Result<BTreeMap<u8, [u8; 3]>, RuleFileError> {
+    let mut colors = BTreeMap::new();
+
+    for &(line, text) in lines {
+        let values: Vec<u8> = text
+            .split_whitespace()
+            .map(|value| value.parse().ok())
+            .collect::<Option<_>>()
+            .ok_or_else(|| RuleFileError::new(line, "malformed color"))?;
+
+        match values[..] {
+            [state, r, g, b] if (state as usize) < states => {
+                colors.insert(state, [r, g, b]);
+            }
+            [r1, g1, b1, r2, g2, b2] => {
+                let span = states.saturating_sub(2).max(1) as f32;
+
+                for state in 1..states {
+                    let t = (state - 1) as f32 / span;
+                    let mix = |from: u8, to: u8| {
+                        (from as f32 + (to as f32 - from as f32) * t).round() as u8
+                    };
+
+                    colors.insert(state as u8, [mix(r1, r2), mix(g1, g2), mix(b1, b2)]);
+                }
+            }
+            _ => return Err(RuleFileError::new(line, "malformed color")),
+        }
+    }
+
+    Ok(colors)
+}
+
+#[derive(Debug)]
+pub enum TableNeighborhoodStates<S> {
+    Moore(MooreNeighborhood<S>),
+    VonNeumann(VonNeumannNeighborhood<S>),
+}
+
+impl<S> Neighborhood for TableNeighborhoodStates<S> {
+    type State = S;
+    type Iter<'a>
+        = std::slice::Iter<'a, S>
+    where
+        S: 'a;
+
+    fn iter_states(&self) -> Self::Iter<'_> {
+        match self {
+            Self::Moore(neighborhood) => neighborhood.iter_states(),
+            Self::VonNeumann(neighborhood) => neighborhood.iter_states(),
+        }
+    }
+}
+
+impl<S: Clone + Default> NeighborhoodBuilder<UniversalLattice<2, S>> for TableNeighborhood {
+    type Neighborhood = TableNeighborhoodStates<S>;
+
+    fn build_neighborhood(
+        &self,
+        point: &<UniversalLattice<2, S> as Lattice>::Point,
+        lattice: &UniversalLattice<2, S>,
+    ) -> Self::Neighborhood {
+        match self {
+            Self::Moore => TableNeighborhoodStates::Moore(
+                MooreNeighborhoodBuilder.build_neighborhood(point, lattice),
+            ),
+            Self::VonNeumann => TableNeighborhoodStates::VonNeumann(
+                VonNeumannNeighborhoodBuilder.build_neighborhood(point, lattice),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TableRuleAutomaton {
+    rule: TableRule,
+    update_scheme: UpdateScheme,
+}
+
+impl TableRuleAutomaton {
+    pub fn new(rule: TableRule) -> Self {
+        Self {
+            rule,
+            update_scheme: UpdateScheme::default(),
+        }
+    }
+
+    pub fn with_update_scheme(mut self, update_scheme: UpdateScheme) -> Self {
+        self.update_scheme = update_scheme;
+        self
+    }
+}
+
+impl CellularAutomaton for TableRuleAutomaton {
+    type Lattice = UniversalLattice<2, u8>;
+    type Rule = TableRule;
+    type NeighborhoodBuilder = TableNeighborhood;
+
+    fn rule(&self) -> &Self::Rule {
+        &self.rule
+    }
+
+    fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+        self.rule.neighborhood()
+    }
+
+    fn update_scheme(&self) -> UpdateScheme {
+        self.update_scheme
+    }
+}
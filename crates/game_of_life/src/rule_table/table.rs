@@ -0,0 +1,446 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{RuleFileError, TableNeighborhood};
+
+/// How each transition is expanded before matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    Rotate4,
+    Rotate8,
+    /// Mirror image across the vertical axis, `reflect_horizontal` in Golly.
+    Reflect,
+    Rotate4Reflect,
+    Rotate8Reflect,
+    /// Any arrangement of the neighbor states.
+    Permute,
+}
+
+impl Symmetry {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "none" => Self::None,
+            "rotate4" => Self::Rotate4,
+            "rotate8" => Self::Rotate8,
+            "reflect" | "reflect_horizontal" => Self::Reflect,
+            "rotate4reflect" => Self::Rotate4Reflect,
+            "rotate8reflect" => Self::Rotate8Reflect,
+            "permute" => Self::Permute,
+            _ => return None,
+        })
+    }
+
+    /// Index maps `new[i] = old[map[i]]` over neighbors in Golly's clockwise
+    /// order starting at north. `None` when the neighborhood has no such
+    /// symmetry or for [`Symmetry::Permute`], which is matched on neighbor
+    /// state counts instead.
+    fn maps(self, neighbors: usize) -> Option<Vec<Vec<usize>>> {
+        let rotations = |step: usize| -> Vec<Vec<usize>> {
+            (0..neighbors / step)
+                .map(|turn| {
+                    (0..neighbors)
+                        .map(|i| (i + turn * step) % neighbors)
+                        .collect()
+                })
+                .collect()
+        };
+        let with_reflections = |maps: Vec<Vec<usize>>| -> Vec<Vec<usize>> {
+            let reflected: Vec<Vec<usize>> = maps
+                .iter()
+                .map(|map| map.iter().map(|i| (neighbors - i) % neighbors).collect())
+                .collect();
+            maps.into_iter().chain(reflected).collect()
+        };
+        let quarter = neighbors / 4;
+
+        match self {
+            Self::None => Some(rotations(neighbors)),
+            Self::Rotate4 => Some(rotations(quarter)),
+            Self::Rotate8 if neighbors == 8 => Some(rotations(1)),
+            Self::Reflect => Some(with_reflections(rotations(neighbors))),
+            Self::Rotate4Reflect => Some(with_reflections(rotations(quarter))),
+            Self::Rotate8Reflect if neighbors == 8 => Some(with_reflections(rotations(1))),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Term {
+    State(u8),
+    Variable(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Transition {
+    center: Term,
+    neighbors: Vec<Term>,
+    next: Term,
+}
+
+/// `@TABLE` section: transitions tried in file order, the first match wins
+/// and cells without a match keep their state. A variable that appears more
+/// than once in a transition takes the same value everywhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Table {
+    pub(crate) states: usize,
+    pub(crate) neighborhood: TableNeighborhood,
+    variables: Vec<[u64; 4]>,
+    /// Whether neighbors match in any order; their terms are then kept sorted.
+    permute: bool,
+    /// Expanded transitions grouped by the center states they accept.
+    by_center: Vec<Vec<Transition>>,
+}
+
+impl Table {
+    pub(crate) fn parse(lines: &[(usize, &str)]) -> Result<Self, RuleFileError> {
+        let mut states = None;
+        let mut neighborhood = None;
+        let mut symmetry = Symmetry::None;
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut variables: Vec<[u64; 4]> = Vec::new();
+        let mut transitions: Vec<Transition> = Vec::new();
+        let mut seen: HashSet<Transition> = HashSet::new();
+
+        for &(line, text) in lines {
+            if let Some((key, value)) = text.split_once(':') {
+                let value = value.trim();
+
+                match key.trim() {
+                    "n_states" => {
+                        states = value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|states| (2..=256).contains(states));
+                        if states.is_none() {
+                            return Err(RuleFileError::new(
+                                line,
+                                "n_states must be between 2 and 256",
+                            ));
+                        }
+                    }
+                    "neighborhood" => {
+                        neighborhood = Some(match value {
+                            "Moore" => TableNeighborhood::Moore,
+                            "vonNeumann" => TableNeighborhood::VonNeumann,
+                            _ => {
+                                return Err(RuleFileError {
+                                    line,
+                                    message: format!("unsupported neighborhood `{value}`"),
+                                });
+                            }
+                        });
+                    }
+                    "symmetries" => {
+                        symmetry = Symmetry::parse(value).ok_or_else(|| RuleFileError {
+                            line,
+                            message: format!("unknown symmetry `{value}`"),
+                        })?;
+                    }
+                    key => {
+                        return Err(RuleFileError {
+                            line,
+                            message: format!("unknown table setting `{key}`"),
+                        });
+                    }
+                }
+
+                continue;
+            }
+
+            let states = states.ok_or_else(|| RuleFileError::new(line, "missing n_states"))?;
+            let neighborhood =
+                neighborhood.ok_or_else(|| RuleFileError::new(line, "missing neighborhood"))?;
+
+            if let Some(definition) = text.strip_prefix("var ") {
+                let (name, values) = parse_variable(definition, &names, &variables, states)
+                    .map_err(|message| RuleFileError { line, message })?;
+
+                names.insert(name, variables.len());
+                variables.push(values);
+                continue;
+            }
+
+            let transition = parse_transition(text, &names, neighborhood.len(), states)
+                .map_err(|message| RuleFileError { line, message })?;
+            let expanded: Vec<Transition> = match symmetry.maps(neighborhood.len()) {
+                Some(maps) => maps
+                    .iter()
+                    .map(|map| Transition {
+                        neighbors: map.iter().map(|i| transition.neighbors[*i]).collect(),
+                        ..transition.clone()
+                    })
+                    .collect(),
+                None if symmetry == Symmetry::Permute => {
+                    let mut transition = transition;
+                    transition.neighbors.sort();
+                    vec![transition]
+                }
+                None => {
+                    return Err(RuleFileError::new(
+                        line,
+                        "symmetry does not apply to this neighborhood",
+                    ));
+                }
+            };
+
+            for transition in expanded {
+                if seen.insert(transition.clone()) {
+                    transitions.push(transition);
+                }
+            }
+        }
+
+        let last_line = lines.last().map_or(0, |(line, _)| *line);
+        let states = states.ok_or_else(|| RuleFileError::new(last_line, "missing n_states"))?;
+        let neighborhood =
+            neighborhood.ok_or_else(|| RuleFileError::new(last_line, "missing neighborhood"))?;
+
+        let mut by_center = vec![Vec::new(); states];
+        for transition in transitions {
+            for (center, group) in by_center.iter_mut().enumerate() {
+                if accepts(&variables, transition.center, center as u8) {
+                    group.push(transition.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            states,
+            neighborhood,
+            variables,
+            permute: symmetry == Symmetry::Permute,
+            by_center,
+        })
+    }
+
+    /// Next state for `center` with neighbors in Golly order.
+    pub(crate) fn next(&self, center: u8, neighbors: &[u8]) -> u8 {
+        let Some(transitions) = self.by_center.get(center as usize) else {
+            return center;
+        };
+
+        transitions
+            .iter()
+            .find_map(|transition| self.apply(transition, center, neighbors))
+            .unwrap_or(center)
+    }
+
+    fn apply(&self, transition: &Transition, center: u8, neighbors: &[u8]) -> Option<u8> {
+        let mut bindings: Vec<(usize, u8)> = Vec::with_capacity(neighbors.len() + 1);
+
+        if !self.bind(&mut bindings, transition.center, center) {
+            return None;
+        }
+
+        let matched = if self.permute {
+            self.bind_unordered(&mut bindings, &transition.neighbors, neighbors)
+        } else {
+            let mut inputs = transition.neighbors.iter().zip(neighbors);
+            inputs.all(|(term, state)| self.bind(&mut bindings, *term, *state))
+        };
+
+        if !matched {
+            return None;
+        }
+
+        match transition.next {
+            Term::State(state) => Some(state),
+            Term::Variable(variable) => bound(&bindings, variable),
+        }
+    }
+
+    /// Matches `state` against `term`, binding a variable on first use.
+    fn bind(&self, bindings: &mut Vec<(usize, u8)>, term: Term, state: u8) -> bool {
+        match term {
+            Term::State(expected) => expected == state,
+            Term::Variable(variable) => match bound(bindings, variable) {
+                Some(value) => value == state,
+                None if accepts(&self.variables, term, state) => {
+                    bindings.push((variable, state));
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Matches sorted `terms` against `neighbors` in any order by counting
+    /// states, as Golly does for `permute`: fixed and bound terms take their
+    /// state from the counts, then each free variable claims one state for
+    /// all of its occurrences.
+    fn bind_unordered(
+        &self,
+        bindings: &mut Vec<(usize, u8)>,
+        terms: &[Term],
+        neighbors: &[u8],
+    ) -> bool {
+        let mut counts: Vec<(u8, usize)> = Vec::with_capacity(neighbors.len());
+        for state in neighbors {
+            match counts.iter_mut().find(|(counted, _)| counted == state) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*state, 1)),
+            }
+        }
+        counts.sort();
+
+        let mut free = Vec::new();
+        for run in terms.chunk_by(|a, b| a == b) {
+            let state = match run[0] {
+                Term::State(state) => state,
+                Term::Variable(variable) => match bound(bindings, variable) {
+                    Some(state) => state,
+                    None => {
+                        free.push((variable, run.len()));
+                        continue;
+                    }
+                },
+            };
+
+            match counts.iter_mut().find(|(counted, _)| *counted == state) {
+                Some((_, count)) if *count >= run.len() => *count -= run.len(),
+                _ => return false,
+            }
+        }
+
+        self.claim(bindings, &free, &mut counts)
+    }
+
+    /// Binds each `(variable, occurrences)` to a state with enough neighbors
+    /// left, backtracking when a later variable finds none.
+    fn claim(
+        &self,
+        bindings: &mut Vec<(usize, u8)>,
+        free: &[(usize, usize)],
+        counts: &mut [(u8, usize)],
+    ) -> bool {
+        let Some((&(variable, occurrences), rest)) = free.split_first() else {
+            return true;
+        };
+
+        for index in 0..counts.len() {
+            let (state, count) = counts[index];
+            if count < occurrences || !accepts(&self.variables, Term::Variable(variable), state) {
+                continue;
+            }
+
+            counts[index].1 -= occurrences;
+            bindings.push((variable, state));
+            if self.claim(bindings, rest, counts) {
+                return true;
+            }
+            bindings.pop();
+            counts[index].1 += occurrences;
+        }
+
+        false
+    }
+}
+
+fn bound(bindings: &[(usize, u8)], variable: usize) -> Option<u8> {
+    bindings
+        .iter()
+        .find(|(bound, _)| *bound == variable)
+        .map(|(_, value)| *value)
+}
+
+impl TableNeighborhood {
+    fn len(self) -> usize {
+        match self {
+            Self::Moore => 8,
+            Self::VonNeumann => 4,
+        }
+    }
+}
+
+fn accepts(variables: &[[u64; 4]], term: Term, state: u8) -> bool {
+    match term {
+        Term::State(expected) => expected == state,
+        Term::Variable(variable) => {
+            variables[variable][state as usize / 64] & (1 << (state % 64)) != 0
+        }
+    }
+}
+
+/// `name={0,1,other}`; values may name earlier variables.
+fn parse_variable(
+    definition: &str,
+    names: &HashMap<String, usize>,
+    variables: &[[u64; 4]],
+    states: usize,
+) -> Result<(String, [u64; 4]), String> {
+    let (name, values) = definition
+        .split_once('=')
+        .ok_or_else(|| "variable has no values".to_string())?;
+    let values = values
+        .trim()
+        .strip_prefix('{')
+        .and_then(|values| values.strip_suffix('}'))
+        .ok_or_else(|| "variable values must be in braces".to_string())?;
+
+    let mut set = [0u64; 4];
+    for value in values.split(',').map(str::trim) {
+        match parse_term(value, names, states)? {
+            Term::State(state) => set[state as usize / 64] |= 1 << (state % 64),
+            Term::Variable(variable) => {
+                (0..4).for_each(|word| set[word] |= variables[variable][word]);
+            }
+        }
+    }
+
+    Ok((name.trim().to_string(), set))
+}
+
+/// Comma-separated terms, or bare digits when every state is a single digit.
+fn parse_transition(
+    text: &str,
+    names: &HashMap<String, usize>,
+    neighbors: usize,
+    states: usize,
+) -> Result<Transition, String> {
+    let terms: Vec<Term> = if text.contains(',') {
+        text.split(',')
+            .map(|term| parse_term(term.trim(), names, states))
+            .collect::<Result<_, _>>()?
+    } else {
+        text.chars()
+            .filter(|character| !character.is_whitespace())
+            .map(|character| parse_term(&character.to_string(), names, states))
+            .collect::<Result<_, _>>()?
+    };
+
+    if terms.len() != neighbors + 2 {
+        return Err(format!(
+            "transition has {} terms instead of {}",
+            terms.len(),
+            neighbors + 2
+        ));
+    }
+
+    let transition = Transition {
+        center: terms[0],
+        neighbors: terms[1..=neighbors].to_vec(),
+        next: terms[neighbors + 1],
+    };
+
+    if let Term::Variable(variable) = transition.next {
+        let bound = terms[..=neighbors].contains(&Term::Variable(variable));
+        if !bound {
+            return Err("output variable does not appear in the inputs".to_string());
+        }
+    }
+
+    Ok(transition)
+}
+
+fn parse_term(term: &str, names: &HashMap<String, usize>, states: usize) -> Result<Term, String> {
+    if let Some(variable) = names.get(term) {
+        return Ok(Term::Variable(*variable));
+    }
+
+    term.parse::<usize>()
+        .ok()
+        .filter(|state| *state < states)
+        .map(|state| Term::State(state as u8))
+        .ok_or_else(|| format!("`{term}` is neither a state nor a variable"))
+}
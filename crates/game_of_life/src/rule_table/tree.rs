@@ -0,0 +1,122 @@
+use super::{RuleFileError, TableNeighborhood};
+
+/// `@TREE` section: a decision tree with one level per input, read in the
+/// order `nw, ne, sw, se, n, w, e, s, c` for Moore and `n, w, e, s, c` for
+/// von Neumann. Level-1 nodes hold the next state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tree {
+    pub(crate) states: usize,
+    pub(crate) neighborhood: TableNeighborhood,
+    nodes: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    pub(crate) fn parse(lines: &[(usize, &str)]) -> Result<Self, RuleFileError> {
+        let mut states = None;
+        let mut neighbors = None;
+        let mut nodes: Vec<Vec<usize>> = Vec::new();
+        let mut levels: Vec<usize> = Vec::new();
+
+        for &(line, text) in lines {
+            if let Some((key, value)) = text.split_once('=') {
+                let value = value.trim().parse::<usize>().map_err(|_| RuleFileError {
+                    line,
+                    message: format!("`{}` is not a number", value.trim()),
+                })?;
+
+                match key.trim() {
+                    "num_states" if (2..=256).contains(&value) => states = Some(value),
+                    "num_states" => {
+                        return Err(RuleFileError::new(
+                            line,
+                            "num_states must be between 2 and 256",
+                        ));
+                    }
+                    "num_neighbors" => neighbors = Some(value),
+                    "num_nodes" => nodes.reserve(value),
+                    key => {
+                        return Err(RuleFileError {
+                            line,
+                            message: format!("unknown tree setting `{key}`"),
+                        });
+                    }
+                }
+
+                continue;
+            }
+
+            let states = states.ok_or_else(|| RuleFileError::new(line, "missing num_states"))?;
+            let values: Vec<usize> = text
+                .split_whitespace()
+                .map(|value| value.parse().ok())
+                .collect::<Option<_>>()
+                .filter(|values: &Vec<usize>| values.len() == states + 1)
+                .ok_or_else(|| RuleFileError::new(line, "malformed tree node"))?;
+
+            let level = values[0];
+            let valid = match level {
+                0 => false,
+                1 => values[1..].iter().all(|state| *state < states),
+                _ => values[1..]
+                    .iter()
+                    .all(|child| levels.get(*child) == Some(&(level - 1))),
+            };
+
+            if !valid {
+                return Err(RuleFileError::new(
+                    line,
+                    "tree node refers to an invalid child",
+                ));
+            }
+
+            levels.push(level);
+            nodes.push(values[1..].to_vec());
+        }
+
+        let last_line = lines.last().map_or(0, |(line, _)| *line);
+        let states = states.ok_or_else(|| RuleFileError::new(last_line, "missing num_states"))?;
+        let neighborhood = match neighbors {
+            Some(8) => TableNeighborhood::Moore,
+            Some(4) => TableNeighborhood::VonNeumann,
+            _ => {
+                return Err(RuleFileError::new(
+                    last_line,
+                    "num_neighbors must be 4 or 8",
+                ));
+            }
+        };
+
+        if levels.last() != neighbors.map(|neighbors| neighbors + 1).as_ref() {
+            return Err(RuleFileError::new(
+                last_line,
+                "tree root has the wrong level",
+            ));
+        }
+
+        Ok(Self {
+            states,
+            neighborhood,
+            nodes,
+        })
+    }
+
+    /// Next state for `center` with neighbors in builder order.
+    pub(crate) fn next(&self, center: u8, neighbors: &[u8]) -> u8 {
+        let order: &[usize] = match self.neighborhood {
+            TableNeighborhood::Moore => &[0, 2, 5, 7, 1, 3, 4, 6],
+            TableNeighborhood::VonNeumann => &[2, 0, 1, 3],
+        };
+
+        let inputs = order.iter().map(|index| neighbors[*index]).chain([center]);
+        let mut value = self.nodes.len() - 1;
+
+        for input in inputs {
+            match self.nodes[value].get(input as usize) {
+                Some(next) => value = *next,
+                None => return center,
+            }
+        }
+
+        value as u8
+    }
+}
@@ -1,7 +1,10 @@
+use toolkit::lattice::snapshot::SnapshotState;
+
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum GameOfLifeState {
     #[default]
@@ -27,3 +30,33 @@ impl From<bool> for GameOfLifeState {
         }
     }
 }
+
+impl From<GameOfLifeState> for u8 {
+    fn from(value: GameOfLifeState) -> Self {
+        value as u8
+    }
+}
+
+impl TryFrom<u8> for GameOfLifeState {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Dead),
+            1 => Ok(Self::Alive),
+            other => Err(other),
+        }
+    }
+}
+
+impl SnapshotState for GameOfLifeState {
+    const WIDTH: usize = 1;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(u8::from(*self));
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(*bytes.first()?).ok()
+    }
+}
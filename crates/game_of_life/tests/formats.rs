@@ -0,0 +1,255 @@
+use game_of_life::formats::{
+    FormatError, Pattern,
+    apgcode::{read_apgcode, write_apgcode},
+    life::{read_life_105, read_life_106, write_life_105, write_life_106},
+    macrocell::{Macrocell, read_macrocell, write_macrocell},
+    plaintext::{read_cells, write_cells},
+    rle::{MAX_LIVE_CELLS, read_rle, write_rle},
+};
+use toolkit::aliases::UniversalLatticePoint;
+
+fn cells(pattern: &Pattern) -> Vec<([i128; 2], u8)> {
+    pattern
+        .cells()
+        .map(|(point, state)| (point.values(), *state))
+        .collect()
+}
+
+fn glider() -> Pattern {
+    [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]].into_iter().fold(
+        Pattern::new(3, 3),
+        |mut pattern, point| {
+            pattern.set(&UniversalLatticePoint::new(point), 1);
+            pattern
+        },
+    )
+}
+
+#[test]
+fn rle_round_trip() {
+    let pattern = glider().with_rule("B3/S23").with_comment("N Glider");
+    let text = write_rle(&pattern);
+
+    assert_eq!(text, "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    assert_eq!(read_rle(&text), Ok(pattern));
+}
+
+#[test]
+fn rle_multi_state_round_trip() {
+    let mut pattern = Pattern::new(4, 2);
+    pattern.set(&UniversalLatticePoint::new([0, 0]), 2);
+    pattern.set(&UniversalLatticePoint::new([3, 0]), 25);
+    pattern.set(&UniversalLatticePoint::new([1, 1]), 255);
+
+    let text = write_rle(&pattern);
+
+    assert_eq!(text, "x = 4, y = 2\nB2.pA$.yO!\n");
+    assert_eq!(read_rle(&text), Ok(pattern));
+}
+
+#[test]
+fn rle_rejects_malformed_input() {
+    assert_eq!(read_rle("bo$2bo$3o!"), Err(FormatError::MissingHeader));
+    assert!(matches!(
+        read_rle("x = 3\nbo!"),
+        Err(FormatError::InvalidHeader(_))
+    ));
+    assert_eq!(
+        read_rle("x = 3, y = 3\nbo$2bo$3o"),
+        Err(FormatError::UnterminatedPattern)
+    );
+    assert_eq!(
+        read_rle("x = 3, y = 3\nbo$2bq!"),
+        Err(FormatError::UnexpectedCharacter {
+            line: 2,
+            character: '!'
+        })
+    );
+}
+
+#[test]
+fn rle_runs_stay_inside_the_header_size() {
+    let out_of_bounds = Err(FormatError::OutOfBounds { line: 2 });
+
+    assert_eq!(read_rle("x = 1, y = 1\n99999999999o!"), out_of_bounds);
+    assert_eq!(read_rle("x = 3, y = 3\nb3o!"), out_of_bounds);
+    assert_eq!(read_rle("x = 3, y = 1\n3o$o!"), out_of_bounds);
+    assert_eq!(
+        read_rle("x = 3, y = 3\n3o$$3o$!").map(|pattern| pattern.population()),
+        Ok(6)
+    );
+}
+
+#[test]
+fn rle_caps_live_cells() {
+    let text = format!("x = {0}, y = 1\n{0}o!", MAX_LIVE_CELLS + 1);
+
+    assert_eq!(
+        read_rle(&text),
+        Err(FormatError::TooManyCells(MAX_LIVE_CELLS))
+    );
+}
+
+#[test]
+fn cells_round_trip() {
+    let pattern = glider().with_comment("Name: Glider");
+    let text = write_cells(&pattern);
+
+    assert_eq!(text, "!Name: Glider\n.O.\n..O\nOOO\n");
+    assert_eq!(read_cells(&text), Ok(pattern));
+}
+
+#[test]
+fn life_105_round_trip() {
+    let text = "#Life 1.05\n#D Glider\n#R 23/3\n#P -1 -1\n.*\n..*\n***\n";
+    let pattern = read_life_105(text).unwrap();
+
+    assert_eq!(pattern.origin().values(), [-1, -1]);
+    assert_eq!(pattern.life_rule().unwrap().rulestring(), "B3/S23");
+    assert_eq!(write_life_105(&pattern), text);
+}
+
+#[test]
+fn life_106_round_trip() {
+    let text = "#Life 1.06\n-1 -1\n0 0\n1 0\n-1 1\n0 1\n";
+    let pattern = read_life_106(text).unwrap();
+
+    assert_eq!(pattern.origin().values(), [-1, -1]);
+    assert_eq!((pattern.width(), pattern.height()), (3, 3));
+    assert_eq!(read_life_106(&write_life_106(&pattern)), Ok(pattern));
+    assert_eq!(
+        read_life_106("#Life 1.06\n0 x\n"),
+        Err(FormatError::InvalidCoordinates { line: 2 })
+    );
+}
+
+#[test]
+fn coordinate_lists_agree() {
+    let from_105 = read_life_105(&write_life_105(&glider())).unwrap();
+    let from_106 = read_life_106(&write_life_106(&glider())).unwrap();
+
+    assert_eq!(cells(&from_105), cells(&glider()));
+    assert_eq!(cells(&from_106), cells(&glider()));
+}
+
+#[test]
+fn apgcode_round_trip() {
+    let glider = read_apgcode("xq4_153").unwrap();
+
+    assert_eq!(
+        cells(&glider),
+        vec![
+            ([0, 0], 1),
+            ([1, 0], 1),
+            ([1, 2], 1),
+            ([2, 0], 1),
+            ([2, 1], 1)
+        ]
+    );
+    assert_eq!(write_apgcode(&glider, "xq4"), "xq4_153");
+
+    for code in [
+        "xs4_33",
+        "xp2_7",
+        "xs8_6996",
+        "xq4_27deee6",
+        "xp15_4r4z4r4",
+        "xs6_303",
+        "xs6_3w3",
+        "xs6_3x3",
+        "xs6_3yc3",
+    ] {
+        assert_eq!(
+            write_apgcode(
+                &read_apgcode(code).unwrap(),
+                &code[..code.find('_').unwrap()]
+            ),
+            code
+        );
+    }
+}
+
+#[test]
+fn apgcode_rejects_malformed_input() {
+    assert!(matches!(
+        read_apgcode("153"),
+        Err(FormatError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read_apgcode("yl_153"),
+        Err(FormatError::InvalidHeader(_))
+    ));
+    assert_eq!(
+        read_apgcode("xs4_3!"),
+        Err(FormatError::UnexpectedCharacter {
+            line: 1,
+            character: '!'
+        })
+    );
+}
+
+#[test]
+fn macrocell_round_trip() {
+    let pattern = glider().with_rule("B3/S23");
+    let macrocell = Macrocell::from_pattern(&pattern);
+    let mut text = Vec::new();
+    write_macrocell(&macrocell, &mut text).unwrap();
+
+    assert_eq!(
+        String::from_utf8(text.clone()).unwrap(),
+        "[M2]\n#R B3/S23\n.*$..*$***$\n"
+    );
+    assert_eq!(read_macrocell(&text[..]), Ok(macrocell));
+
+    let mut pattern = Pattern::default();
+    pattern.set(&UniversalLatticePoint::new([-3, 0]), 2);
+    pattern.set(&UniversalLatticePoint::new([4, 5]), 7);
+    let macrocell = Macrocell::from_pattern(&pattern);
+    let mut text = Vec::new();
+    write_macrocell(&macrocell, &mut text).unwrap();
+
+    assert_eq!(macrocell.level(), 3);
+    assert_eq!(
+        cells(&read_macrocell(&text[..]).unwrap().to_pattern()),
+        vec![([0, 0], 2), ([7, 5], 7)]
+    );
+}
+
+#[test]
+fn deep_macrocell_populations_do_not_overflow() {
+    // A full 8×8 leaf under nodes that repeat their child four times.
+    let mut text = "[M2]\n".to_string() + &"********$".repeat(8) + "\n";
+    for level in 4..=64 {
+        let child = level - 3;
+        text += &format!("{level} {child} {child} {child} {child}\n");
+    }
+
+    let macrocell = read_macrocell(text.as_bytes()).unwrap();
+    assert_eq!(macrocell.level(), 64);
+    assert_eq!(macrocell.population(), None);
+
+    let lines: Vec<&str> = text.lines().collect();
+    let shallower = read_macrocell(lines[..lines.len() - 1].join("\n").as_bytes()).unwrap();
+    assert_eq!(shallower.population(), Some(1 << 126));
+}
+
+#[test]
+fn macrocell_shares_repeated_subtrees() {
+    let blocks = (0..64).flat_map(|index| {
+        [[0, 0], [1, 0], [0, 1], [1, 1]].map(|[x, y]| {
+            (
+                UniversalLatticePoint::new([index % 8 * 256 + x, index / 8 * 256 + y]),
+                1,
+            )
+        })
+    });
+    let macrocell = Macrocell::from_cells(blocks);
+
+    assert_eq!(macrocell.population(), Some(256));
+    assert_eq!(macrocell.level(), 11);
+    assert!(macrocell.node_count() < 20);
+    assert_eq!(
+        read_macrocell("[M2]\n4 1 0 0 0\n".as_bytes()),
+        Err(FormatError::InvalidNode { line: 2 })
+    );
+}
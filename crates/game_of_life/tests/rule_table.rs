@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use game_of_life::{
+    automaton::GameOfLifeAutomaton,
+    rule::GameOfLifeRule,
+    rule_table::{RuleFileError, TableNeighborhood, TableRule, TableRuleAutomaton},
+    state::GameOfLifeState,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+const LIFE_TABLE: &str = "\
+@RULE Life
+@TABLE
+n_states:2
+neighborhood:Moore
+symmetries:permute
+var a={0,1}
+var b={0,1}
+var c={0,1}
+var d={0,1}
+var e={0,1}
+var f={0,1}
+var g={0,1}
+var h={0,1}
+0,1,1,1,0,0,0,0,0,1
+1,1,1,0,0,0,0,0,0,1
+1,1,1,1,0,0,0,0,0,1
+1,a,b,c,d,e,f,g,h,0
+@COLORS
+0 48 48 48
+1 255 255 255
+";
+
+/// `@TREE` section computing `next` from the inputs in tree order, with
+/// equal nodes shared.
+fn tree(neighbors: usize, next: impl Fn(&[u8]) -> u8) -> String {
+    fn build(
+        prefix: &mut Vec<u8>,
+        neighbors: usize,
+        next: &impl Fn(&[u8]) -> u8,
+        nodes: &mut Vec<String>,
+        indices: &mut HashMap<String, usize>,
+    ) -> usize {
+        let level = neighbors + 1 - prefix.len();
+        let children: Vec<String> = (0..2)
+            .map(|state| {
+                prefix.push(state);
+                let child = if level == 1 {
+                    next(prefix) as usize
+                } else {
+                    build(prefix, neighbors, next, nodes, indices)
+                };
+                prefix.pop();
+                child.to_string()
+            })
+            .collect();
+
+        let node = format!("{level} {}", children.join(" "));
+        *indices.entry(node.clone()).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() - 1
+        })
+    }
+
+    let mut nodes = Vec::new();
+    build(
+        &mut Vec::new(),
+        neighbors,
+        &next,
+        &mut nodes,
+        &mut HashMap::new(),
+    );
+
+    format!(
+        "@RULE Generated\n@TREE\nnum_states=2\nnum_neighbors={neighbors}\nnum_nodes={}\n{}\n",
+        nodes.len(),
+        nodes.join("\n")
+    )
+}
+
+fn life(inputs: &[u8]) -> u8 {
+    let (neighbors, center) = inputs.split_at(inputs.len() - 1);
+    let alive = neighbors.iter().filter(|state| **state == 1).count();
+
+    (alive == 3 || (center[0] == 1 && alive == 2)) as u8
+}
+
+/// Tree logic for a dead cell being born when the neighbor at `index` in
+/// tree order is the only live one; other cells keep their state.
+fn born_from(index: usize) -> impl Fn(&[u8]) -> u8 {
+    move |inputs| {
+        let (neighbors, center) = inputs.split_at(inputs.len() - 1);
+        let alone = neighbors[index] == 1 && neighbors.iter().sum::<u8>() == 1;
+
+        if center[0] == 0 && alone {
+            1
+        } else {
+            center[0]
+        }
+    }
+}
+
+fn lattice(size: usize, cells: &[[i128; 2]]) -> UniversalLattice<2, u8> {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([size, size]));
+    for cell in cells {
+        lattice.set_state(&UniversalLatticePoint::new(*cell), &1);
+    }
+    lattice
+}
+
+fn alive(lattice: &UniversalLattice<2, u8>) -> Vec<[i128; 2]> {
+    lattice
+        .points()
+        .into_iter()
+        .filter(|point| lattice.get_state(point) == 1)
+        .map(|point| point.values())
+        .collect()
+}
+
+/// Cells alive after one step of `text` from the given live cells on a 7x7
+/// lattice.
+fn step(text: &str, cells: &[[i128; 2]]) -> Vec<[i128; 2]> {
+    let automaton = TableRuleAutomaton::new(TableRule::parse(text).expect("valid rule"));
+    let mut lattice = lattice(7, cells);
+    automaton.step(&mut lattice);
+    alive(&lattice)
+}
+
+fn table(neighborhood: &str, symmetries: &str, transitions: &str) -> String {
+    format!(
+        "@RULE Test\n@TABLE\nn_states:2\nneighborhood:{neighborhood}\nsymmetries:{symmetries}\n{transitions}\n"
+    )
+}
+
+#[test]
+fn life_table_and_tree_match_game_of_life() {
+    let glider = [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]];
+    let table = TableRule::parse(LIFE_TABLE).expect("valid table");
+    let tree = TableRule::parse(&tree(8, life)).expect("valid tree");
+
+    assert_eq!(table.name(), "Life");
+    assert_eq!(
+        (table.states(), table.neighborhood()),
+        (2, TableNeighborhood::Moore)
+    );
+    assert_eq!(table.colors()[&1], [255, 255, 255]);
+    assert_eq!(
+        (tree.states(), tree.neighborhood()),
+        (2, TableNeighborhood::Moore)
+    );
+
+    let reference = GameOfLifeAutomaton::new(GameOfLifeRule::from_rulestring("B3/S23").unwrap());
+    let mut expected: UniversalLattice<2, GameOfLifeState> =
+        UniversalLattice::from(UniversalLatticeSize::new([10, 10]));
+    expected.set_boundary_handling(BoundaryHandling::Wrap);
+    for cell in glider {
+        expected.set_state(&UniversalLatticePoint::new(cell), &GameOfLifeState::Alive);
+    }
+
+    let automata = [
+        TableRuleAutomaton::new(table),
+        TableRuleAutomaton::new(tree),
+    ];
+    let mut lattices = [lattice(10, &glider), lattice(10, &glider)];
+    lattices
+        .iter_mut()
+        .for_each(|lattice| lattice.set_boundary_handling(BoundaryHandling::Wrap));
+
+    for generation in 0..40 {
+        reference.step(&mut expected);
+        let states: Vec<u8> = expected.states().into_iter().map(u8::from).collect();
+
+        for (automaton, lattice) in automata.iter().zip(&mut lattices) {
+            automaton.step(lattice);
+            assert_eq!(lattice.states(), states, "generation {generation}");
+        }
+    }
+}
+
+#[test]
+fn moore_neighbors_follow_golly_order() {
+    // Born when exactly the north neighbor is alive; rows grow downwards.
+    let north = [
+        table("Moore", "none", "0,1,0,0,0,0,0,0,0,1"),
+        tree(8, born_from(4)),
+    ];
+
+    for text in north {
+        assert_eq!(step(&text, &[[3, 3]]), [[3, 3], [3, 4]]);
+    }
+
+    // Born when exactly the north-east neighbor is alive.
+    let north_east = [
+        table("Moore", "none", "0,0,1,0,0,0,0,0,0,1"),
+        tree(8, born_from(1)),
+    ];
+
+    for text in north_east {
+        assert_eq!(step(&text, &[[3, 3]]), [[3, 3], [2, 4]]);
+    }
+}
+
+#[test]
+fn von_neumann_neighbors_follow_golly_order() {
+    // Born when exactly the east neighbor is alive.
+    let east = [
+        table("vonNeumann", "none", "0,0,1,0,0,1"),
+        tree(4, born_from(2)),
+    ];
+
+    for text in east {
+        let rule = TableRule::parse(&text).unwrap();
+        assert_eq!(rule.neighborhood(), TableNeighborhood::VonNeumann);
+        assert_eq!(step(&text, &[[3, 3]]), [[2, 3], [3, 3]]);
+    }
+}
+
+#[test]
+fn symmetries_expand_transitions() {
+    let north = "0,1,0,0,0,0,0,0,0,1";
+    let orthogonal = [[3, 2], [2, 3], [3, 3], [4, 3], [3, 4]];
+    let all = [
+        [2, 2],
+        [3, 2],
+        [4, 2],
+        [2, 3],
+        [3, 3],
+        [4, 3],
+        [2, 4],
+        [3, 4],
+        [4, 4],
+    ];
+
+    assert_eq!(
+        step(&table("Moore", "rotate4", north), &[[3, 3]]),
+        orthogonal
+    );
+    assert_eq!(step(&table("Moore", "rotate8", north), &[[3, 3]]), all);
+    assert_eq!(
+        step(&table("vonNeumann", "rotate4", "0,1,0,0,0,1"), &[[3, 3]]),
+        orthogonal
+    );
+
+    // North plus north-east, mirrored to north plus north-west.
+    let pair = "0,1,1,0,0,0,0,0,0,1";
+    assert_eq!(
+        step(&table("Moore", "none", pair), &[[3, 2], [4, 2]]),
+        [[3, 2], [4, 2], [3, 3]]
+    );
+    assert_eq!(
+        step(
+            &table("Moore", "reflect_horizontal", pair),
+            &[[3, 2], [4, 2]]
+        ),
+        [[3, 2], [4, 2], [3, 3], [4, 3]]
+    );
+
+    // Two live neighbors anywhere.
+    assert_eq!(
+        step(
+            &table("vonNeumann", "permute", "0,1,1,0,0,1"),
+            &[[2, 3], [4, 3]]
+        ),
+        [[2, 3], [3, 3], [4, 3]]
+    );
+}
+
+#[test]
+fn permuted_variables_bind_consistently() {
+    // Born with exactly three live neighbors in any arrangement.
+    let rule = table("vonNeumann", "permute", "var a={0,1}\n0,a,a,a,0,a");
+
+    assert_eq!(
+        step(&rule, &[[3, 2], [2, 3], [4, 3]]),
+        [[3, 2], [2, 3], [3, 3], [4, 3]]
+    );
+    assert_eq!(step(&rule, &[[3, 2], [2, 3]]), [[3, 2], [2, 3]]);
+    assert_eq!(
+        step(&rule, &[[3, 2], [2, 3], [4, 3], [3, 4]]),
+        [[3, 2], [2, 3], [4, 3], [3, 4]]
+    );
+}
+
+#[test]
+fn variables_bind_consistently() {
+    // Born when the north and south neighbors agree on being alive.
+    let rule = table("vonNeumann", "none", "var a={0,1}\n0,a,0,a,0,a");
+
+    assert_eq!(step(&rule, &[[3, 2], [3, 4]]), [[3, 2], [3, 3], [3, 4]]);
+    assert_eq!(step(&rule, &[[3, 2]]), [[3, 2]]);
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let error = |text: &str| TableRule::parse(text).unwrap_err();
+    let at = |line: usize, message: &str| RuleFileError {
+        line,
+        message: message.to_string(),
+    };
+
+    assert_eq!(error("@RULE Empty\n"), at(1, "no @TABLE or @TREE section"));
+    assert_eq!(
+        error("@TABLE\nn_states:1\n"),
+        at(2, "n_states must be between 2 and 256")
+    );
+    assert_eq!(
+        error("@TABLE\nn_states:2\nneighborhood:hex\n"),
+        at(3, "unsupported neighborhood `hex`")
+    );
+    assert_eq!(
+        error(&table("Moore", "spin", "")),
+        at(5, "unknown symmetry `spin`")
+    );
+    assert_eq!(
+        error("@TABLE\nneighborhood:Moore\n0,1,0,0,0,0,0,0,0,1\n"),
+        at(3, "missing n_states")
+    );
+    assert_eq!(
+        error(&table("vonNeumann", "none", "0,1,0,1")),
+        at(6, "transition has 4 terms instead of 6")
+    );
+    assert_eq!(
+        error(&table("vonNeumann", "none", "0,1,0,2,0,1")),
+        at(6, "`2` is neither a state nor a variable")
+    );
+    assert_eq!(
+        error(&table("vonNeumann", "none", "var a={0,1}\n0,1,0,0,0,a")),
+        at(7, "output variable does not appear in the inputs")
+    );
+    assert_eq!(
+        error(&table("vonNeumann", "rotate8", "0,1,0,0,0,1")),
+        at(6, "symmetry does not apply to this neighborhood")
+    );
+    assert_eq!(
+        error("@TREE\nnum_states=2\nnum_neighbors=4\n1 0 1\n2 0 5\n"),
+        at(5, "tree node refers to an invalid child")
+    );
+    assert_eq!(
+        error("@TREE\nnum_states=2\nnum_neighbors=6\n1 0 1\n"),
+        at(4, "num_neighbors must be 4 or 8")
+    );
+    assert_eq!(
+        error("@TREE\nnum_states=2\nnum_neighbors=4\n1 0 1\n2 0 0\n"),
+        at(5, "tree root has the wrong level")
+    );
+    assert_eq!(
+        error(&format!("{LIFE_TABLE}1 255 255\n")),
+        at(21, "malformed color")
+    );
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "serde")]
+
+use game_of_life::rule::GameOfLifeRule;
+
+#[test]
+fn rule_is_its_rulestring() {
+    let highlife = GameOfLifeRule::from_rulestring("B36/S23").unwrap();
+    let json = serde_json::to_string(&highlife).unwrap();
+
+    assert_eq!(json, "\"B36/S23\"");
+
+    let decoded: GameOfLifeRule = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        (decoded.birth(), decoded.survival()),
+        (highlife.birth(), highlife.survival())
+    );
+}
+
+#[test]
+fn invalid_rulestrings_are_rejected() {
+    let error = serde_json::from_str::<GameOfLifeRule>("\"B9/S23\"").unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .contains("invalid Life-like rulestring `B9/S23`")
+    );
+}
@@ -267,6 +267,20 @@ pub fn define_lattice_wrapper(input: TokenStream) -> TokenStream {
                 self.inner.set_size(size.into());
             }
 
+            #[cfg_attr(feature = "wasm", wasm_bindgen)]
+            pub fn label_regions(
+                &self,
+                connectivity: toolkit::analysis::regions::Connectivity,
+                state: #state,
+            ) -> toolkit::analysis::regions::RegionLabelling {
+                toolkit::analysis::regions::label_regions(
+                    &self.inner,
+                    connectivity,
+                    |candidate| *candidate == state,
+                )
+                .summarize(&self.inner.points())
+            }
+
             // #[cfg_attr(feature = "wasm", wasm_bindgen)]
             // pub fn transform_point(&self, point: &#wrapper_point) -> #wrapper_point {
             //     self.inner.transform_point(point)
@@ -293,6 +307,15 @@ pub fn define_lattice_wrapper(input: TokenStream) -> TokenStream {
             pub fn step_wrapper(&self, inner_lattice: &mut #wrapper_name) {
                 self.step(&mut inner_lattice.inner);
             }
+
+            #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "stepWithContext"))]
+            pub fn step_with_context_wrapper(
+                &self,
+                inner_lattice: &mut #wrapper_name,
+                context: &mut toolkit::automaton::context::StepContext,
+            ) {
+                self.step_with_context(&mut inner_lattice.inner, context);
+            }
         }
 
         #[cfg_attr(feature = "wasm", wasm_bindgen)]
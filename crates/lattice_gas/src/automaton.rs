@@ -0,0 +1,124 @@
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
+use toolkit::{
+    automaton::{
+        context::StepContext,
+        pipeline::{Pipeline, RulePhase},
+    },
+    lattice::universal_lattice::UniversalLattice,
+    types::CellularAutomaton,
+};
+
+#[cfg(feature = "wasm")]
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
+};
+
+use crate::{
+    model::{LatticeGasModel, LatticeGasNeighborhoodBuilder, LatticeGasState},
+    rule::{CollisionPhase, StreamingRule},
+};
+
+#[cfg(feature = "wasm")]
+use crate::field::VelocityField;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// HPP or FHP lattice gas. One generation is a [`CollisionPhase`] followed by
+/// a streaming [`RulePhase`], run through a toolkit [`Pipeline`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LatticeGasAutomaton {
+    model: LatticeGasModel,
+    streaming: StreamingRule,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LatticeGasAutomaton {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(model: LatticeGasModel) -> Self {
+        Self {
+            model,
+            streaming: StreamingRule::new(model),
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn model(&self) -> LatticeGasModel {
+        self.model
+    }
+}
+
+impl LatticeGasAutomaton {
+    pub fn pipeline(&self) -> Pipeline<UniversalLattice<2, LatticeGasState>> {
+        Pipeline::new()
+            .with_phase(CollisionPhase::new(self.model))
+            .with_phase(RulePhase::new(self.streaming, self.neighborhood_builder()))
+    }
+}
+
+impl CellularAutomaton for LatticeGasAutomaton {
+    type Lattice = UniversalLattice<2, LatticeGasState>;
+    type Rule = StreamingRule;
+    type NeighborhoodBuilder = LatticeGasNeighborhoodBuilder;
+
+    fn rule(&self) -> &Self::Rule {
+        &self.streaming
+    }
+
+    fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+        LatticeGasNeighborhoodBuilder::new(self.model)
+    }
+
+    fn step_with_context(&self, lattice: &mut Self::Lattice, context: &mut StepContext) {
+        self.pipeline().step(lattice, context);
+    }
+}
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(LatticeGasLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(LatticeGasLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+type InnerLattice = UniversalLattice<2, LatticeGasState>;
+#[cfg(feature = "wasm")]
+define_lattice_wrapper!(
+    LatticeGasLattice,
+    LatticeGasState,
+    LatticeGasLatticePoint,
+    LatticeGasLatticeSize,
+    LatticeGasAutomaton,
+    InnerLattice,
+    InnerSize,
+    InnerPoint
+);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl LatticeGasLattice {
+    /// Block velocities flattened as `[vx0, vy0, vx1, vy1, ...]` row by row.
+    #[wasm_bindgen]
+    pub fn velocity_field(&self, model: LatticeGasModel, block_size: usize) -> Vec<f32> {
+        VelocityField::from_lattice(&self.inner, model, block_size)
+            .velocities()
+            .iter()
+            .flat_map(|(vx, vy)| [*vx, *vy])
+            .collect()
+    }
+
+    #[wasm_bindgen]
+    pub fn density_field(&self, model: LatticeGasModel, block_size: usize) -> Vec<f32> {
+        VelocityField::from_lattice(&self.inner, model, block_size)
+            .densities()
+            .to_vec()
+    }
+}
@@ -0,0 +1,112 @@
+use toolkit::{
+    aliases::UniversalLatticePoint, lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+use crate::model::{LatticeGasModel, LatticeGasState, OBSTACLE};
+
+/// Coarse-grained momentum and density averaged over square blocks of cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityField {
+    columns: usize,
+    rows: usize,
+    block_size: usize,
+    velocities: Vec<(f32, f32)>,
+    densities: Vec<f32>,
+}
+
+impl VelocityField {
+    pub fn from_lattice(
+        lattice: &UniversalLattice<2, LatticeGasState>,
+        model: LatticeGasModel,
+        block_size: usize,
+    ) -> Self {
+        let block_size = block_size.max(1);
+        let size = lattice.size();
+        let columns = size[0].div_ceil(block_size);
+        let rows = size[1].div_ceil(block_size);
+
+        let mut momenta = vec![(0.0, 0.0); columns * rows];
+        let mut particles = vec![0.0; columns * rows];
+        let mut cells = vec![0.0f32; columns * rows];
+
+        for point in lattice.points() {
+            let state = lattice.get_state(&point);
+            let block = point[1] as usize / block_size * columns + point[0] as usize / block_size;
+
+            if state & OBSTACLE != 0 {
+                continue;
+            }
+
+            cells[block] += 1.0;
+            particles[block] += model.particles(state) as f32;
+
+            for direction in
+                (0..model.directions()).filter(|direction| state & (1 << direction) != 0)
+            {
+                let (vx, vy) = model.velocity(direction);
+                momenta[block].0 += vx;
+                momenta[block].1 += vy;
+            }
+        }
+
+        let velocities = momenta
+            .iter()
+            .zip(&cells)
+            .map(|((px, py), cells)| {
+                if *cells > 0.0 {
+                    (px / cells, py / cells)
+                } else {
+                    (0.0, 0.0)
+                }
+            })
+            .collect();
+
+        let densities = particles
+            .iter()
+            .zip(&cells)
+            .map(|(particles, cells)| if *cells > 0.0 { particles / cells } else { 0.0 })
+            .collect();
+
+        Self {
+            columns,
+            rows,
+            block_size,
+            velocities,
+            densities,
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn velocities(&self) -> &[(f32, f32)] {
+        &self.velocities
+    }
+
+    pub fn densities(&self) -> &[f32] {
+        &self.densities
+    }
+
+    pub fn velocity(&self, block: &UniversalLatticePoint<2>) -> Option<(f32, f32)> {
+        if block[0] < 0 || block[1] < 0 {
+            return None;
+        }
+
+        let (column, row) = (block[0] as usize, block[1] as usize);
+
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.velocities.get(row * self.columns + column).copied()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod model;
+pub mod rule;
+pub mod field;
+pub mod automaton;
@@ -0,0 +1,73 @@
+use lattice_gas::{
+    automaton::LatticeGasAutomaton,
+    field::VelocityField,
+    model::{LatticeGasModel, LatticeGasState, OBSTACLE},
+};
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+pub fn main() {
+    let model = LatticeGasModel::Fhp;
+    let ca = LatticeGasAutomaton::new(model);
+    let mut context = StepContext::new(42);
+
+    let size = UniversalLatticeSize::new([64, 32]);
+    let mut lattice = UniversalLattice::<2, LatticeGasState>::from(size);
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    let mut rng = context.rng(u64::MAX);
+
+    for point in lattice.points() {
+        let (x, y) = (point[0], point[1]);
+
+        let state = if (x - 20).pow(2) + (y - 16).pow(2) <= 16 {
+            OBSTACLE
+        } else {
+            // Bias towards the east-pointing directions to create a flow.
+            (0..model.directions()).fold(0, |state, direction| {
+                let probability = if model.velocity(direction).0 > 0.0 {
+                    0.5
+                } else {
+                    0.2
+                };
+                if rng.gen_bool(probability) {
+                    state | 1 << direction
+                } else {
+                    state
+                }
+            })
+        };
+
+        lattice.set_state(&point, &state);
+    }
+
+    for _ in 0..50 {
+        ca.step_with_context(&mut lattice, &mut context);
+    }
+
+    let field = VelocityField::from_lattice(&lattice, model, 8);
+    let arrows = ['→', '↗', '↑', '↖', '←', '↙', '↓', '↘'];
+
+    for row in 0..field.rows() {
+        let line: String = (0..field.columns())
+            .map(|column| {
+                let (vx, vy) = field
+                    .velocity(&UniversalLatticePoint::new([column as i128, row as i128]))
+                    .unwrap_or_default();
+
+                if vx.hypot(vy) < 0.05 {
+                    return '·';
+                }
+
+                let octant = ((-vy).atan2(vx) / std::f32::consts::FRAC_PI_4).round() as i32;
+                arrows[octant.rem_euclid(8) as usize]
+            })
+            .collect();
+
+        println!("{line}");
+    }
+}
@@ -0,0 +1,112 @@
+use toolkit::{
+    aliases::UniversalLatticePoint,
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::hexagonal::HexagonalNeighborhoodBuilder,
+    types::{Lattice, Neighborhood, NeighborhoodBuilder},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Cell states are bit flags: bit `d` is set when a particle moves in
+/// direction `d`, and [`OBSTACLE`] marks a solid cell that bounces particles
+/// back.
+pub type LatticeGasState = u8;
+
+pub const OBSTACLE: LatticeGasState = 1 << 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum LatticeGasModel {
+    /// Hardy–de Pazzis–Pomeau gas on a square grid, directions east, north,
+    /// west, south.
+    #[default]
+    Hpp,
+    /// Frisch–Hasslacher–Pomeau gas on a hexagonal grid, directions as in
+    /// [`HexagonalNeighborhoodBuilder`].
+    Fhp,
+}
+
+impl LatticeGasModel {
+    pub fn directions(&self) -> usize {
+        match self {
+            Self::Hpp => 4,
+            Self::Fhp => HexagonalNeighborhoodBuilder::DIRECTIONS,
+        }
+    }
+
+    pub fn particle_mask(&self) -> LatticeGasState {
+        (1 << self.directions()) - 1
+    }
+
+    pub fn opposite(&self, direction: usize) -> usize {
+        (direction + self.directions() / 2) % self.directions()
+    }
+
+    /// Unit velocity of `direction` with y pointing down.
+    pub fn velocity(&self, direction: usize) -> (f32, f32) {
+        let angle = std::f32::consts::TAU * direction as f32 / self.directions() as f32;
+        (angle.cos(), -angle.sin())
+    }
+
+    pub fn neighbor_point(
+        &self,
+        point: &UniversalLatticePoint<2>,
+        direction: usize,
+    ) -> UniversalLatticePoint<2> {
+        match self {
+            Self::Hpp => {
+                let (dx, dy) = [(1, 0), (0, -1), (-1, 0), (0, 1)][direction % 4];
+                UniversalLatticePoint::new([point[0] + dx, point[1] + dy])
+            }
+            Self::Fhp => HexagonalNeighborhoodBuilder::neighbor_point(point, direction),
+        }
+    }
+
+    pub fn particles(&self, state: LatticeGasState) -> u32 {
+        (state & self.particle_mask()).count_ones()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LatticeGasNeighborhood {
+    states: Vec<LatticeGasState>,
+}
+
+impl Neighborhood for LatticeGasNeighborhood {
+    type State = LatticeGasState;
+    type Iter<'a> = std::slice::Iter<'a, LatticeGasState>;
+
+    fn iter_states(&self) -> Self::Iter<'_> {
+        self.states.iter()
+    }
+}
+
+/// Neighbors in direction order of the model.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatticeGasNeighborhoodBuilder {
+    model: LatticeGasModel,
+}
+
+impl LatticeGasNeighborhoodBuilder {
+    pub fn new(model: LatticeGasModel) -> Self {
+        Self { model }
+    }
+}
+
+impl NeighborhoodBuilder<UniversalLattice<2, LatticeGasState>> for LatticeGasNeighborhoodBuilder {
+    type Neighborhood = LatticeGasNeighborhood;
+
+    fn build_neighborhood(
+        &self,
+        point: &UniversalLatticePoint<2>,
+        lattice: &UniversalLattice<2, LatticeGasState>,
+    ) -> Self::Neighborhood {
+        let states = (0..self.model.directions())
+            .map(|direction| lattice.get_state(&self.model.neighbor_point(point, direction)))
+            .collect();
+
+        LatticeGasNeighborhood { states }
+    }
+}
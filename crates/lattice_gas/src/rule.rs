@@ -0,0 +1,102 @@
+use rand::Rng;
+use toolkit::{
+    automaton::pipeline::StepPhase, lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+use crate::model::{LatticeGasModel, LatticeGasState, OBSTACLE};
+
+/// Local collision phase. Obstacles reverse every particle, HPP swaps
+/// head-on pairs by 90°, FHP rotates head-on pairs by ±60° with a random
+/// chirality and swaps symmetric three-particle configurations.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionPhase {
+    model: LatticeGasModel,
+}
+
+impl CollisionPhase {
+    pub fn new(model: LatticeGasModel) -> Self {
+        Self { model }
+    }
+
+    pub fn collide(&self, state: LatticeGasState, rng: &mut StepRng) -> LatticeGasState {
+        let particles = state & self.model.particle_mask();
+
+        if state & OBSTACLE != 0 {
+            return OBSTACLE | self.rotate(particles, self.model.directions() / 2);
+        }
+
+        let collided = match self.model {
+            LatticeGasModel::Hpp => match particles {
+                0b0101 => 0b1010,
+                0b1010 => 0b0101,
+                _ => particles,
+            },
+            LatticeGasModel::Fhp => match particles {
+                0b001001 | 0b010010 | 0b100100 => {
+                    let turn = if rng.gen_bool(0.5) { 1 } else { 5 };
+                    self.rotate(particles, turn)
+                }
+                0b010101 => 0b101010,
+                0b101010 => 0b010101,
+                _ => particles,
+            },
+        };
+
+        (state & !self.model.particle_mask()) | collided
+    }
+
+    fn rotate(&self, particles: LatticeGasState, turn: usize) -> LatticeGasState {
+        (0..self.model.directions())
+            .filter(|direction| particles & (1 << direction) != 0)
+            .fold(0, |rotated, direction| {
+                rotated | 1 << ((direction + turn) % self.model.directions())
+            })
+    }
+}
+
+impl StepPhase<UniversalLattice<2, LatticeGasState>> for CollisionPhase {
+    fn apply(&mut self, lattice: &mut UniversalLattice<2, LatticeGasState>, context: &StepContext) {
+        for (index, point) in lattice.points().into_iter().enumerate() {
+            let state = lattice.get_state(&point);
+
+            if state & self.model.particle_mask() == 0 {
+                continue;
+            }
+
+            let collided = self.collide(state, &mut context.cell(index).rng());
+            lattice.set_state(&point, &collided);
+        }
+    }
+}
+
+/// Streaming phase: every particle hops to the neighbor in its direction.
+/// Expects neighbors from [`crate::model::LatticeGasNeighborhoodBuilder`].
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingRule {
+    model: LatticeGasModel,
+}
+
+impl StreamingRule {
+    pub fn new(model: LatticeGasModel) -> Self {
+        Self { model }
+    }
+}
+
+impl Rule for StreamingRule {
+    type State = LatticeGasState;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        let neighbors: Vec<_> = neighbors.iter_states().copied().collect();
+
+        (0..self.model.directions()).fold(current_state & OBSTACLE, |state, direction| {
+            let source = neighbors[self.model.opposite(direction)];
+            state | (source & (1 << direction))
+        })
+    }
+}
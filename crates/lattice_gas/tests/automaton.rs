@@ -0,0 +1,78 @@
+use lattice_gas::{
+    automaton::LatticeGasAutomaton,
+    model::{LatticeGasModel, LatticeGasState},
+};
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+fn random_gas(model: LatticeGasModel) -> UniversalLattice<2, LatticeGasState> {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([16, 16]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    let mut rng = StepContext::new(1).rng(0);
+    for point in lattice.points() {
+        lattice.set_state(&point, &(rng.r#gen::<u8>() & model.particle_mask()));
+    }
+
+    lattice
+}
+
+fn totals(
+    model: LatticeGasModel,
+    lattice: &UniversalLattice<2, LatticeGasState>,
+) -> (u32, f32, f32) {
+    lattice
+        .states()
+        .iter()
+        .fold((0, 0.0, 0.0), |(count, px, py), state| {
+            let (vx, vy) = (0..model.directions())
+                .filter(|direction| state & (1 << direction) != 0)
+                .map(|direction| model.velocity(direction))
+                .fold((0.0, 0.0), |(x, y), (vx, vy)| (x + vx, y + vy));
+
+            (count + model.particles(*state), px + vx, py + vy)
+        })
+}
+
+#[test]
+fn collisions_and_streaming_conserve_mass_and_momentum() {
+    for model in [LatticeGasModel::Hpp, LatticeGasModel::Fhp] {
+        let automaton = LatticeGasAutomaton::new(model);
+        let mut lattice = random_gas(model);
+        let (count, px, py) = totals(model, &lattice);
+
+        let mut context = StepContext::new(9);
+        for _ in 0..20 {
+            automaton.step_with_context(&mut lattice, &mut context);
+        }
+
+        let (next_count, next_px, next_py) = totals(model, &lattice);
+        assert_eq!(next_count, count, "{model:?}");
+        assert!((next_px - px).abs() < 1e-3, "{model:?}");
+        assert!((next_py - py).abs() < 1e-3, "{model:?}");
+    }
+}
+
+#[test]
+fn hpp_head_on_pair_scatters_sideways() {
+    let automaton = LatticeGasAutomaton::new(LatticeGasModel::Hpp);
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([5, 5]));
+    // East and west particles meet in the center.
+    lattice.set_state(&UniversalLatticePoint::new([2, 2]), &0b0101);
+
+    automaton.step_with_context(&mut lattice, &mut StepContext::new(0));
+
+    assert_eq!(lattice.get_state(&UniversalLatticePoint::new([2, 2])), 0);
+    assert_eq!(
+        lattice.get_state(&UniversalLatticePoint::new([2, 1])),
+        0b0010
+    );
+    assert_eq!(
+        lattice.get_state(&UniversalLatticePoint::new([2, 3])),
+        0b1000
+    );
+}
@@ -0,0 +1,246 @@
+use game_of_life::{automaton::GameOfLifeAutomaton, rule::GameOfLifeRule, state::GameOfLifeState};
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::regions::{Connectivity, label_regions},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    prelude::*,
+    utils::{clamp_probability, translate},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    #[default]
+    Wall,
+    Floor,
+}
+
+impl From<GameOfLifeState> for Tile {
+    fn from(state: GameOfLifeState) -> Self {
+        match state {
+            GameOfLifeState::Alive => Self::Wall,
+            GameOfLifeState::Dead => Self::Floor,
+        }
+    }
+}
+
+pub type TileGrid = UniversalLattice<2, Tile>;
+
+/// Cave generator: random fill, Life-like smoothing where alive cells are
+/// walls, removal of small floor regions and tunnels that connect the rest.
+#[derive(Debug, Clone)]
+pub struct CaveGenerator {
+    size: UniversalLatticeSize<2>,
+    fill_probability: f64,
+    smoothing_passes: usize,
+    rule: GameOfLifeRule,
+    min_region_size: usize,
+    tunnel_radius: i128,
+    seed: u64,
+}
+
+impl CaveGenerator {
+    pub fn new(size: UniversalLatticeSize<2>) -> Self {
+        Self {
+            size,
+            fill_probability: 0.45,
+            smoothing_passes: 5,
+            rule: GameOfLifeRule::from_masks(0b1_1110_0000, 0b1_1111_0000),
+            min_region_size: 16,
+            tunnel_radius: 0,
+            seed: 0,
+        }
+    }
+
+    /// Clamped to `0..=1`, NaN counts as `0`.
+    pub fn with_fill_probability(mut self, fill_probability: f64) -> Self {
+        self.fill_probability = clamp_probability(fill_probability);
+        self
+    }
+
+    pub fn with_smoothing_passes(mut self, smoothing_passes: usize) -> Self {
+        self.smoothing_passes = smoothing_passes;
+        self
+    }
+
+    pub fn with_rule(mut self, rule: GameOfLifeRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn with_min_region_size(mut self, min_region_size: usize) -> Self {
+        self.min_region_size = min_region_size;
+        self
+    }
+
+    pub fn with_tunnel_radius(mut self, tunnel_radius: usize) -> Self {
+        self.tunnel_radius = tunnel_radius as i128;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn generate(&self) -> TileGrid {
+        let mut lattice = self.seed_lattice();
+        let automaton = GameOfLifeAutomaton::new(self.rule.clone());
+
+        for _ in 0..self.smoothing_passes {
+            automaton.step(&mut lattice);
+        }
+
+        let mut grid = TileGrid::from(self.size);
+
+        for point in lattice.points() {
+            let tile = if self.is_border(&point) {
+                Tile::Wall
+            } else {
+                Tile::from(lattice.get_state(&point))
+            };
+
+            grid.set_state(&point, &tile);
+        }
+
+        let mut regions: Vec<_> =
+            label_regions(&grid, Connectivity::VonNeumann, |tile| *tile == Tile::Floor)
+                .into_regions()
+                .into_iter()
+                .map(|region| region.points)
+                .collect();
+
+        regions.retain(|region| {
+            if region.len() >= self.min_region_size {
+                return true;
+            }
+
+            region
+                .iter()
+                .for_each(|point| grid.set_state(point, &Tile::Wall));
+            false
+        });
+
+        self.connect_regions(&mut grid, regions);
+
+        grid
+    }
+
+    fn seed_lattice(&self) -> UniversalLattice<2, GameOfLifeState> {
+        let mut rng = StepContext::new(self.seed).rng(0);
+        let mut lattice = UniversalLattice::from(self.size);
+
+        // Clamping makes the outside read as the nearest border cell, which
+        // keeps the smoothing from eating into the map edges.
+        lattice.set_boundary_handling(BoundaryHandling::Clamp);
+
+        for point in lattice.points() {
+            let wall = self.is_border(&point) || rng.gen_bool(self.fill_probability);
+            lattice.set_state(&point, &GameOfLifeState::from(wall));
+        }
+
+        lattice
+    }
+
+    fn is_border(&self, point: &UniversalLatticePoint<2>) -> bool {
+        (0..2).any(|axis| point[axis] == 0 || point[axis] == self.size[axis] as i128 - 1)
+    }
+
+    fn connect_regions(
+        &self,
+        grid: &mut TileGrid,
+        mut regions: Vec<Vec<UniversalLatticePoint<2>>>,
+    ) {
+        regions.sort_by_key(|region| std::cmp::Reverse(region.len()));
+
+        let mut regions = regions.into_iter();
+        let Some(mut connected) = regions.next().map(|region| edge_cells(grid, &region)) else {
+            return;
+        };
+
+        for region in regions {
+            let edges = edge_cells(grid, &region);
+
+            let closest = edges
+                .iter()
+                .flat_map(|from| connected.iter().map(move |to| (from, to)))
+                .min_by_key(|(from, to)| (from[0] - to[0]).pow(2) + (from[1] - to[1]).pow(2));
+
+            if let Some((from, to)) = closest {
+                self.carve_tunnel(grid, from, to);
+            }
+
+            connected.extend(edges);
+        }
+    }
+
+    fn carve_tunnel(
+        &self,
+        grid: &mut TileGrid,
+        from: &UniversalLatticePoint<2>,
+        to: &UniversalLatticePoint<2>,
+    ) {
+        for point in line(from, to) {
+            for dy in -self.tunnel_radius..=self.tunnel_radius {
+                for dx in -self.tunnel_radius..=self.tunnel_radius {
+                    let brush = UniversalLatticePoint::new([point[0] + dx, point[1] + dy]);
+
+                    if grid.contains(&brush) && !self.is_border(&brush) {
+                        grid.set_state(&brush, &Tile::Floor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn edge_cells(
+    grid: &TileGrid,
+    region: &[UniversalLatticePoint<2>],
+) -> Vec<UniversalLatticePoint<2>> {
+    let offsets = VonNeumannNeighborhoodBuilder::offsets::<2>();
+
+    region
+        .iter()
+        .filter(|point| {
+            offsets
+                .iter()
+                .any(|offset| grid.get_state(&translate(point, offset)) == Tile::Wall)
+        })
+        .copied()
+        .collect()
+}
+
+/// Bresenham line between two points, both ends included. Diagonal steps are
+/// split into a horizontal and a vertical one, so the line is 4-connected
+/// like the regions it joins.
+fn line(
+    from: &UniversalLatticePoint<2>,
+    to: &UniversalLatticePoint<2>,
+) -> Vec<UniversalLatticePoint<2>> {
+    let (mut x, mut y) = (from[0], from[1]);
+    let (dx, dy) = ((to[0] - x).abs(), -(to[1] - y).abs());
+    let (sx, sy) = ((to[0] - x).signum(), (to[1] - y).signum());
+    let mut error = dx + dy;
+    let mut points = vec![UniversalLatticePoint::new([x, y])];
+
+    while (x, y) != (to[0], to[1]) {
+        let doubled = 2 * error;
+
+        if doubled >= dy {
+            error += dy;
+            x += sx;
+            points.push(UniversalLatticePoint::new([x, y]));
+        }
+
+        if doubled <= dx {
+            error += dx;
+            y += sy;
+            points.push(UniversalLatticePoint::new([x, y]));
+        }
+    }
+
+    points
+}
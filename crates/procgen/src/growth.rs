@@ -0,0 +1,471 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap},
+};
+
+use rand::Rng;
+use toolkit::{
+    aliases::UniversalLatticePoint, lattice::universal_lattice::UniversalLattice, prelude::*,
+    utils::translate,
+};
+
+/// Stochastic cluster growth on a lattice. Cells already in `state` form the
+/// initial cluster; an empty lattice is seeded at its center.
+///
+/// A candidate cell touching `k` cluster cells sticks with probability
+/// `1 - (1 - p)^k`, so lower sticking probabilities favour well-supported
+/// cells and give denser clusters.
+pub trait GrowthModel<const D: usize> {
+    /// Adds up to `particles` cells and returns them in the order they stuck.
+    /// Growth stops early once a particle runs out of its step or attempt
+    /// budget.
+    fn grow<S: Clone + Default + PartialEq>(
+        &self,
+        lattice: &mut UniversalLattice<D, S>,
+        state: S,
+        particles: usize,
+    ) -> Vec<UniversalLatticePoint<D>>;
+}
+
+/// Diffusion-limited aggregation: random walkers launched around the cluster
+/// wander along the neighborhood offsets until they stick. On wrapped
+/// lattices walkers start anywhere and are never lost.
+#[derive(Debug, Clone)]
+pub struct DiffusionLimitedAggregation<B> {
+    builder: B,
+    sticking_probability: f64,
+    max_steps: usize,
+    seed: u64,
+}
+
+/// Eden growth: a uniformly random perimeter cell is added each step.
+#[derive(Debug, Clone)]
+pub struct EdenGrowth<B> {
+    builder: B,
+    sticking_probability: f64,
+    max_attempts: usize,
+    seed: u64,
+}
+
+/// Invasion percolation: every cell draws a random resistance and the
+/// perimeter cell with the lowest one is invaded next. A rejected cell draws
+/// a fresh resistance and goes back on the perimeter.
+#[derive(Debug, Clone)]
+pub struct InvasionPercolation<B> {
+    builder: B,
+    sticking_probability: f64,
+    max_attempts: usize,
+    seed: u64,
+}
+
+impl<B> DiffusionLimitedAggregation<B> {
+    pub fn new(builder: B) -> Self {
+        Self {
+            builder,
+            sticking_probability: 1.0,
+            max_steps: 1_000_000,
+            seed: 0,
+        }
+    }
+
+    pub fn with_sticking_probability(mut self, sticking_probability: f64) -> Self {
+        self.sticking_probability = sticking_probability;
+        self
+    }
+
+    /// Walker steps allowed per particle before growth gives up.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<B> EdenGrowth<B> {
+    pub fn new(builder: B) -> Self {
+        Self {
+            builder,
+            sticking_probability: 1.0,
+            max_attempts: 1_000_000,
+            seed: 0,
+        }
+    }
+
+    pub fn with_sticking_probability(mut self, sticking_probability: f64) -> Self {
+        self.sticking_probability = sticking_probability;
+        self
+    }
+
+    /// Rejected sticking attempts allowed per particle before growth gives
+    /// up.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<B> InvasionPercolation<B> {
+    pub fn new(builder: B) -> Self {
+        Self {
+            builder,
+            sticking_probability: 1.0,
+            max_attempts: 1_000_000,
+            seed: 0,
+        }
+    }
+
+    pub fn with_sticking_probability(mut self, sticking_probability: f64) -> Self {
+        self.sticking_probability = sticking_probability;
+        self
+    }
+
+    /// Rejected sticking attempts allowed per particle before growth gives
+    /// up.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<const D: usize, B: OffsetNeighborhoodBuilder<D>> GrowthModel<D>
+    for DiffusionLimitedAggregation<B>
+{
+    fn grow<S: Clone + Default + PartialEq>(
+        &self,
+        lattice: &mut UniversalLattice<D, S>,
+        state: S,
+        particles: usize,
+    ) -> Vec<UniversalLatticePoint<D>> {
+        let offsets = self.builder.neighbor_offsets();
+        let mut rng = StepContext::new(self.seed).rng(0);
+        let cluster = Cluster::new(lattice, &state, &offsets, self.sticking_probability);
+
+        let center = cluster.center(lattice);
+        let mut radius = cluster
+            .points
+            .iter()
+            .map(|point| distance(point, &center))
+            .fold(0.0, f64::max);
+
+        let mut added = Vec::new();
+
+        for _ in 0..particles {
+            let stuck = added.len();
+            let mut steps = 0;
+
+            'launch: while steps < self.max_steps {
+                let Some(mut walker) = cluster.launch(lattice, &center, radius + 2.0, &mut rng)
+                else {
+                    steps += 1;
+                    continue;
+                };
+
+                while steps < self.max_steps {
+                    steps += 1;
+
+                    if cluster.sticks(lattice, &walker, &mut rng) {
+                        lattice.set_state(&walker, &state);
+                        radius = radius.max(distance(&walker, &center));
+                        added.push(walker);
+                        break 'launch;
+                    }
+
+                    let offset = &offsets[rng.gen_range(0..offsets.len())];
+                    let Some(next) = cluster.resolve(lattice, &translate(&walker, offset)) else {
+                        continue 'launch;
+                    };
+
+                    if lattice.get_state(&next) != state {
+                        walker = next;
+                    }
+
+                    if !cluster.wraps && distance(&walker, &center) > 2.0 * radius + 10.0 {
+                        continue 'launch;
+                    }
+                }
+            }
+
+            if added.len() == stuck {
+                break;
+            }
+        }
+
+        added
+    }
+}
+
+impl<const D: usize, B: OffsetNeighborhoodBuilder<D>> GrowthModel<D> for EdenGrowth<B> {
+    fn grow<S: Clone + Default + PartialEq>(
+        &self,
+        lattice: &mut UniversalLattice<D, S>,
+        state: S,
+        particles: usize,
+    ) -> Vec<UniversalLatticePoint<D>> {
+        let offsets = self.builder.neighbor_offsets();
+        let mut rng = StepContext::new(self.seed).rng(0);
+        let cluster = Cluster::new(lattice, &state, &offsets, self.sticking_probability);
+
+        let mut perimeter: Vec<UniversalLatticePoint<D>> = Vec::new();
+        let mut on_perimeter = BTreeSet::new();
+        let mut added = Vec::new();
+
+        for point in &cluster.points {
+            cluster.extend_perimeter(lattice, point, &mut perimeter, &mut on_perimeter);
+        }
+
+        let mut attempts = 0;
+
+        while added.len() < particles && !perimeter.is_empty() && attempts < self.max_attempts {
+            let index = rng.gen_range(0..perimeter.len());
+
+            if !cluster.sticks(lattice, &perimeter[index], &mut rng) {
+                attempts += 1;
+                continue;
+            }
+
+            attempts = 0;
+
+            let point = perimeter.swap_remove(index);
+            lattice.set_state(&point, &state);
+            cluster.extend_perimeter(lattice, &point, &mut perimeter, &mut on_perimeter);
+            added.push(point);
+        }
+
+        added
+    }
+}
+
+impl<const D: usize, B: OffsetNeighborhoodBuilder<D>> GrowthModel<D> for InvasionPercolation<B> {
+    fn grow<S: Clone + Default + PartialEq>(
+        &self,
+        lattice: &mut UniversalLattice<D, S>,
+        state: S,
+        particles: usize,
+    ) -> Vec<UniversalLatticePoint<D>> {
+        let offsets = self.builder.neighbor_offsets();
+        let mut rng = StepContext::new(self.seed).rng(0);
+        let cluster = Cluster::new(lattice, &state, &offsets, self.sticking_probability);
+
+        // Resistances are uniform in [0, 1), so their bit patterns order
+        // the same way as the values.
+        let mut frontier = BinaryHeap::new();
+        let mut added = Vec::new();
+
+        let enqueue =
+            |point: UniversalLatticePoint<D>, rng: &mut StepRng, frontier: &mut BinaryHeap<_>| {
+                let resistance: f64 = rng.r#gen();
+                frontier.push(Reverse((resistance.to_bits(), point)));
+            };
+
+        let mut seen = BTreeSet::new();
+        for point in &cluster.points {
+            for neighbor in cluster.empty_neighbors(lattice, point) {
+                if seen.insert(neighbor) {
+                    enqueue(neighbor, &mut rng, &mut frontier);
+                }
+            }
+        }
+
+        let mut attempts = 0;
+
+        while added.len() < particles && attempts < self.max_attempts {
+            let Some(Reverse((_, point))) = frontier.pop() else {
+                break;
+            };
+
+            if lattice.get_state(&point) == state {
+                continue;
+            }
+
+            if !cluster.sticks(lattice, &point, &mut rng) {
+                attempts += 1;
+                enqueue(point, &mut rng, &mut frontier);
+                continue;
+            }
+
+            attempts = 0;
+
+            lattice.set_state(&point, &state);
+            added.push(point);
+
+            for neighbor in cluster.empty_neighbors(lattice, &point) {
+                if seen.insert(neighbor) {
+                    enqueue(neighbor, &mut rng, &mut frontier);
+                }
+            }
+        }
+
+        added
+    }
+}
+
+/// Shared bookkeeping: the seed cluster, the offsets and the boundary rules.
+struct Cluster<'a, const D: usize, S> {
+    state: &'a S,
+    offsets: &'a [UniversalLatticePoint<D>],
+    sticking_probability: f64,
+    wraps: bool,
+    points: Vec<UniversalLatticePoint<D>>,
+}
+
+impl<'a, const D: usize, S: Clone + Default + PartialEq> Cluster<'a, D, S> {
+    fn new(
+        lattice: &mut UniversalLattice<D, S>,
+        state: &'a S,
+        offsets: &'a [UniversalLatticePoint<D>],
+        sticking_probability: f64,
+    ) -> Self {
+        let mut points: Vec<_> = lattice
+            .points()
+            .into_iter()
+            .filter(|point| lattice.get_state(point) == *state)
+            .collect();
+
+        if points.is_empty() {
+            let size = lattice.size();
+            let center = UniversalLatticePoint::new(size.values().map(|length| length as i128 / 2));
+            lattice.set_state(&center, state);
+            points.push(center);
+        }
+
+        Self {
+            state,
+            offsets,
+            // NaN would make the sticking draws panic, so it counts as zero.
+            sticking_probability: if sticking_probability.is_nan() {
+                f64::MIN_POSITIVE
+            } else {
+                sticking_probability.clamp(f64::MIN_POSITIVE, 1.0)
+            },
+            wraps: lattice.boundary_handling() == BoundaryHandling::Wrap,
+            points,
+        }
+    }
+
+    fn center(&self, lattice: &UniversalLattice<D, S>) -> [f64; D] {
+        if self.wraps {
+            return lattice.size().values().map(|length| length as f64 / 2.0);
+        }
+
+        let mut center = [0.0; D];
+
+        for point in &self.points {
+            (0..D).for_each(|axis| center[axis] += point[axis] as f64);
+        }
+
+        center.map(|sum| sum / self.points.len() as f64)
+    }
+
+    fn resolve(
+        &self,
+        lattice: &UniversalLattice<D, S>,
+        point: &UniversalLatticePoint<D>,
+    ) -> Option<UniversalLatticePoint<D>> {
+        if self.wraps {
+            Some(lattice.transform_point(point))
+        } else {
+            lattice.contains(point).then_some(*point)
+        }
+    }
+
+    fn empty_neighbors(
+        &self,
+        lattice: &UniversalLattice<D, S>,
+        point: &UniversalLatticePoint<D>,
+    ) -> Vec<UniversalLatticePoint<D>> {
+        self.offsets
+            .iter()
+            .filter_map(|offset| self.resolve(lattice, &translate(point, offset)))
+            .filter(|neighbor| lattice.get_state(neighbor) != *self.state)
+            .collect()
+    }
+
+    fn extend_perimeter(
+        &self,
+        lattice: &UniversalLattice<D, S>,
+        point: &UniversalLatticePoint<D>,
+        perimeter: &mut Vec<UniversalLatticePoint<D>>,
+        on_perimeter: &mut BTreeSet<UniversalLatticePoint<D>>,
+    ) {
+        for neighbor in self.empty_neighbors(lattice, point) {
+            if on_perimeter.insert(neighbor) {
+                perimeter.push(neighbor);
+            }
+        }
+    }
+
+    fn sticks(
+        &self,
+        lattice: &UniversalLattice<D, S>,
+        point: &UniversalLatticePoint<D>,
+        rng: &mut StepRng,
+    ) -> bool {
+        let contacts = self
+            .offsets
+            .iter()
+            .filter_map(|offset| self.resolve(lattice, &translate(point, offset)))
+            .filter(|neighbor| lattice.get_state(neighbor) == *self.state)
+            .count();
+
+        contacts > 0 && rng.gen_bool(1.0 - (1.0 - self.sticking_probability).powi(contacts as i32))
+    }
+
+    fn launch(
+        &self,
+        lattice: &UniversalLattice<D, S>,
+        center: &[f64; D],
+        radius: f64,
+        rng: &mut StepRng,
+    ) -> Option<UniversalLatticePoint<D>> {
+        let point = if self.wraps {
+            let size = lattice.size();
+            UniversalLatticePoint::new(size.values().map(|length| rng.gen_range(0..length) as i128))
+        } else {
+            let direction = random_direction::<D>(rng);
+            let mut coords = [0; D];
+            (0..D).for_each(|axis| {
+                coords[axis] = (center[axis] + direction[axis] * radius).round() as i128
+            });
+            UniversalLatticePoint::new(coords)
+        };
+
+        self.resolve(lattice, &point)
+            .filter(|point| lattice.get_state(point) != *self.state)
+    }
+}
+
+fn random_direction<const D: usize>(rng: &mut StepRng) -> [f64; D] {
+    loop {
+        let direction = [0.0; D].map(|_| rng.gen_range(-1.0..1.0));
+        let length = direction
+            .iter()
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt();
+
+        if length > 1e-9 && length <= 1.0 {
+            return direction.map(|value| value / length);
+        }
+    }
+}
+
+fn distance<const D: usize>(point: &UniversalLatticePoint<D>, center: &[f64; D]) -> f64 {
+    (0..D)
+        .map(|axis| (point[axis] as f64 - center[axis]).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
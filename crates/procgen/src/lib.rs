@@ -0,0 +1,6 @@
+pub mod caves;
+pub mod growth;
+pub mod lsystem;
+pub mod maze;
+pub mod terrain;
+pub mod wfc;
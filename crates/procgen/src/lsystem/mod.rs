@@ -0,0 +1,414 @@
+pub mod turtle;
+
+use std::{collections::BTreeSet, fmt, rc::Rc};
+
+use rand::Rng;
+use toolkit::automaton::context::{StepContext, StepRng};
+
+/// A symbol with optional numeric parameters, written `F` or `F(1, 0.5)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub symbol: char,
+    pub parameters: Vec<f64>,
+}
+
+impl Module {
+    pub fn new(symbol: char, parameters: Vec<f64>) -> Self {
+        Self { symbol, parameters }
+    }
+
+    pub fn symbol(symbol: char) -> Self {
+        Self::new(symbol, Vec::new())
+    }
+
+    pub fn parameter(&self, index: usize) -> Option<f64> {
+        self.parameters.get(index).copied()
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)?;
+
+        if !self.parameters.is_empty() {
+            let parameters: Vec<String> = self.parameters.iter().map(f64::to_string).collect();
+            write!(f, "({})", parameters.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at character {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a module string such as `F(1)[+F(0.5)]A`; whitespace is ignored.
+pub fn parse_modules(text: &str) -> Result<Vec<Module>, ParseError> {
+    let mut modules = Vec::new();
+    let mut chars = text.chars().enumerate().peekable();
+
+    while let Some((position, symbol)) = chars.next() {
+        if symbol.is_whitespace() {
+            continue;
+        }
+
+        if symbol == '(' || symbol == ')' || symbol == ',' {
+            return Err(ParseError {
+                position,
+                message: "unexpected delimiter",
+            });
+        }
+
+        let mut parameters = Vec::new();
+
+        if chars.next_if(|(_, next)| *next == '(').is_some() {
+            let mut argument = String::new();
+
+            loop {
+                let Some((position, next)) = chars.next() else {
+                    return Err(ParseError {
+                        position: text.chars().count(),
+                        message: "unclosed parameter list",
+                    });
+                };
+
+                if next == ',' || next == ')' {
+                    let value = argument.trim().parse().map_err(|_| ParseError {
+                        position,
+                        message: "invalid parameter",
+                    })?;
+                    parameters.push(value);
+                    argument.clear();
+
+                    if next == ')' {
+                        break;
+                    }
+                } else {
+                    argument.push(next);
+                }
+            }
+        }
+
+        modules.push(Module::new(symbol, parameters));
+    }
+
+    Ok(modules)
+}
+
+pub fn format_modules(modules: &[Module]) -> String {
+    modules.iter().map(Module::to_string).collect()
+}
+
+/// The module a production is applied to together with its matched context.
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    pub module: &'a Module,
+    pub left: Vec<&'a Module>,
+    pub right: Vec<&'a Module>,
+}
+
+impl Match<'_> {
+    /// Parameter of the predecessor, `0.0` when missing.
+    pub fn parameter(&self, index: usize) -> f64 {
+        self.module.parameter(index).unwrap_or_default()
+    }
+}
+
+type Condition = Rc<dyn Fn(&Match) -> bool>;
+type Successor = Rc<dyn Fn(&Match) -> Vec<Module>>;
+
+/// Rewrites `predecessor` into a successor. Productions can require left and
+/// right context (`a < b > c`), a condition on parameters and carry a weight
+/// for stochastic choice among all productions that match a module.
+#[derive(Clone)]
+pub struct Production {
+    predecessor: char,
+    left_context: Vec<char>,
+    right_context: Vec<char>,
+    condition: Option<Condition>,
+    successor: Successor,
+    weight: f64,
+}
+
+impl fmt::Debug for Production {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Production")
+            .field("predecessor", &self.predecessor)
+            .field("left_context", &self.left_context)
+            .field("right_context", &self.right_context)
+            .field("conditional", &self.condition.is_some())
+            .field("weight", &self.weight)
+            .finish()
+    }
+}
+
+impl Production {
+    pub fn new(predecessor: char, successor: Vec<Module>) -> Self {
+        Self::parametric(predecessor, move |_| successor.clone())
+    }
+
+    /// Successor computed from the matched module and its context.
+    pub fn parametric(
+        predecessor: char,
+        successor: impl Fn(&Match) -> Vec<Module> + 'static,
+    ) -> Self {
+        Self {
+            predecessor,
+            left_context: Vec::new(),
+            right_context: Vec::new(),
+            condition: None,
+            successor: Rc::new(successor),
+            weight: 1.0,
+        }
+    }
+
+    /// Symbols that must directly precede the module, nearest last.
+    pub fn with_left_context(mut self, symbols: &str) -> Self {
+        self.left_context = symbols.chars().collect();
+        self
+    }
+
+    /// Symbols that must directly follow the module, nearest first.
+    pub fn with_right_context(mut self, symbols: &str) -> Self {
+        self.right_context = symbols.chars().collect();
+        self
+    }
+
+    pub fn with_condition(mut self, condition: impl Fn(&Match) -> bool + 'static) -> Self {
+        self.condition = Some(Rc::new(condition));
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight.max(0.0);
+        self
+    }
+
+    pub fn predecessor(&self) -> char {
+        self.predecessor
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LSystem {
+    axiom: Vec<Module>,
+    productions: Vec<Production>,
+    ignored: BTreeSet<char>,
+    seed: u64,
+}
+
+impl LSystem {
+    pub fn new(axiom: Vec<Module>) -> Self {
+        Self {
+            axiom,
+            productions: Vec::new(),
+            ignored: BTreeSet::new(),
+            seed: 0,
+        }
+    }
+
+    pub fn with_production(mut self, production: Production) -> Self {
+        self.productions.push(production);
+        self
+    }
+
+    /// Symbols skipped when matching context, typically the turtle turns.
+    pub fn with_ignored(mut self, symbols: &str) -> Self {
+        self.ignored = symbols.chars().collect();
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn axiom(&self) -> &[Module] {
+        &self.axiom
+    }
+
+    pub fn generate(&self, iterations: usize) -> Vec<Module> {
+        let context = StepContext::new(self.seed);
+
+        (0..iterations).fold(self.axiom.clone(), |modules, iteration| {
+            self.derive(&modules, &mut context.rng(iteration as u64))
+        })
+    }
+
+    /// One parallel rewriting step. Modules without a matching production
+    /// are copied unchanged.
+    pub fn derive(&self, modules: &[Module], rng: &mut StepRng) -> Vec<Module> {
+        let mut derived = Vec::with_capacity(modules.len());
+
+        for index in 0..modules.len() {
+            let mut candidates = Vec::new();
+
+            for production in &self.productions {
+                if let Some(matched) = self.matches(production, modules, index) {
+                    candidates.push((production, matched));
+                }
+            }
+
+            let total: f64 = candidates
+                .iter()
+                .map(|(production, _)| production.weight)
+                .sum();
+
+            if candidates.is_empty() || total <= 0.0 {
+                derived.push(modules[index].clone());
+                continue;
+            }
+
+            let mut threshold = rng.gen_range(0.0..total);
+            let chosen = candidates
+                .iter()
+                .position(|(production, _)| {
+                    threshold -= production.weight;
+                    threshold < 0.0
+                })
+                .unwrap_or(candidates.len() - 1);
+
+            let (production, matched) = &candidates[chosen];
+            derived.extend((production.successor)(matched));
+        }
+
+        derived
+    }
+
+    fn matches<'a>(
+        &self,
+        production: &Production,
+        modules: &'a [Module],
+        index: usize,
+    ) -> Option<Match<'a>> {
+        let module = &modules[index];
+
+        if module.symbol != production.predecessor {
+            return None;
+        }
+
+        let left = self.left_context(modules, index, production.left_context.len());
+        let right = self.right_context(modules, index, production.right_context.len());
+
+        let symbols_match = |context: &[&Module], expected: &[char]| {
+            context.len() == expected.len()
+                && context
+                    .iter()
+                    .zip(expected)
+                    .all(|(module, symbol)| module.symbol == *symbol)
+        };
+
+        if !symbols_match(&left, &production.left_context)
+            || !symbols_match(&right, &production.right_context)
+        {
+            return None;
+        }
+
+        let matched = Match {
+            module,
+            left,
+            right,
+        };
+
+        match &production.condition {
+            Some(condition) if !condition(&matched) => None,
+            _ => Some(matched),
+        }
+    }
+
+    /// Up to `count` modules before `index` on the path to the root: side
+    /// branches are skipped and entering a branch steps back to its parent.
+    fn left_context<'a>(
+        &self,
+        modules: &'a [Module],
+        index: usize,
+        count: usize,
+    ) -> Vec<&'a Module> {
+        let mut context = Vec::new();
+        let mut position = index;
+
+        while context.len() < count && position > 0 {
+            position -= 1;
+
+            match modules[position].symbol {
+                ']' => position = matching_open(modules, position).unwrap_or(0),
+                '[' => {}
+                symbol if self.ignored.contains(&symbol) => {}
+                _ => context.push(&modules[position]),
+            }
+        }
+
+        context.reverse();
+        context
+    }
+
+    /// Up to `count` modules after `index` on the same branch, skipping
+    /// side branches that start in between.
+    fn right_context<'a>(
+        &self,
+        modules: &'a [Module],
+        index: usize,
+        count: usize,
+    ) -> Vec<&'a Module> {
+        let mut context = Vec::new();
+        let mut position = index + 1;
+
+        while context.len() < count && position < modules.len() {
+            match modules[position].symbol {
+                '[' => position = matching_close(modules, position),
+                ']' => break,
+                symbol if self.ignored.contains(&symbol) => {}
+                _ => context.push(&modules[position]),
+            }
+
+            position += 1;
+        }
+
+        context
+    }
+}
+
+fn matching_open(modules: &[Module], close: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    for position in (0..close).rev() {
+        match modules[position].symbol {
+            ']' => depth += 1,
+            '[' if depth == 0 => return Some(position),
+            '[' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn matching_close(modules: &[Module], open: usize) -> usize {
+    let mut depth = 0;
+
+    for (position, module) in modules.iter().enumerate().skip(open + 1) {
+        match module.symbol {
+            '[' => depth += 1,
+            ']' if depth == 0 => return position,
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    modules.len()
+}
@@ -0,0 +1,168 @@
+use toolkit::{
+    aliases::UniversalLatticePoint, lattice::universal_lattice::UniversalLattice, prelude::*,
+};
+
+use super::Module;
+
+type Vector = [f64; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Vector,
+    pub end: Vector,
+    /// Bracket nesting level, `0` on the trunk.
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TurtleState {
+    position: Vector,
+    heading: Vector,
+    left: Vector,
+    up: Vector,
+}
+
+/// Turtle interpretation of module strings in the usual notation:
+///
+/// | symbol | action |
+/// |--------|--------|
+/// | `F`, `G` | move forward drawing a segment |
+/// | `f`, `g` | move forward without drawing |
+/// | `+`, `-` | turn left / right |
+/// | `&`, `^` | pitch down / up |
+/// | `\`, `/` | roll left / right |
+/// | `\|` | turn around |
+/// | `[`, `]` | push / pop the turtle state |
+///
+/// The first parameter, when present, overrides the step length or angle.
+/// Other symbols are ignored. The turtle starts at the origin heading to
+/// negative `y`, which is up on a lattice whose rows grow downwards, so plain
+/// 2D systems only ever use `+` and `-`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Turtle {
+    step: f64,
+    angle: f64,
+}
+
+impl Turtle {
+    /// `angle` is in degrees.
+    pub fn new(step: f64, angle: f64) -> Self {
+        Self { step, angle }
+    }
+
+    pub fn interpret(&self, modules: &[Module]) -> Vec<Segment> {
+        let mut state = TurtleState {
+            position: [0.0; 3],
+            heading: [0.0, -1.0, 0.0],
+            left: [-1.0, 0.0, 0.0],
+            up: [0.0, 0.0, -1.0],
+        };
+        let mut stack = Vec::new();
+        let mut segments = Vec::new();
+
+        for module in modules {
+            let length = module.parameter(0).unwrap_or(self.step);
+            let angle = module.parameter(0).unwrap_or(self.angle).to_radians();
+
+            match module.symbol {
+                'F' | 'G' | 'f' | 'g' => {
+                    let start = state.position;
+                    state.position = add(start, scale(state.heading, length));
+
+                    if module.symbol.is_uppercase() {
+                        segments.push(Segment {
+                            start,
+                            end: state.position,
+                            depth: stack.len(),
+                        });
+                    }
+                }
+                '+' => (state.heading, state.left) = rotate(state.heading, state.left, angle),
+                '-' => (state.heading, state.left) = rotate(state.heading, state.left, -angle),
+                '&' => (state.heading, state.up) = rotate(state.heading, state.up, -angle),
+                '^' => (state.heading, state.up) = rotate(state.heading, state.up, angle),
+                '\\' => (state.left, state.up) = rotate(state.left, state.up, angle),
+                '/' => (state.left, state.up) = rotate(state.left, state.up, -angle),
+                '|' => {
+                    state.heading = scale(state.heading, -1.0);
+                    state.left = scale(state.left, -1.0);
+                }
+                '[' => stack.push(state),
+                ']' => {
+                    if let Some(saved) = stack.pop() {
+                        state = saved;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        segments
+    }
+}
+
+/// Draws segments onto a lattice, with the turtle origin at `origin`. Turtle
+/// axes past `D` are dropped and lattice axes past the third stay at
+/// `origin`; cells off the lattice are skipped unless it wraps.
+pub fn rasterize<const D: usize, S: Clone + Default>(
+    segments: &[Segment],
+    lattice: &mut UniversalLattice<D, S>,
+    origin: &UniversalLatticePoint<D>,
+    state: &S,
+) {
+    let wraps = lattice.boundary_handling() == BoundaryHandling::Wrap;
+
+    for segment in segments {
+        let delta: Vec<f64> = (0..D)
+            .map(|axis| coordinate(segment.end, axis) - coordinate(segment.start, axis))
+            .collect();
+        let steps = delta
+            .iter()
+            .fold(0.0f64, |steps, delta| steps.max(delta.abs()))
+            .ceil() as usize;
+
+        for step in 0..=steps {
+            let t = if steps == 0 {
+                0.0
+            } else {
+                step as f64 / steps as f64
+            };
+            let mut point = *origin;
+
+            (0..D).for_each(|axis| {
+                point[axis] += (coordinate(segment.start, axis) + delta[axis] * t).round() as i128
+            });
+
+            if wraps {
+                point = lattice.transform_point(&point);
+            } else if !lattice.contains(&point) {
+                continue;
+            }
+
+            lattice.set_state(&point, state);
+        }
+    }
+}
+
+/// Component of a turtle vector along a lattice axis, `0` past the third.
+fn coordinate(vector: Vector, axis: usize) -> f64 {
+    vector.get(axis).copied().unwrap_or_default()
+}
+
+/// Rotates the orthonormal pair `(a, b)` by `angle` within their plane.
+fn rotate(a: Vector, b: Vector, angle: f64) -> (Vector, Vector) {
+    let (sin, cos) = angle.sin_cos();
+
+    (
+        add(scale(a, cos), scale(b, sin)),
+        add(scale(a, -sin), scale(b, cos)),
+    )
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vector, factor: f64) -> Vector {
+    a.map(|value| value * factor)
+}
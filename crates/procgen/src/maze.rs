@@ -0,0 +1,519 @@
+use std::collections::{BTreeMap, VecDeque};
+
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_point_wrapper, define_size_wrapper};
+use rand::{Rng, seq::SliceRandom};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    prelude::*,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum MazeCell {
+    #[default]
+    Wall,
+    Passage,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum MazeAlgorithm {
+    #[default]
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+    Wilson,
+    /// Works one row at a time, so under wrap it only uses horizontal seams.
+    Eller,
+}
+
+/// Maze cell `(x, y)` lives at lattice point `(2x + 1, 2y + 1)` with walls in
+/// between. A wrapped maze of `w × h` cells is `2w × 2h` so that the walls
+/// on row and column zero are shared across the seams.
+pub type MazeGrid = UniversalLattice<2, MazeCell>;
+
+const DIRECTIONS: [[i128; 2]; 4] = [[1, 0], [-1, 0], [0, 1], [0, -1]];
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct MazeGenerator {
+    size: UniversalLatticeSize<2>,
+    algorithm: MazeAlgorithm,
+    boundary_handling: BoundaryHandling,
+    seed: u64,
+}
+
+impl MazeGenerator {
+    /// `size` counts maze cells, not lattice points.
+    pub fn new(size: UniversalLatticeSize<2>) -> Self {
+        Self {
+            size: UniversalLatticeSize::new([size[0].max(1), size[1].max(1)]),
+            algorithm: MazeAlgorithm::default(),
+            boundary_handling: BoundaryHandling::default(),
+            seed: 0,
+        }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: MazeAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_boundary_handling(mut self, boundary_handling: BoundaryHandling) -> Self {
+        self.boundary_handling = boundary_handling;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn generate(&self) -> MazeGrid {
+        let mut carver = Carver::new(self.size, self.wraps());
+        let mut rng = StepContext::new(self.seed).rng(0);
+
+        match self.algorithm {
+            MazeAlgorithm::RecursiveBacktracker => carver.backtracker(&mut rng),
+            MazeAlgorithm::Prim => carver.prim(&mut rng),
+            MazeAlgorithm::Kruskal => carver.kruskal(&mut rng),
+            MazeAlgorithm::Wilson => carver.wilson(&mut rng),
+            MazeAlgorithm::Eller => carver.eller(&mut rng),
+        }
+
+        carver.grid
+    }
+
+    fn wraps(&self) -> bool {
+        self.boundary_handling == BoundaryHandling::Wrap
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MazeGenerator {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_algorithm(&mut self, algorithm: MazeAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_boundary_handling(&mut self, boundary_handling: BoundaryHandling) {
+        self.boundary_handling = boundary_handling;
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
+
+/// Lattice point of maze cell `cell`.
+pub fn cell_point(cell: &UniversalLatticePoint<2>) -> UniversalLatticePoint<2> {
+    UniversalLatticePoint::new([2 * cell[0] + 1, 2 * cell[1] + 1])
+}
+
+/// Shortest passage path from `start` to `goal`, both ends included, going
+/// through the seams when the grid wraps.
+pub fn solve(
+    grid: &MazeGrid,
+    start: &UniversalLatticePoint<2>,
+    goal: &UniversalLatticePoint<2>,
+) -> Option<Vec<UniversalLatticePoint<2>>> {
+    let wraps = grid.boundary_handling() == BoundaryHandling::Wrap;
+    let resolve = |point: UniversalLatticePoint<2>| {
+        if wraps {
+            Some(grid.transform_point(&point))
+        } else {
+            grid.contains(&point).then_some(point)
+        }
+    };
+
+    let (start, goal) = (resolve(*start)?, resolve(*goal)?);
+
+    if grid.get_state(&start) != MazeCell::Passage || grid.get_state(&goal) != MazeCell::Passage {
+        return None;
+    }
+
+    let offsets = VonNeumannNeighborhoodBuilder::offsets::<2>();
+    let mut previous = BTreeMap::from([(start, start)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(point) = queue.pop_front() {
+        if point == goal {
+            let mut path = vec![goal];
+
+            while let Some(last) = path.last().copied().filter(|last| *last != start) {
+                path.push(previous[&last]);
+            }
+
+            path.reverse();
+            return Some(path);
+        }
+
+        for offset in &offsets {
+            let Some(neighbor) = resolve(UniversalLatticePoint::new([
+                point[0] + offset[0],
+                point[1] + offset[1],
+            ])) else {
+                continue;
+            };
+
+            if grid.get_state(&neighbor) == MazeCell::Passage && !previous.contains_key(&neighbor) {
+                previous.insert(neighbor, point);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    None
+}
+
+/// Spanning-tree builder over the maze cells; every algorithm only decides
+/// which walls to knock down.
+struct Carver {
+    columns: usize,
+    rows: usize,
+    wraps: bool,
+    grid: MazeGrid,
+}
+
+impl Carver {
+    fn new(size: UniversalLatticeSize<2>, wraps: bool) -> Self {
+        let (columns, rows) = (size[0], size[1]);
+        let extra = usize::from(!wraps);
+        let mut grid = MazeGrid::from(UniversalLatticeSize::new([
+            2 * columns + extra,
+            2 * rows + extra,
+        ]));
+
+        if wraps {
+            grid.set_boundary_handling(BoundaryHandling::Wrap);
+        }
+
+        for index in 0..columns * rows {
+            let point = cell_point(&cell_of(index, columns));
+            grid.set_state(&point, &MazeCell::Passage);
+        }
+
+        Self {
+            columns,
+            rows,
+            wraps,
+            grid,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    fn neighbors(&self, index: usize) -> Vec<usize> {
+        let cell = cell_of(index, self.columns);
+        let (columns, rows) = (self.columns as i128, self.rows as i128);
+
+        DIRECTIONS
+            .iter()
+            .filter_map(|[dx, dy]| {
+                let (mut x, mut y) = (cell[0] + dx, cell[1] + dy);
+
+                if self.wraps {
+                    (x, y) = (x.rem_euclid(columns), y.rem_euclid(rows));
+                } else if !(0..columns).contains(&x) || !(0..rows).contains(&y) {
+                    return None;
+                }
+
+                let neighbor = (y * columns + x) as usize;
+                (neighbor != index).then_some(neighbor)
+            })
+            .collect()
+    }
+
+    fn carve(&mut self, from: usize, to: usize) {
+        let (from, to) = (cell_of(from, self.columns), cell_of(to, self.columns));
+
+        // A jump longer than one cell is a wrapped neighbor across the seam.
+        let step = |from: i128, to: i128| {
+            let delta = to - from;
+            if delta.abs() > 1 {
+                -delta.signum()
+            } else {
+                delta
+            }
+        };
+
+        let (dx, dy) = (step(from[0], to[0]), step(from[1], to[1]));
+        let center = cell_point(&from);
+        let wall = UniversalLatticePoint::new([center[0] + dx, center[1] + dy]);
+        let wall = self.grid.transform_point(&wall);
+
+        self.grid.set_state(&wall, &MazeCell::Passage);
+    }
+
+    fn backtracker(&mut self, rng: &mut StepRng) {
+        let mut visited = vec![false; self.len()];
+        let start = rng.gen_range(0..self.len());
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(&current) = stack.last() {
+            let unvisited: Vec<usize> = self
+                .neighbors(current)
+                .into_iter()
+                .filter(|neighbor| !visited[*neighbor])
+                .collect();
+
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    self.carve(current, next);
+                    visited[next] = true;
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    fn prim(&mut self, rng: &mut StepRng) {
+        let mut visited = vec![false; self.len()];
+        let start = rng.gen_range(0..self.len());
+        visited[start] = true;
+
+        let mut frontier: Vec<(usize, usize)> = self
+            .neighbors(start)
+            .into_iter()
+            .map(|next| (start, next))
+            .collect();
+
+        while !frontier.is_empty() {
+            let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+
+            if visited[to] {
+                continue;
+            }
+
+            self.carve(from, to);
+            visited[to] = true;
+
+            frontier.extend(
+                self.neighbors(to)
+                    .into_iter()
+                    .filter(|next| !visited[*next])
+                    .map(|next| (to, next)),
+            );
+        }
+    }
+
+    fn kruskal(&mut self, rng: &mut StepRng) {
+        let mut edges: Vec<(usize, usize)> = (0..self.len())
+            .flat_map(|from| {
+                self.neighbors(from)
+                    .into_iter()
+                    .filter(move |to| from < *to)
+                    .map(move |to| (from, to))
+            })
+            .collect();
+        edges.shuffle(rng);
+
+        let mut sets = DisjointSets::new(self.len());
+
+        for (from, to) in edges {
+            if sets.union(from, to) {
+                self.carve(from, to);
+            }
+        }
+    }
+
+    /// Loop-erased random walks: unbiased over all spanning trees.
+    fn wilson(&mut self, rng: &mut StepRng) {
+        let mut in_maze = vec![false; self.len()];
+        in_maze[rng.gen_range(0..self.len())] = true;
+
+        let mut next = vec![0; self.len()];
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.shuffle(rng);
+
+        for start in order {
+            let mut current = start;
+
+            while !in_maze[current] {
+                let neighbors = self.neighbors(current);
+                next[current] = neighbors[rng.gen_range(0..neighbors.len())];
+                current = next[current];
+            }
+
+            current = start;
+
+            while !in_maze[current] {
+                in_maze[current] = true;
+                self.carve(current, next[current]);
+                current = next[current];
+            }
+        }
+    }
+
+    fn eller(&mut self, rng: &mut StepRng) {
+        let columns = self.columns;
+        let mut sets: Vec<Option<usize>> = vec![None; columns];
+        let mut next_set = 0;
+
+        for row in 0..self.rows {
+            for set in sets.iter_mut().filter(|set| set.is_none()) {
+                *set = Some(next_set);
+                next_set += 1;
+            }
+
+            let last_row = row + 1 == self.rows;
+            let seams = if self.wraps && columns > 2 {
+                columns
+            } else {
+                columns - 1
+            };
+
+            for x in 0..seams {
+                let right = (x + 1) % columns;
+
+                if sets[x] != sets[right] && (last_row || rng.gen_bool(0.5)) {
+                    self.carve(row * columns + x, row * columns + right);
+
+                    let (from, to) = (sets[right], sets[x]);
+                    sets.iter_mut()
+                        .filter(|set| **set == from)
+                        .for_each(|set| *set = to);
+                }
+            }
+
+            if last_row {
+                break;
+            }
+
+            let mut below = vec![None; columns];
+            let mut members: Vec<usize> = (0..columns).collect();
+            members.shuffle(rng);
+
+            for x in members {
+                let set = sets[x];
+                let first_of_set = !below.contains(&set);
+
+                if first_of_set || rng.gen_bool(0.5) {
+                    self.carve(row * columns + x, (row + 1) * columns + x);
+                    below[x] = set;
+                }
+            }
+
+            sets = below;
+        }
+    }
+}
+
+fn cell_of(index: usize, columns: usize) -> UniversalLatticePoint<2> {
+    UniversalLatticePoint::new([(index % columns) as i128, (index / columns) as i128])
+}
+
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut item: usize) -> usize {
+        while self.parents[item] != item {
+            self.parents[item] = self.parents[self.parents[item]];
+            item = self.parents[item];
+        }
+
+        item
+    }
+
+    /// Returns `false` when both items were already in one set.
+    fn union(&mut self, left: usize, right: usize) -> bool {
+        let (left, right) = (self.find(left), self.find(right));
+        self.parents[left] = right;
+        left != right
+    }
+}
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(MazeLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(MazeLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct MazeLattice {
+    inner: MazeGrid,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl MazeGenerator {
+    #[wasm_bindgen(constructor)]
+    pub fn new_wrapper(size: MazeLatticeSize) -> Self {
+        Self::new(size.into())
+    }
+
+    #[wasm_bindgen(js_name = "generate")]
+    pub fn generate_wrapper(&self) -> MazeLattice {
+        MazeLattice {
+            inner: self.generate(),
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl MazeLattice {
+    #[wasm_bindgen]
+    pub fn get_state(&self, point: &MazeLatticePoint) -> MazeCell {
+        self.inner.get_state(&(*point).into())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn states(&self) -> Vec<MazeCell> {
+        self.inner.states()
+    }
+
+    #[wasm_bindgen]
+    pub fn size(&self) -> MazeLatticeSize {
+        self.inner.size().into()
+    }
+
+    #[wasm_bindgen]
+    pub fn cell_point(&self, cell: &MazeLatticePoint) -> MazeLatticePoint {
+        cell_point(&(*cell).into()).into()
+    }
+
+    /// Empty when the goal cannot be reached.
+    #[wasm_bindgen]
+    pub fn solve(
+        &self,
+        start: &MazeLatticePoint,
+        goal: &MazeLatticePoint,
+    ) -> Vec<MazeLatticePoint> {
+        solve(&self.inner, &(*start).into(), &(*goal).into())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|point| point.into())
+            .collect()
+    }
+}
@@ -0,0 +1,264 @@
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    automaton::pipeline::{AutomatonPhase, Pipeline, StepPhase},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    noise::{Noise, fill_with},
+    prelude::*,
+};
+
+pub type Heightmap = UniversalLattice<2, f32>;
+
+/// Heightmap in `[0, 1]` from a noise field. Its boundary is clamped so that
+/// erosion treats the edges as continuing terrain instead of a drop to zero.
+pub fn heightmap_from_noise<N: Noise<2>>(
+    size: UniversalLatticeSize<2>,
+    noise: &N,
+    frequency: f64,
+) -> Heightmap {
+    let mut heightmap = Heightmap::from(size);
+    heightmap.set_boundary_handling(BoundaryHandling::Clamp);
+    fill_with(&mut heightmap, noise, frequency, |value| {
+        ((value + 1.0) / 2.0) as f32
+    });
+    heightmap
+}
+
+/// Thermal erosion: wherever the drop to a neighbor exceeds the talus, a
+/// `rate` share of the excess slides down. Every exchange is computed from
+/// both cells' heights alone, so material is conserved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalErosionRule {
+    talus: f32,
+    rate: f32,
+}
+
+impl ThermalErosionRule {
+    pub fn new(talus: f32, rate: f32) -> Self {
+        Self {
+            talus: talus.max(0.0),
+            // Above a quarter the four exchanges can overshoot and oscillate.
+            rate: rate.clamp(0.0, 0.25),
+        }
+    }
+
+    pub fn talus(&self) -> f32 {
+        self.talus
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+}
+
+impl Default for ThermalErosionRule {
+    fn default() -> Self {
+        Self::new(0.01, 0.25)
+    }
+}
+
+impl Rule for ThermalErosionRule {
+    type State = f32;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        let exchange: f32 = neighbors
+            .iter_states()
+            .map(|neighbor| {
+                let drop = neighbor - current_state;
+                let excess = (drop.abs() - self.talus).max(0.0);
+                drop.signum() * excess * self.rate
+            })
+            .sum();
+
+        current_state + exchange
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThermalErosionAutomaton {
+    rule: ThermalErosionRule,
+}
+
+impl ThermalErosionAutomaton {
+    pub fn new(rule: ThermalErosionRule) -> Self {
+        Self { rule }
+    }
+}
+
+impl CellularAutomaton for ThermalErosionAutomaton {
+    type Lattice = Heightmap;
+    type Rule = ThermalErosionRule;
+    type NeighborhoodBuilder = VonNeumannNeighborhoodBuilder;
+
+    fn rule(&self) -> &Self::Rule {
+        &self.rule
+    }
+
+    fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+        VonNeumannNeighborhoodBuilder
+    }
+}
+
+/// Particle-based hydraulic erosion. Each application drops `droplets` water
+/// particles at random positions; a droplet rolls downhill with some inertia,
+/// picks up sediment while it has spare capacity and deposits it when it
+/// slows down or climbs, until it evaporates or leaves the map. Whatever a
+/// droplet still carries when it stops is dropped where it stands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HydraulicErosion {
+    pub droplets: usize,
+    pub lifetime: usize,
+    pub inertia: f32,
+    pub capacity: f32,
+    pub min_capacity: f32,
+    pub erosion: f32,
+    pub deposition: f32,
+    pub evaporation: f32,
+    pub gravity: f32,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            droplets: 1000,
+            lifetime: 30,
+            inertia: 0.05,
+            capacity: 4.0,
+            min_capacity: 0.01,
+            erosion: 0.3,
+            deposition: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+        }
+    }
+}
+
+impl HydraulicErosion {
+    fn simulate(&self, heightmap: &mut Heightmap, rng: &mut StepRng) {
+        let size = heightmap.size();
+        let (width, height) = (size[0] as f32, size[1] as f32);
+        let wraps = heightmap.boundary_handling() == BoundaryHandling::Wrap;
+
+        if size[0] < 2 || size[1] < 2 {
+            return;
+        }
+
+        let mut position = [
+            rng.gen_range(0.0..width - 1.0),
+            rng.gen_range(0.0..height - 1.0),
+        ];
+        let mut direction = [0.0f32; 2];
+        let (mut speed, mut water, mut sediment) = (1.0f32, 1.0f32, 0.0f32);
+
+        for _ in 0..self.lifetime {
+            let (current, gradient) = sample(heightmap, position);
+
+            direction = [0, 1]
+                .map(|axis| direction[axis] * self.inertia - gradient[axis] * (1.0 - self.inertia));
+
+            let length = direction[0].hypot(direction[1]);
+            if length <= f32::EPSILON {
+                break;
+            }
+
+            let previous = position;
+            position = [0, 1].map(|axis| position[axis] + direction[axis] / length);
+
+            if wraps {
+                position = [
+                    position[0].rem_euclid(width),
+                    position[1].rem_euclid(height),
+                ];
+            } else if !(0.0..width - 1.0).contains(&position[0])
+                || !(0.0..height - 1.0).contains(&position[1])
+            {
+                // Sediment carried off the map is lost with the droplet.
+                return;
+            }
+
+            let delta = sample(heightmap, position).0 - current;
+            let capacity = (-delta * speed * water * self.capacity).max(self.min_capacity);
+
+            if sediment > capacity || delta > 0.0 {
+                let deposit = if delta > 0.0 {
+                    delta.min(sediment)
+                } else {
+                    (sediment - capacity) * self.deposition
+                };
+
+                sediment -= deposit;
+                spread(heightmap, previous, deposit);
+            } else {
+                let eroded = ((capacity - sediment) * self.erosion).min(-delta);
+
+                sediment += eroded;
+                spread(heightmap, previous, -eroded);
+            }
+
+            speed = (speed * speed - delta * self.gravity).max(0.0).sqrt();
+            water *= 1.0 - self.evaporation;
+        }
+
+        spread(heightmap, position, sediment);
+    }
+}
+
+impl StepPhase<Heightmap> for HydraulicErosion {
+    fn apply(&mut self, lattice: &mut Heightmap, context: &StepContext) {
+        let mut rng = context.rng(0);
+
+        for _ in 0..self.droplets {
+            self.simulate(lattice, &mut rng);
+        }
+    }
+}
+
+/// Thermal then hydraulic erosion as one generation.
+pub fn erosion_pipeline(
+    thermal: ThermalErosionAutomaton,
+    hydraulic: HydraulicErosion,
+) -> Pipeline<Heightmap> {
+    Pipeline::new()
+        .with_phase(AutomatonPhase(thermal))
+        .with_phase(hydraulic)
+}
+
+fn corners(heightmap: &Heightmap, position: [f32; 2]) -> ([UniversalLatticePoint<2>; 4], [f32; 2]) {
+    let (x, y) = (position[0].floor(), position[1].floor());
+    let (cx, cy) = (x as i128, y as i128);
+
+    let points = [[cx, cy], [cx + 1, cy], [cx, cy + 1], [cx + 1, cy + 1]]
+        .map(|coords| heightmap.transform_point(&UniversalLatticePoint::new(coords)));
+
+    (points, [position[0] - x, position[1] - y])
+}
+
+/// Bilinear height and gradient at a fractional position.
+fn sample(heightmap: &Heightmap, position: [f32; 2]) -> (f32, [f32; 2]) {
+    let (points, [u, v]) = corners(heightmap, position);
+    let [nw, ne, sw, se] = points.map(|point| heightmap.get_state(&point));
+
+    let height = nw * (1.0 - u) * (1.0 - v) + ne * u * (1.0 - v) + sw * (1.0 - u) * v + se * u * v;
+    let gradient = [
+        (ne - nw) * (1.0 - v) + (se - sw) * v,
+        (sw - nw) * (1.0 - u) + (se - ne) * u,
+    ];
+
+    (height, gradient)
+}
+
+/// Adds `amount` to the four cells around a position, weighted bilinearly.
+fn spread(heightmap: &mut Heightmap, position: [f32; 2], amount: f32) {
+    let (points, [u, v]) = corners(heightmap, position);
+    let weights = [(1.0 - u) * (1.0 - v), u * (1.0 - v), (1.0 - u) * v, u * v];
+
+    for (point, weight) in points.iter().zip(weights) {
+        let height = heightmap.get_state(point);
+        heightmap.set_state(point, &(height + amount * weight));
+    }
+}
@@ -0,0 +1,577 @@
+use std::{collections::BTreeMap, fmt};
+
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+    utils::{flat_index_from_point, generate_points_until, point_from_flat_index, translate},
+};
+
+const DEFAULT_BACKTRACK_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfcError {
+    /// The model has no patterns, e.g. the sample was smaller than a pattern.
+    EmptyModel,
+    /// The constraints cannot be satisfied for the requested output.
+    Contradiction,
+    /// Backtracking gave up after this many attempts.
+    BacktrackLimit(usize),
+}
+
+impl fmt::Display for WfcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyModel => write!(f, "model has no patterns"),
+            Self::Contradiction => write!(f, "constraints cannot be satisfied"),
+            Self::BacktrackLimit(limit) => write!(f, "gave up after {limit} backtracks"),
+        }
+    }
+}
+
+impl std::error::Error for WfcError {}
+
+/// Overlapping model: learns every `pattern_size`-sided hypercube of a sample
+/// and tiles the output so that neighboring patterns agree on their overlap.
+///
+/// The sample is read periodically when its boundary handling is
+/// [`BoundaryHandling::Wrap`]; the output wraps under the same condition.
+#[derive(Debug, Clone)]
+pub struct OverlappingModel<const D: usize, S> {
+    pattern_size: usize,
+    patterns: Vec<Vec<S>>,
+    weights: Vec<f64>,
+    backtrack_limit: usize,
+}
+
+impl<const D: usize, S: Clone + Default + Ord> OverlappingModel<D, S> {
+    pub fn from_sample(sample: &UniversalLattice<D, S>, pattern_size: usize) -> Self {
+        let pattern_size = pattern_size.max(1);
+        let periodic = sample.boundary_handling() == BoundaryHandling::Wrap;
+        let size = sample.size();
+
+        let origins = UniversalLatticeSize::new(size.values().map(|length| {
+            if periodic {
+                length
+            } else {
+                (length + 1).saturating_sub(pattern_size)
+            }
+        }));
+
+        let mut counts: BTreeMap<Vec<S>, usize> = BTreeMap::new();
+        let offsets = cube_offsets::<D>(pattern_size);
+
+        for origin in generate_points_until(origins) {
+            let pattern = offsets
+                .iter()
+                .map(|offset| sample.get_state(&translate(&origin, offset)))
+                .collect();
+
+            *counts.entry(pattern).or_default() += 1;
+        }
+
+        let (patterns, weights) = counts
+            .into_iter()
+            .map(|(pattern, count)| (pattern, count as f64))
+            .unzip();
+
+        Self {
+            pattern_size,
+            patterns,
+            weights,
+            backtrack_limit: DEFAULT_BACKTRACK_LIMIT,
+        }
+    }
+
+    pub fn with_backtrack_limit(mut self, backtrack_limit: usize) -> Self {
+        self.backtrack_limit = backtrack_limit;
+        self
+    }
+
+    pub fn patterns(&self) -> &[Vec<S>] {
+        &self.patterns
+    }
+
+    pub fn generate(&self, output: &mut UniversalLattice<D, S>, seed: u64) -> Result<(), WfcError> {
+        if self.patterns.is_empty() {
+            return Err(WfcError::EmptyModel);
+        }
+
+        let periodic = output.boundary_handling() == BoundaryHandling::Wrap;
+        let size = output.size();
+        let wave_size = UniversalLatticeSize::new(size.values().map(|length| {
+            if periodic {
+                length
+            } else {
+                (length + 1).saturating_sub(self.pattern_size).max(1)
+            }
+        }));
+
+        let offsets = cube_offsets::<D>(self.pattern_size);
+        let propagator = (0..2 * D)
+            .map(|direction| {
+                (0..self.patterns.len())
+                    .map(|pattern| {
+                        Bitset::from_fn(self.patterns.len(), |other| {
+                            self.agrees(&offsets, pattern, other, direction)
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let solver = Solver::new(wave_size, periodic, self.weights.clone(), propagator);
+        let wave = solver.run(seed, self.backtrack_limit)?;
+
+        for point in output.points() {
+            let mut cell = point;
+            let mut offset = UniversalLatticePoint::new([0; D]);
+
+            for axis in 0..D {
+                cell[axis] = point[axis].min(wave_size[axis] as i128 - 1);
+                offset[axis] = point[axis] - cell[axis];
+            }
+
+            let pattern = wave[flat_index_from_point(&cell, wave_size)];
+            let index = flat_index_from_point(&offset, cube(self.pattern_size));
+            output.set_state(&point, &self.patterns[pattern][index]);
+        }
+
+        Ok(())
+    }
+
+    /// Whether `other` may sit one step from `pattern` in `direction`.
+    fn agrees(
+        &self,
+        offsets: &[UniversalLatticePoint<D>],
+        pattern: usize,
+        other: usize,
+        direction: usize,
+    ) -> bool {
+        let (axis, delta) = (
+            direction / 2,
+            if direction.is_multiple_of(2) { -1 } else { 1 },
+        );
+        let side = self.pattern_size as i128;
+
+        offsets.iter().enumerate().all(|(index, offset)| {
+            let shifted = offset[axis] - delta;
+
+            if !(0..side).contains(&shifted) {
+                return true;
+            }
+
+            let mut other_offset = *offset;
+            other_offset[axis] = shifted;
+            let other_index = flat_index_from_point(&other_offset, cube(self.pattern_size));
+
+            self.patterns[pattern][index] == self.patterns[other][other_index]
+        })
+    }
+}
+
+/// Simple tiled model: every cell holds one tile and explicit adjacency rules
+/// say which tile may follow another along each axis.
+#[derive(Debug, Clone)]
+pub struct SimpleTiledModel<const D: usize, S> {
+    tiles: Vec<S>,
+    weights: Vec<f64>,
+    adjacency: Vec<Vec<Vec<bool>>>,
+    backtrack_limit: usize,
+}
+
+impl<const D: usize, S: Clone + Default + Ord> Default for SimpleTiledModel<D, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const D: usize, S: Clone + Default + Ord> SimpleTiledModel<D, S> {
+    pub fn new() -> Self {
+        Self {
+            tiles: Vec::new(),
+            weights: Vec::new(),
+            adjacency: vec![Vec::new(); D],
+            backtrack_limit: DEFAULT_BACKTRACK_LIMIT,
+        }
+    }
+
+    /// Learns tiles, frequencies and adjacencies from the cells of a sample.
+    pub fn from_sample(sample: &UniversalLattice<D, S>) -> Self {
+        let mut model = Self::new();
+        let mut indices = BTreeMap::new();
+
+        for state in sample.states() {
+            let index = *indices
+                .entry(state.clone())
+                .or_insert_with(|| model.add_tile(state, 0.0));
+            model.weights[index] += 1.0;
+        }
+
+        let periodic = sample.boundary_handling() == BoundaryHandling::Wrap;
+
+        for point in sample.points() {
+            for axis in 0..D {
+                let mut next = point;
+                next[axis] += 1;
+
+                if !periodic && !sample.contains(&next) {
+                    continue;
+                }
+
+                let lower = indices[&sample.get_state(&point)];
+                let upper = indices[&sample.get_state(&next)];
+                model.allow(axis, lower, upper);
+            }
+        }
+
+        model
+    }
+
+    pub fn with_backtrack_limit(mut self, backtrack_limit: usize) -> Self {
+        self.backtrack_limit = backtrack_limit;
+        self
+    }
+
+    pub fn add_tile(&mut self, state: S, weight: f64) -> usize {
+        self.tiles.push(state);
+        self.weights.push(weight);
+
+        for axis in self.adjacency.iter_mut() {
+            axis.iter_mut().for_each(|row| row.push(false));
+            axis.push(vec![false; self.tiles.len()]);
+        }
+
+        self.tiles.len() - 1
+    }
+
+    /// Allows tile `upper` one step after tile `lower` along `axis`.
+    pub fn allow(&mut self, axis: usize, lower: usize, upper: usize) {
+        if let Some(allowed) = self
+            .adjacency
+            .get_mut(axis)
+            .and_then(|axis| axis.get_mut(lower))
+            .and_then(|row| row.get_mut(upper))
+        {
+            *allowed = true;
+        }
+    }
+
+    pub fn tiles(&self) -> &[S] {
+        &self.tiles
+    }
+
+    pub fn generate(&self, output: &mut UniversalLattice<D, S>, seed: u64) -> Result<(), WfcError> {
+        if self.tiles.is_empty() {
+            return Err(WfcError::EmptyModel);
+        }
+
+        let count = self.tiles.len();
+        let propagator = (0..2 * D)
+            .map(|direction| {
+                let axis = direction / 2;
+
+                (0..count)
+                    .map(|tile| {
+                        Bitset::from_fn(count, |other| {
+                            if direction.is_multiple_of(2) {
+                                self.adjacency[axis][other][tile]
+                            } else {
+                                self.adjacency[axis][tile][other]
+                            }
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let periodic = output.boundary_handling() == BoundaryHandling::Wrap;
+        let solver = Solver::new(output.size(), periodic, self.weights.clone(), propagator);
+        let wave = solver.run(seed, self.backtrack_limit)?;
+
+        for (index, point) in output.points().into_iter().enumerate() {
+            output.set_state(&point, &self.tiles[wave[index]]);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bitset(Vec<u64>);
+
+impl Bitset {
+    fn from_fn(len: usize, f: impl Fn(usize) -> bool) -> Self {
+        let mut words = vec![0; len.div_ceil(64)];
+        (0..len)
+            .filter(|bit| f(*bit))
+            .for_each(|bit| words[bit / 64] |= 1 << (bit % 64));
+        Self(words)
+    }
+
+    fn remove(&mut self, bit: usize) {
+        self.0[bit / 64] &= !(1 << (bit % 64));
+    }
+
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(word_index, word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| word_index * 64 + bit)
+        })
+    }
+
+    fn count(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WaveCell {
+    allowed: Bitset,
+    weight_sum: f64,
+    weight_log_sum: f64,
+}
+
+struct Decision {
+    cell: usize,
+    pattern: usize,
+    trail_len: usize,
+}
+
+/// Constraint solver shared by both models. The wave keeps a bitset of
+/// allowed patterns per cell; every change is recorded on a trail so that a
+/// contradiction rolls back to the last decision instead of restarting.
+struct Solver<const D: usize> {
+    size: UniversalLatticeSize<D>,
+    periodic: bool,
+    weights: Vec<f64>,
+    propagator: Vec<Vec<Bitset>>,
+    wave: Vec<WaveCell>,
+    trail: Vec<(usize, WaveCell)>,
+}
+
+impl<const D: usize> Solver<D> {
+    fn new(
+        size: UniversalLatticeSize<D>,
+        periodic: bool,
+        weights: Vec<f64>,
+        propagator: Vec<Vec<Bitset>>,
+    ) -> Self {
+        let all = Bitset::from_fn(weights.len(), |_| true);
+        let cells = size.values().iter().product();
+
+        let mut solver = Self {
+            size,
+            periodic,
+            weights,
+            propagator,
+            wave: Vec::new(),
+            trail: Vec::new(),
+        };
+
+        let cell = solver.describe(all);
+        solver.wave = vec![cell; cells];
+        solver
+    }
+
+    fn run(mut self, seed: u64, backtrack_limit: usize) -> Result<Vec<usize>, WfcError> {
+        let mut rng = StepContext::new(seed).rng(0);
+        let mut decisions: Vec<Decision> = Vec::new();
+        let mut backtracks = 0;
+
+        if !self.propagate((0..self.wave.len()).collect()) {
+            return Err(WfcError::Contradiction);
+        }
+
+        while let Some(cell) = self.lowest_entropy(&mut rng) {
+            let pattern = self.choose(cell, &mut rng);
+            decisions.push(Decision {
+                cell,
+                pattern,
+                trail_len: self.trail.len(),
+            });
+
+            let allowed = Bitset::from_fn(self.weights.len(), |other| other == pattern);
+            if self.restrict(cell, allowed) && self.propagate(vec![cell]) {
+                continue;
+            }
+
+            loop {
+                let Some(decision) = decisions.pop() else {
+                    return Err(WfcError::Contradiction);
+                };
+
+                backtracks += 1;
+                if backtracks > backtrack_limit {
+                    return Err(WfcError::BacktrackLimit(backtrack_limit));
+                }
+
+                self.undo(decision.trail_len);
+
+                let mut allowed = self.wave[decision.cell].allowed.clone();
+                allowed.remove(decision.pattern);
+
+                if self.restrict(decision.cell, allowed) && self.propagate(vec![decision.cell]) {
+                    break;
+                }
+            }
+        }
+
+        Ok(self
+            .wave
+            .iter()
+            .map(|cell| cell.allowed.ones().next().unwrap_or_default())
+            .collect())
+    }
+
+    fn describe(&self, allowed: Bitset) -> WaveCell {
+        let (weight_sum, weight_log_sum) =
+            allowed.ones().fold((0.0, 0.0), |(sum, log_sum), pattern| {
+                let weight = self.weights[pattern];
+                (
+                    sum + weight,
+                    log_sum
+                        + if weight > 0.0 {
+                            weight * weight.ln()
+                        } else {
+                            0.0
+                        },
+                )
+            });
+
+        WaveCell {
+            allowed,
+            weight_sum,
+            weight_log_sum,
+        }
+    }
+
+    /// Narrows a cell to `allowed`, returning `false` on a contradiction.
+    fn restrict(&mut self, cell: usize, allowed: Bitset) -> bool {
+        if allowed == self.wave[cell].allowed {
+            return true;
+        }
+
+        let described = self.describe(allowed);
+        let previous = std::mem::replace(&mut self.wave[cell], described);
+        self.trail.push((cell, previous));
+
+        self.wave[cell].allowed.count() > 0
+    }
+
+    fn undo(&mut self, trail_len: usize) {
+        while self.trail.len() > trail_len {
+            if let Some((cell, previous)) = self.trail.pop() {
+                self.wave[cell] = previous;
+            }
+        }
+    }
+
+    fn propagate(&mut self, mut stack: Vec<usize>) -> bool {
+        while let Some(cell) = stack.pop() {
+            for direction in 0..2 * D {
+                let Some(neighbor) = self.neighbor(cell, direction) else {
+                    continue;
+                };
+
+                let mut supported = Bitset(vec![0; self.wave[neighbor].allowed.0.len()]);
+                for pattern in self.wave[cell].allowed.ones() {
+                    let compatible = &self.propagator[direction][pattern];
+                    supported
+                        .0
+                        .iter_mut()
+                        .zip(&compatible.0)
+                        .for_each(|(word, bits)| *word |= bits);
+                }
+
+                let mut allowed = self.wave[neighbor].allowed.clone();
+                allowed
+                    .0
+                    .iter_mut()
+                    .zip(&supported.0)
+                    .for_each(|(word, bits)| *word &= bits);
+
+                if allowed == self.wave[neighbor].allowed {
+                    continue;
+                }
+
+                if !self.restrict(neighbor, allowed) {
+                    return false;
+                }
+
+                stack.push(neighbor);
+            }
+        }
+
+        true
+    }
+
+    fn lowest_entropy(&self, rng: &mut StepRng) -> Option<usize> {
+        self.wave
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.allowed.count() > 1)
+            .map(|(index, cell)| {
+                let entropy = if cell.weight_sum > 0.0 {
+                    cell.weight_sum.ln() - cell.weight_log_sum / cell.weight_sum
+                } else {
+                    0.0
+                };
+
+                // A little noise breaks ties without favouring low indices.
+                (index, entropy + rng.gen_range(0.0..1e-6))
+            })
+            .min_by(|(_, left), (_, right)| left.total_cmp(right))
+            .map(|(index, _)| index)
+    }
+
+    fn choose(&self, cell: usize, rng: &mut StepRng) -> usize {
+        let allowed: Vec<usize> = self.wave[cell].allowed.ones().collect();
+        let total: f64 = allowed.iter().map(|pattern| self.weights[*pattern]).sum();
+
+        if total <= 0.0 {
+            return allowed[rng.gen_range(0..allowed.len())];
+        }
+
+        let mut threshold = rng.gen_range(0.0..total);
+
+        for pattern in &allowed {
+            threshold -= self.weights[*pattern];
+
+            if threshold < 0.0 {
+                return *pattern;
+            }
+        }
+
+        allowed[allowed.len() - 1]
+    }
+
+    fn neighbor(&self, cell: usize, direction: usize) -> Option<usize> {
+        let (axis, delta) = (
+            direction / 2,
+            if direction.is_multiple_of(2) { -1 } else { 1 },
+        );
+        let mut point = point_from_flat_index(cell, self.size);
+        let length = self.size[axis] as i128;
+
+        point[axis] += delta;
+
+        if self.periodic {
+            point[axis] = point[axis].rem_euclid(length);
+        } else if !(0..length).contains(&point[axis]) {
+            return None;
+        }
+
+        Some(flat_index_from_point(&point, self.size))
+    }
+}
+
+fn cube<const D: usize>(side: usize) -> UniversalLatticeSize<D> {
+    UniversalLatticeSize::new([side; D])
+}
+
+fn cube_offsets<const D: usize>(side: usize) -> Vec<UniversalLatticePoint<D>> {
+    generate_points_until(cube(side))
+}
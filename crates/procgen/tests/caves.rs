@@ -0,0 +1,49 @@
+use procgen::caves::{CaveGenerator, Tile};
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    analysis::regions::{Connectivity, label_regions},
+    prelude::*,
+};
+
+#[test]
+fn caves_form_a_single_floor_region() {
+    for seed in 0..8 {
+        for tunnel_radius in [0, 1] {
+            let grid = CaveGenerator::new(UniversalLatticeSize::new([48, 32]))
+                .with_tunnel_radius(tunnel_radius)
+                .with_seed(seed)
+                .generate();
+
+            let regions =
+                label_regions(&grid, Connectivity::VonNeumann, |tile| *tile == Tile::Floor);
+            assert_eq!(regions.len(), 1, "seed {seed}, radius {tunnel_radius}");
+        }
+    }
+}
+
+#[test]
+fn border_stays_wall() {
+    let grid = CaveGenerator::new(UniversalLatticeSize::new([40, 30]))
+        .with_tunnel_radius(2)
+        .with_seed(4)
+        .generate();
+
+    for point in grid.points() {
+        if [point[0], point[1]].contains(&0) || point[0] == 39 || point[1] == 29 {
+            assert_eq!(grid.get_state(&point), Tile::Wall);
+        }
+    }
+}
+
+#[test]
+fn nan_fill_probability_counts_as_zero() {
+    let generate = |fill_probability| {
+        CaveGenerator::new(UniversalLatticeSize::new([24, 16]))
+            .with_fill_probability(fill_probability)
+            .with_seed(3)
+            .generate()
+            .states()
+    };
+
+    assert_eq!(generate(f64::NAN), generate(0.0));
+}
@@ -0,0 +1,60 @@
+use procgen::growth::{DiffusionLimitedAggregation, EdenGrowth, GrowthModel, InvasionPercolation};
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    analysis::regions::{Connectivity, label_regions},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+};
+
+/// Grows `particles` cells on an empty lattice and returns the lattice with
+/// the number of cells that stuck.
+fn grow(model: &impl GrowthModel<2>, particles: usize) -> (UniversalLattice<2, bool>, usize) {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([15, 15]));
+    let added = model.grow(&mut lattice, true, particles);
+    (lattice, added.len())
+}
+
+fn assert_one_cluster((lattice, added): (UniversalLattice<2, bool>, usize), particles: usize) {
+    assert_eq!(added, particles);
+
+    let clusters = label_regions(&lattice, Connectivity::VonNeumann, |state| *state);
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters.regions()[0].area(), particles + 1);
+}
+
+#[test]
+fn models_grow_one_connected_cluster() {
+    let builder = VonNeumannNeighborhoodBuilder;
+
+    assert_one_cluster(
+        grow(&DiffusionLimitedAggregation::new(builder).with_seed(1), 20),
+        20,
+    );
+    assert_one_cluster(grow(&EdenGrowth::new(builder).with_seed(1), 20), 20);
+    assert_one_cluster(
+        grow(&InvasionPercolation::new(builder).with_seed(1), 20),
+        20,
+    );
+}
+
+#[test]
+fn vanishing_sticking_probability_gives_up() {
+    let builder = VonNeumannNeighborhoodBuilder;
+
+    for probability in [0.0, -1.0, f64::NAN] {
+        let eden = EdenGrowth::new(builder)
+            .with_sticking_probability(probability)
+            .with_max_attempts(1_000);
+        assert_eq!(grow(&eden, 5).1, 0, "{probability}");
+
+        let invasion = InvasionPercolation::new(builder)
+            .with_sticking_probability(probability)
+            .with_max_attempts(1_000);
+        assert_eq!(grow(&invasion, 5).1, 0, "{probability}");
+
+        let aggregation = DiffusionLimitedAggregation::new(builder)
+            .with_sticking_probability(probability)
+            .with_max_steps(1_000);
+        assert_eq!(grow(&aggregation, 5).1, 0, "{probability}");
+    }
+}
@@ -0,0 +1,80 @@
+use procgen::lsystem::{
+    LSystem, Module, Production, format_modules, parse_modules,
+    turtle::{Turtle, rasterize},
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+fn modules(text: &str) -> Vec<Module> {
+    parse_modules(text).expect("valid modules")
+}
+
+#[test]
+fn algae_grows_fibonacci_strings() {
+    let system = LSystem::new(modules("A"))
+        .with_production(Production::new('A', modules("AB")))
+        .with_production(Production::new('B', modules("A")));
+
+    let lengths: Vec<usize> = (0..7).map(|n| system.generate(n).len()).collect();
+
+    assert_eq!(lengths, [1, 2, 3, 5, 8, 13, 21]);
+    assert_eq!(format_modules(&system.generate(4)), "ABAABABA");
+}
+
+#[test]
+fn turtle_turns_and_branches() {
+    let segments = Turtle::new(2.0, 90.0).interpret(&modules("F[+F]F"));
+    let ends: Vec<[i64; 2]> = segments
+        .iter()
+        .map(|segment| [segment.end[0].round() as i64, segment.end[1].round() as i64])
+        .collect();
+
+    assert_eq!(ends, [[0, -2], [-2, -2], [0, -4]]);
+    assert_eq!(
+        segments
+            .iter()
+            .map(|segment| segment.depth)
+            .collect::<Vec<_>>(),
+        [0, 1, 0]
+    );
+}
+
+#[test]
+fn rasterize_draws_every_cell_of_a_segment() {
+    let segments = Turtle::new(4.0, 90.0).interpret(&modules("F+F"));
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([9, 9]));
+
+    rasterize(
+        &segments,
+        &mut lattice,
+        &UniversalLatticePoint::new([6, 6]),
+        &true,
+    );
+
+    let drawn = lattice.sparse_points();
+    assert_eq!(drawn.len(), 9);
+    assert!((2..=6).all(|y| lattice.get_state(&UniversalLatticePoint::new([6, y]))));
+    assert!((2..=6).all(|x| lattice.get_state(&UniversalLatticePoint::new([x, 2]))));
+}
+
+#[test]
+fn rasterize_keeps_extra_axes_at_the_origin() {
+    let segments = Turtle::new(3.0, 90.0).interpret(&modules("F"));
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([2, 5, 2, 3]));
+
+    rasterize(
+        &segments,
+        &mut lattice,
+        &UniversalLatticePoint::new([1, 4, 1, 2]),
+        &true,
+    );
+
+    let drawn = lattice.sparse_points();
+    let expected: Vec<_> = (1..=4)
+        .map(|y| UniversalLatticePoint::new([1, y, 1, 2]))
+        .collect();
+    assert_eq!(drawn, expected);
+}
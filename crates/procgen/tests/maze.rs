@@ -0,0 +1,72 @@
+use procgen::maze::{MazeAlgorithm, MazeCell, MazeGenerator, MazeGrid, cell_point, solve};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    prelude::*,
+};
+
+const ALGORITHMS: [MazeAlgorithm; 5] = [
+    MazeAlgorithm::RecursiveBacktracker,
+    MazeAlgorithm::Prim,
+    MazeAlgorithm::Kruskal,
+    MazeAlgorithm::Wilson,
+    MazeAlgorithm::Eller,
+];
+
+fn passages(grid: &MazeGrid) -> Vec<UniversalLatticePoint<2>> {
+    grid.points()
+        .into_iter()
+        .filter(|point| grid.get_state(point) == MazeCell::Passage)
+        .collect()
+}
+
+#[test]
+fn mazes_are_spanning_trees() {
+    let (columns, rows) = (9, 7);
+
+    for algorithm in ALGORITHMS {
+        let grid = MazeGenerator::new(UniversalLatticeSize::new([columns, rows]))
+            .with_algorithm(algorithm)
+            .with_seed(2)
+            .generate();
+        let passages = passages(&grid);
+
+        // A tree over the cells has one passage per cell and per edge, and
+        // passages only touch along those edges.
+        let cells = columns * rows;
+        assert_eq!(passages.len(), 2 * cells - 1, "{algorithm:?}");
+
+        let adjacencies = passages
+            .iter()
+            .flat_map(|point| {
+                [[1, 0], [0, 1]]
+                    .map(|[dx, dy]| UniversalLatticePoint::new([point[0] + dx, point[1] + dy]))
+            })
+            .filter(|neighbor| grid.get_state(neighbor) == MazeCell::Passage)
+            .count();
+        assert_eq!(adjacencies, passages.len() - 1, "{algorithm:?}");
+    }
+}
+
+#[test]
+fn solve_finds_a_connected_path() {
+    for algorithm in ALGORITHMS {
+        for boundary_handling in [BoundaryHandling::Default, BoundaryHandling::Wrap] {
+            let grid = MazeGenerator::new(UniversalLatticeSize::new([8, 6]))
+                .with_algorithm(algorithm)
+                .with_boundary_handling(boundary_handling)
+                .with_seed(5)
+                .generate();
+
+            let start = cell_point(&UniversalLatticePoint::new([0, 0]));
+            let goal = cell_point(&UniversalLatticePoint::new([7, 5]));
+            let path = solve(&grid, &start, &goal).expect("every cell is reachable");
+
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(&goal));
+            assert!(
+                path.iter()
+                    .all(|point| grid.get_state(point) == MazeCell::Passage)
+            );
+        }
+    }
+}
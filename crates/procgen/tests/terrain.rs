@@ -0,0 +1,97 @@
+use procgen::terrain::{
+    Heightmap, HydraulicErosion, ThermalErosionAutomaton, ThermalErosionRule, heightmap_from_noise,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    automaton::pipeline::StepPhase,
+    noise::perlin::PerlinNoise,
+    prelude::*,
+};
+
+fn terrain(boundary_handling: BoundaryHandling) -> Heightmap {
+    let mut heightmap = heightmap_from_noise(
+        UniversalLatticeSize::new([32, 32]),
+        &PerlinNoise::new(3),
+        0.15,
+    );
+    heightmap.set_boundary_handling(boundary_handling);
+    heightmap
+}
+
+fn total(heightmap: &Heightmap) -> f64 {
+    heightmap
+        .points()
+        .iter()
+        .map(|point| heightmap.get_state(point) as f64)
+        .sum()
+}
+
+/// Largest height difference between horizontally or vertically adjacent
+/// cells.
+fn steepest_slope(heightmap: &Heightmap) -> f32 {
+    heightmap
+        .points()
+        .iter()
+        .flat_map(|point| {
+            [[1, 0], [0, 1]].map(|[dx, dy]| {
+                let neighbor = UniversalLatticePoint::new([point[0] + dx, point[1] + dy]);
+                (heightmap.get_state(point) - heightmap.get_state(&neighbor)).abs()
+            })
+        })
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn thermal_erosion_conserves_material_and_flattens_slopes() {
+    let automaton = ThermalErosionAutomaton::new(ThermalErosionRule::new(0.005, 0.25));
+
+    for boundary_handling in [BoundaryHandling::Clamp, BoundaryHandling::Wrap] {
+        let mut heightmap = terrain(boundary_handling);
+        let (mass, slope) = (total(&heightmap), steepest_slope(&heightmap));
+
+        for _ in 0..50 {
+            automaton.step(&mut heightmap);
+        }
+
+        assert!(
+            (total(&heightmap) - mass).abs() < 1e-3,
+            "{boundary_handling:?}"
+        );
+        assert!(steepest_slope(&heightmap) < slope, "{boundary_handling:?}");
+    }
+}
+
+#[test]
+fn thermal_erosion_keeps_slopes_below_the_talus() {
+    let automaton = ThermalErosionAutomaton::new(ThermalErosionRule::new(1.0, 0.25));
+    let mut heightmap = terrain(BoundaryHandling::Clamp);
+    let before = heightmap.states();
+
+    automaton.step(&mut heightmap);
+
+    assert_eq!(heightmap.states(), before);
+}
+
+#[test]
+fn hydraulic_erosion_on_a_torus_conserves_material() {
+    let mut heightmap = terrain(BoundaryHandling::Wrap);
+    let mass = total(&heightmap);
+    let before = heightmap.states();
+
+    HydraulicErosion::default().apply(&mut heightmap, &StepContext::new(7));
+
+    assert_ne!(heightmap.states(), before);
+    assert!((total(&heightmap) - mass).abs() < 1e-2);
+}
+
+#[test]
+fn hydraulic_erosion_is_seeded() {
+    let erode = |seed| {
+        let mut heightmap = terrain(BoundaryHandling::Clamp);
+        HydraulicErosion::default().apply(&mut heightmap, &StepContext::new(seed));
+        heightmap.states()
+    };
+
+    assert_eq!(erode(1), erode(1));
+    assert_ne!(erode(1), erode(2));
+}
@@ -0,0 +1,95 @@
+use procgen::wfc::{OverlappingModel, SimpleTiledModel, WfcError};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+fn grid(rows: &[&str]) -> UniversalLattice<2, char> {
+    UniversalLattice::from_states(
+        rows.iter().flat_map(|row| row.chars()).collect(),
+        UniversalLatticeSize::new([rows[0].len(), rows.len()]),
+    )
+}
+
+fn rows(lattice: &UniversalLattice<2, char>) -> Vec<String> {
+    lattice
+        .states()
+        .chunks(lattice.size()[0])
+        .map(|row| row.iter().collect())
+        .collect()
+}
+
+#[test]
+fn simple_tiled_model_respects_adjacency() {
+    let mut model = SimpleTiledModel::<2, char>::new();
+    let a = model.add_tile('a', 1.0);
+    let b = model.add_tile('b', 1.0);
+    // Tiles alternate along x and repeat along y.
+    model.allow(0, a, b);
+    model.allow(0, b, a);
+    model.allow(1, a, a);
+    model.allow(1, b, b);
+
+    let mut output = UniversalLattice::from(UniversalLatticeSize::new([9, 5]));
+    model.generate(&mut output, 3).unwrap();
+
+    let rows = rows(&output);
+    assert!(rows[0] == "ababababa" || rows[0] == "babababab");
+    assert!(rows.iter().all(|row| *row == rows[0]));
+}
+
+#[test]
+fn generation_is_deterministic_per_seed() {
+    let sample = grid(&["aab.", "abb.", "....", "b..a"]);
+    let model = SimpleTiledModel::from_sample(&sample);
+
+    let generate = |seed| {
+        let mut output = UniversalLattice::from(UniversalLatticeSize::new([12, 12]));
+        model.generate(&mut output, seed).map(|_| rows(&output))
+    };
+
+    assert_eq!(generate(5), generate(5));
+}
+
+#[test]
+fn overlapping_model_reproduces_sample_windows() {
+    let mut sample = grid(&["ab..", "ab..", "ab..", "ab.."]);
+    sample.set_boundary_handling(BoundaryHandling::Wrap);
+    let model = OverlappingModel::from_sample(&sample, 2);
+
+    let mut output = UniversalLattice::from(UniversalLatticeSize::new([10, 6]));
+    output.set_boundary_handling(BoundaryHandling::Wrap);
+    model.generate(&mut output, 8).unwrap();
+
+    // Every 2×2 window of the output is one of the learned patterns.
+    for point in output.points() {
+        let window: Vec<char> = [[0, 0], [1, 0], [0, 1], [1, 1]]
+            .iter()
+            .map(|[dx, dy]| {
+                output.get_state(&UniversalLatticePoint::new([point[0] + dx, point[1] + dy]))
+            })
+            .collect();
+
+        assert!(
+            model.patterns().contains(&window),
+            "{window:?} at {point:?}"
+        );
+    }
+}
+
+#[test]
+fn unsatisfiable_rules_fail() {
+    let mut model = SimpleTiledModel::<2, char>::new();
+    model.add_tile('a', 1.0);
+
+    let mut output = UniversalLattice::from(UniversalLatticeSize::new([3, 3]));
+    assert!(matches!(
+        model.generate(&mut output, 0),
+        Err(WfcError::Contradiction | WfcError::BacktrackLimit(_))
+    ));
+    assert_eq!(
+        SimpleTiledModel::<2, char>::new().generate(&mut output, 0),
+        Err(WfcError::EmptyModel)
+    );
+}
@@ -0,0 +1,216 @@
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
+use toolkit::{
+    aliases::UniversalLatticePoint,
+    automaton::context::StepContext,
+    lattice::universal_lattice::UniversalLattice,
+    types::{CellularAutomaton, Lattice, NeighborhoodBuilder, Rule},
+};
+
+#[cfg(feature = "wasm")]
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    types::{BoundaryHandling, BoundaryHandlingLattice},
+};
+
+use crate::{
+    rule::GrayScottRule,
+    state::GrayScottState,
+    stencil::{LaplacianStencil, StencilNeighborhoodBuilder},
+};
+
+#[cfg(feature = "wasm")]
+use crate::field::{Chemical, ScalarField};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Integrator {
+    /// Forward Euler for both reaction and diffusion.
+    #[default]
+    Explicit,
+    /// Explicit reaction with implicit diffusion, solved by Jacobi sweeps.
+    SemiImplicit,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GrayScottAutomaton {
+    rule: GrayScottRule,
+    integrator: Integrator,
+    jacobi_iterations: usize,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GrayScottAutomaton {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(rule: GrayScottRule, integrator: Integrator) -> Self {
+        Self {
+            rule,
+            integrator,
+            jacobi_iterations: 8,
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn jacobi_iterations(&self) -> usize {
+        self.jacobi_iterations
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(setter))]
+    pub fn set_jacobi_iterations(&mut self, jacobi_iterations: usize) {
+        self.jacobi_iterations = jacobi_iterations.max(1);
+    }
+}
+
+impl GrayScottAutomaton {
+    /// Fills a `radius`-sized square around `center` with the `v` chemical,
+    /// the usual way to start a pattern.
+    pub fn seed_square(
+        lattice: &mut UniversalLattice<2, GrayScottState>,
+        center: &UniversalLatticePoint<2>,
+        radius: i128,
+    ) {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let point = UniversalLatticePoint::new([center[0] + dx, center[1] + dy]);
+                lattice.set_state(&point, &GrayScottState::new(0.5, 0.25));
+            }
+        }
+    }
+
+    fn explicit_step(&self, lattice: &mut UniversalLattice<2, GrayScottState>) {
+        let points = lattice.points();
+        let builder = self.neighborhood_builder();
+
+        let new_states: Vec<_> = points
+            .iter()
+            .map(|point| {
+                let neighborhood = builder.build_neighborhood(point, lattice);
+                self.rule.apply(&lattice.get_state(point), &neighborhood)
+            })
+            .collect();
+
+        for (point, state) in points.iter().zip(new_states) {
+            lattice.set_state(point, &state);
+        }
+    }
+
+    fn semi_implicit_step(&self, lattice: &mut UniversalLattice<2, GrayScottState>) {
+        let points = lattice.points();
+        let builder = self.neighborhood_builder();
+        let time_step = self.rule.time_step();
+
+        let rhs: Vec<_> = points
+            .iter()
+            .map(|point| {
+                let state = lattice.get_state(point);
+                let (reaction_u, reaction_v) = self.rule.reaction(&state);
+
+                GrayScottState::new(
+                    state.u + time_step * reaction_u,
+                    state.v + time_step * reaction_v,
+                )
+            })
+            .collect();
+
+        let scale_u = time_step * self.rule.diffusion_u();
+        let scale_v = time_step * self.rule.diffusion_v();
+        let diagonal_u = 1.0 - scale_u * LaplacianStencil::CENTER_WEIGHT;
+        let diagonal_v = 1.0 - scale_v * LaplacianStencil::CENTER_WEIGHT;
+
+        for (point, state) in points.iter().zip(&rhs) {
+            lattice.set_state(point, state);
+        }
+
+        for _ in 0..self.jacobi_iterations {
+            let iterate: Vec<_> = points
+                .iter()
+                .zip(&rhs)
+                .map(|(point, rhs)| {
+                    let neighborhood = builder.build_neighborhood(point, lattice);
+                    let (sum_u, sum_v) = self.rule.neighbor_sums(&neighborhood);
+
+                    GrayScottState::new(
+                        (rhs.u + scale_u * sum_u) / diagonal_u,
+                        (rhs.v + scale_v * sum_v) / diagonal_v,
+                    )
+                })
+                .collect();
+
+            for (point, state) in points.iter().zip(iterate) {
+                lattice.set_state(point, &state);
+            }
+        }
+    }
+}
+
+impl CellularAutomaton for GrayScottAutomaton {
+    type Lattice = UniversalLattice<2, GrayScottState>;
+    type Rule = GrayScottRule;
+    type NeighborhoodBuilder = StencilNeighborhoodBuilder;
+
+    fn rule(&self) -> &Self::Rule {
+        &self.rule
+    }
+
+    fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+        StencilNeighborhoodBuilder::new(self.rule.stencil())
+    }
+
+    fn step_with_context(&self, lattice: &mut Self::Lattice, context: &mut StepContext) {
+        match self.integrator {
+            Integrator::Explicit => self.explicit_step(lattice),
+            Integrator::SemiImplicit => self.semi_implicit_step(lattice),
+        }
+
+        context.advance();
+    }
+}
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(GrayScottLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(GrayScottLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+type InnerLattice = UniversalLattice<2, GrayScottState>;
+#[cfg(feature = "wasm")]
+define_lattice_wrapper!(
+    GrayScottLattice,
+    GrayScottState,
+    GrayScottLatticePoint,
+    GrayScottLatticeSize,
+    GrayScottAutomaton,
+    InnerLattice,
+    InnerSize,
+    InnerPoint
+);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GrayScottLattice {
+    #[wasm_bindgen]
+    pub fn scalar_field(&self, chemical: Chemical) -> Vec<f32> {
+        ScalarField::from_lattice(&self.inner, chemical).values().to_vec()
+    }
+
+    #[wasm_bindgen]
+    pub fn seed_square(&mut self, center: &GrayScottLatticePoint, radius: i32) {
+        GrayScottAutomaton::seed_square(&mut self.inner, &(*center).into(), radius as i128);
+    }
+}
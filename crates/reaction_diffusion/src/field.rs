@@ -0,0 +1,105 @@
+use toolkit::{aliases::UniversalLatticeSize, prelude::*};
+
+use crate::state::GrayScottState;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Chemical {
+    U,
+    #[default]
+    V,
+}
+
+impl Chemical {
+    pub fn of(&self, state: &GrayScottState) -> f32 {
+        match self {
+            Self::U => state.u,
+            Self::V => state.v,
+        }
+    }
+}
+
+/// Dense scalar field in lattice point order, ready to be thresholded or
+/// colour-mapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarField {
+    size: UniversalLatticeSize<2>,
+    values: Vec<f32>,
+}
+
+impl ScalarField {
+    pub fn from_lattice<L>(lattice: &L, chemical: Chemical) -> Self
+    where
+        L: BoundaryHandlingLattice<State = GrayScottState, Size = UniversalLatticeSize<2>>,
+        L::Point: Clone,
+    {
+        Self {
+            size: lattice.size(),
+            values: lattice
+                .states()
+                .iter()
+                .map(|state| chemical.of(state))
+                .collect(),
+        }
+    }
+
+    pub fn size(&self) -> UniversalLatticeSize<2> {
+        self.size
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    pub fn min(&self) -> f32 {
+        self.values.iter().copied().fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn max(&self) -> f32 {
+        self.values.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    pub fn threshold(&self, level: f32) -> Vec<bool> {
+        self.values.iter().map(|value| *value >= level).collect()
+    }
+
+    /// Values rescaled to `0.0..=1.0`, the input of most colour maps.
+    pub fn normalized(&self) -> Vec<f32> {
+        let (min, max) = (self.min(), self.max());
+        let range = max - min;
+
+        if range <= f32::EPSILON {
+            return vec![0.0; self.values.len()];
+        }
+
+        self.values.iter().map(|value| (value - min) / range).collect()
+    }
+
+    /// Piecewise-linear colour map over evenly spaced RGB `stops`.
+    pub fn colorize(&self, stops: &[[u8; 3]]) -> Vec<[u8; 3]> {
+        if stops.is_empty() {
+            return vec![[0; 3]; self.values.len()];
+        }
+
+        let segments = (stops.len() - 1) as f32;
+
+        self.normalized()
+            .into_iter()
+            .map(|value| {
+                let position = value * segments;
+                let index = (position.floor() as usize).min(stops.len() - 1);
+                let next = (index + 1).min(stops.len() - 1);
+                let t = position - index as f32;
+
+                std::array::from_fn(|channel| {
+                    let from = stops[index][channel] as f32;
+                    let to = stops[next][channel] as f32;
+                    (from + (to - from) * t).round() as u8
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod state;
+pub mod stencil;
+pub mod rule;
+pub mod field;
+pub mod automaton;
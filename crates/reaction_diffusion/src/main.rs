@@ -0,0 +1,42 @@
+use reaction_diffusion::{
+    automaton::{GrayScottAutomaton, Integrator},
+    field::{Chemical, ScalarField},
+    rule::{GrayScottPreset, GrayScottRule},
+    state::GrayScottState,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+pub fn main() {
+    let ca = GrayScottAutomaton::new(
+        GrayScottRule::from_preset(GrayScottPreset::Coral),
+        Integrator::Explicit,
+    );
+
+    let mut lattice = UniversalLattice::<2, GrayScottState>::from_states(
+        vec![GrayScottState::default(); 48 * 48],
+        UniversalLatticeSize::new([48, 48]),
+    );
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    GrayScottAutomaton::seed_square(&mut lattice, &UniversalLatticePoint::new([24, 24]), 3);
+
+    for _ in 0..2000 {
+        ca.step(&mut lattice);
+    }
+
+    let field = ScalarField::from_lattice(&lattice, Chemical::V);
+    let shades = [' ', '░', '▒', '▓', '█'];
+
+    for row in field.normalized().chunks(48) {
+        let row: String = row
+            .iter()
+            .map(|value| shades[((value * 4.0).round() as usize).min(4)])
+            .collect();
+
+        println!("{row}");
+    }
+}
@@ -0,0 +1,153 @@
+use toolkit::prelude::*;
+
+use crate::{state::GrayScottState, stencil::LaplacianStencil};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum GrayScottPreset {
+    Coral,
+    Mitosis,
+    Worms,
+}
+
+impl GrayScottPreset {
+    /// `(feed, kill)` rates of the preset.
+    pub fn rates(&self) -> (f32, f32) {
+        match self {
+            Self::Coral => (0.0545, 0.062),
+            Self::Mitosis => (0.0367, 0.0649),
+            Self::Worms => (0.078, 0.061),
+        }
+    }
+}
+
+/// Gray–Scott reaction `u + 2v -> 3v` with feed rate `feed` and kill rate
+/// `kill`. [`Rule::apply`] performs one explicit Euler step.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GrayScottRule {
+    feed: f32,
+    kill: f32,
+    diffusion_u: f32,
+    diffusion_v: f32,
+    time_step: f32,
+    stencil: LaplacianStencil,
+}
+
+impl Default for GrayScottRule {
+    fn default() -> Self {
+        Self::from_preset(GrayScottPreset::Coral)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GrayScottRule {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(
+        feed: f32,
+        kill: f32,
+        diffusion_u: f32,
+        diffusion_v: f32,
+        time_step: f32,
+        stencil: LaplacianStencil,
+    ) -> Self {
+        Self {
+            feed,
+            kill,
+            diffusion_u,
+            diffusion_v,
+            time_step,
+            stencil,
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn from_preset(preset: GrayScottPreset) -> Self {
+        let (feed, kill) = preset.rates();
+        Self::new(feed, kill, 1.0, 0.5, 1.0, LaplacianStencil::default())
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn feed(&self) -> f32 {
+        self.feed
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn kill(&self) -> f32 {
+        self.kill
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn diffusion_u(&self) -> f32 {
+        self.diffusion_u
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn diffusion_v(&self) -> f32 {
+        self.diffusion_v
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn time_step(&self) -> f32 {
+        self.time_step
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn stencil(&self) -> LaplacianStencil {
+        self.stencil
+    }
+}
+
+impl GrayScottRule {
+    /// Reaction terms `(du/dt, dv/dt)` without diffusion.
+    pub fn reaction(&self, state: &GrayScottState) -> (f32, f32) {
+        let uvv = state.u * state.v * state.v;
+
+        (
+            -uvv + self.feed * (1.0 - state.u),
+            uvv - (self.feed + self.kill) * state.v,
+        )
+    }
+
+    /// Weighted neighbor sums `(Σ w·u, Σ w·v)` of the stencil.
+    pub fn neighbor_sums(
+        &self,
+        neighbors: &impl Neighborhood<State = GrayScottState>,
+    ) -> (f32, f32) {
+        let (us, vs): (Vec<f32>, Vec<f32>) = neighbors
+            .iter_states()
+            .map(|state| (state.u, state.v))
+            .unzip();
+
+        (
+            self.stencil.neighbor_sum(us.iter()),
+            self.stencil.neighbor_sum(vs.iter()),
+        )
+    }
+}
+
+impl Rule for GrayScottRule {
+    type State = GrayScottState;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        let (sum_u, sum_v) = self.neighbor_sums(neighbors);
+        let laplacian_u = sum_u + LaplacianStencil::CENTER_WEIGHT * current_state.u;
+        let laplacian_v = sum_v + LaplacianStencil::CENTER_WEIGHT * current_state.v;
+
+        let (reaction_u, reaction_v) = self.reaction(current_state);
+
+        GrayScottState {
+            u: current_state.u + self.time_step * (self.diffusion_u * laplacian_u + reaction_u),
+            v: current_state.v + self.time_step * (self.diffusion_v * laplacian_v + reaction_v),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use toolkit::lattice::snapshot::SnapshotState;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Concentrations of the two Gray–Scott chemicals in a cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GrayScottState {
+    pub u: f32,
+    pub v: f32,
+}
+
+impl Default for GrayScottState {
+    fn default() -> Self {
+        Self { u: 1.0, v: 0.0 }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GrayScottState {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(u: f32, v: f32) -> Self {
+        Self { u, v }
+    }
+}
+
+impl SnapshotState for GrayScottState {
+    const WIDTH: usize = 2 * f32::WIDTH;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.u.write_bytes(bytes);
+        self.v.write_bytes(bytes);
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        let (u, v) = bytes.split_at_checked(f32::WIDTH)?;
+        Some(Self::new(f32::read_bytes(u)?, f32::read_bytes(v)?))
+    }
+}
@@ -0,0 +1,108 @@
+use toolkit::{
+    aliases::UniversalLatticePoint,
+    lattice::universal_lattice::UniversalLattice,
+    types::{Lattice, Neighborhood, NeighborhoodBuilder},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Discrete Laplacian kernels, normalised so the center weight is `-1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum LaplacianStencil {
+    FivePoint,
+    #[default]
+    NinePoint,
+}
+
+impl LaplacianStencil {
+    pub const CENTER_WEIGHT: f32 = -1.0;
+
+    pub fn offsets(&self) -> &'static [(i128, i128)] {
+        match self {
+            Self::FivePoint => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Self::NinePoint => &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+                (1, 1),
+            ],
+        }
+    }
+
+    pub fn weights(&self) -> &'static [f32] {
+        match self {
+            Self::FivePoint => &[0.25, 0.25, 0.25, 0.25],
+            Self::NinePoint => &[0.2, 0.2, 0.2, 0.2, 0.05, 0.05, 0.05, 0.05],
+        }
+    }
+
+    /// Weighted sum of the neighbors only, without the center term.
+    pub fn neighbor_sum<'a>(&self, values: impl Iterator<Item = &'a f32>) -> f32 {
+        self.weights()
+            .iter()
+            .zip(values)
+            .map(|(weight, value)| weight * value)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StencilNeighborhood<S> {
+    states: Vec<S>,
+}
+
+impl<S> Neighborhood for StencilNeighborhood<S> {
+    type State = S;
+    type Iter<'a>
+        = std::slice::Iter<'a, S>
+    where
+        S: 'a;
+
+    fn iter_states(&self) -> Self::Iter<'_> {
+        self.states.iter()
+    }
+}
+
+/// Collects neighbors in the order of [`LaplacianStencil::offsets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StencilNeighborhoodBuilder {
+    stencil: LaplacianStencil,
+}
+
+impl StencilNeighborhoodBuilder {
+    pub fn new(stencil: LaplacianStencil) -> Self {
+        Self { stencil }
+    }
+
+    pub fn stencil(&self) -> LaplacianStencil {
+        self.stencil
+    }
+}
+
+impl<S: Clone + Default> NeighborhoodBuilder<UniversalLattice<2, S>> for StencilNeighborhoodBuilder {
+    type Neighborhood = StencilNeighborhood<S>;
+
+    fn build_neighborhood(
+        &self,
+        point: &<UniversalLattice<2, S> as Lattice>::Point,
+        lattice: &UniversalLattice<2, S>,
+    ) -> Self::Neighborhood {
+        let (x, y) = (point[0], point[1]);
+
+        let states = self
+            .stencil
+            .offsets()
+            .iter()
+            .map(|(dx, dy)| lattice.get_state(&UniversalLatticePoint::new([x + dx, y + dy])))
+            .collect();
+
+        StencilNeighborhood { states }
+    }
+}
@@ -0,0 +1,98 @@
+use reaction_diffusion::{
+    automaton::{GrayScottAutomaton, Integrator},
+    rule::{GrayScottPreset, GrayScottRule},
+    state::GrayScottState,
+    stencil::LaplacianStencil,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+
+const SIZE: usize = 16;
+
+fn uniform(state: GrayScottState) -> UniversalLattice<2, GrayScottState> {
+    let mut lattice = UniversalLattice::from_states(
+        vec![state; SIZE * SIZE],
+        UniversalLatticeSize::new([SIZE, SIZE]),
+    );
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    lattice
+}
+
+fn seeded() -> UniversalLattice<2, GrayScottState> {
+    let mut lattice = uniform(GrayScottState::default());
+    GrayScottAutomaton::seed_square(&mut lattice, &UniversalLatticePoint::new([8, 8]), 2);
+    lattice
+}
+
+#[test]
+fn uniform_state_follows_the_reaction() {
+    let rule = GrayScottRule::from_preset(GrayScottPreset::Mitosis);
+    let state = GrayScottState::new(0.6, 0.3);
+    let (reaction_u, reaction_v) = rule.reaction(&state);
+
+    for integrator in [Integrator::Explicit, Integrator::SemiImplicit] {
+        let automaton = GrayScottAutomaton::new(rule.clone(), integrator);
+        let mut lattice = uniform(state);
+        automaton.step(&mut lattice);
+
+        // Without gradients diffusion has nothing to do.
+        for next in lattice.states() {
+            assert!(
+                (next.u - (state.u + reaction_u)).abs() < 1e-6,
+                "{integrator:?}"
+            );
+            assert!(
+                (next.v - (state.v + reaction_v)).abs() < 1e-6,
+                "{integrator:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn trivial_state_is_stationary() {
+    let automaton = GrayScottAutomaton::new(GrayScottRule::default(), Integrator::Explicit);
+    let mut lattice = uniform(GrayScottState::default());
+
+    for _ in 0..10 {
+        automaton.step(&mut lattice);
+    }
+
+    assert!(
+        lattice
+            .states()
+            .iter()
+            .all(|state| *state == GrayScottState::default())
+    );
+}
+
+#[test]
+fn integrators_agree_for_small_time_steps() {
+    let rule = GrayScottRule::new(0.0367, 0.0649, 1.0, 0.5, 0.05, LaplacianStencil::default());
+    let explicit = GrayScottAutomaton::new(rule.clone(), Integrator::Explicit);
+    let mut semi_implicit = GrayScottAutomaton::new(rule, Integrator::SemiImplicit);
+    semi_implicit.set_jacobi_iterations(20);
+
+    let (mut left, mut right) = (seeded(), seeded());
+    for _ in 0..40 {
+        explicit.step(&mut left);
+        semi_implicit.step(&mut right);
+    }
+
+    for (left, right) in left.states().iter().zip(right.states()) {
+        assert!((left.u - right.u).abs() < 1e-2);
+        assert!((left.v - right.v).abs() < 1e-2);
+    }
+
+    // The seed is symmetric about its center, and so is the pattern.
+    let mirrored = |x: i128, y: i128| right.get_state(&UniversalLatticePoint::new([16 - x, y]));
+    for y in 0..16 {
+        for x in 1..16 {
+            let state = right.get_state(&UniversalLatticePoint::new([x, y]));
+            assert!((state.v - mirrored(x, y).v).abs() < 1e-5);
+        }
+    }
+}
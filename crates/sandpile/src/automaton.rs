@@ -0,0 +1,296 @@
+use std::collections::BTreeSet;
+
+#[cfg(feature = "wasm")]
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
+use rand::Rng;
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    automaton::context::StepContext,
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    types::{BoundaryHandlingLattice, CellularAutomaton, Lattice},
+    utils::translate,
+};
+
+#[cfg(feature = "wasm")]
+use toolkit::types::BoundaryHandling;
+
+use crate::{
+    rule::SandpileRule,
+    utils::{Avalanche, AvalancheStatistics},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub type SandpileLattice2 = UniversalLattice<2, u32>;
+
+/// Abelian sandpile on a finite lattice whose outside acts as a sink.
+///
+/// [`CellularAutomaton::step`] performs a single parallel toppling wave, while
+/// [`SandpileAutomaton::stabilize`] topples until no cell reaches the
+/// threshold. Both dissipate grains toppled over the edge whatever the
+/// boundary handling of the lattice, otherwise a wrapped or clamped lattice
+/// could never stabilise.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SandpileAutomaton {
+    rule: SandpileRule,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl SandpileAutomaton {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(rule: SandpileRule) -> Self {
+        Self { rule }
+    }
+}
+
+impl SandpileAutomaton {
+    pub fn is_stable(&self, lattice: &SandpileLattice2) -> bool {
+        lattice
+            .sparse_states()
+            .into_iter()
+            .all(|height| self.rule.is_stable(height))
+    }
+
+    pub fn stabilize(&self, lattice: &mut SandpileLattice2) -> Avalanche {
+        let frontier = self.unstable_points(lattice);
+        self.topple(lattice, frontier)
+    }
+
+    pub fn add_grain(
+        &self,
+        lattice: &mut SandpileLattice2,
+        point: &UniversalLatticePoint<2>,
+    ) -> Avalanche {
+        if !lattice.contains(point) {
+            return Avalanche::default();
+        }
+
+        let height = lattice.get_state(point) + 1;
+        lattice.set_state(point, &height);
+
+        if self.rule.is_stable(height) {
+            return Avalanche::default();
+        }
+
+        self.topple(lattice, BTreeSet::from([*point]))
+    }
+
+    /// Drops `grains` grains one by one on uniformly random cells, stabilising
+    /// after each drop.
+    pub fn drive(
+        &self,
+        lattice: &mut SandpileLattice2,
+        context: &mut StepContext,
+        grains: usize,
+    ) -> AvalancheStatistics {
+        let size = lattice.size();
+        let mut rng = context.rng(0);
+        let mut statistics = AvalancheStatistics::default();
+
+        if size.values().contains(&0) {
+            return statistics;
+        }
+
+        for _ in 0..grains {
+            let point = UniversalLatticePoint::new(
+                size.values().map(|size| rng.gen_range(0..size) as i128),
+            );
+
+            statistics.push(self.add_grain(lattice, &point));
+        }
+
+        context.advance();
+        statistics
+    }
+
+    /// Sums two configurations cell by cell, saturating at `u32::MAX`, and
+    /// stabilises the result.
+    pub fn add(&self, left: &SandpileLattice2, right: &SandpileLattice2) -> SandpileLattice2 {
+        let mut sum = left.clone();
+
+        for (point, height) in right.clone() {
+            sum.set_state(&point, &sum.get_state(&point).saturating_add(height));
+        }
+
+        self.stabilize(&mut sum);
+        sum
+    }
+
+    /// Identity element of the sandpile group on a grid of `size`, computed as
+    /// `(2m - (2m)°)°` where `m` is the maximal stable configuration.
+    ///
+    /// Holds for any threshold: the grains a toppling does not hand to its
+    /// four neighbors go to the sink like those toppled over the edge.
+    pub fn identity(&self, size: UniversalLatticeSize<2>) -> SandpileLattice2 {
+        let cells = size.values().iter().product();
+        let double_max = 2 * (self.rule.threshold() - 1);
+
+        let mut stabilized = SandpileLattice2::from_states(vec![double_max; cells], size);
+        self.stabilize(&mut stabilized);
+
+        let mut identity = SandpileLattice2::from_states(
+            stabilized
+                .states()
+                .into_iter()
+                .map(|height| double_max - height)
+                .collect(),
+            size,
+        );
+        self.stabilize(&mut identity);
+
+        identity
+    }
+
+    fn unstable_points(&self, lattice: &SandpileLattice2) -> BTreeSet<UniversalLatticePoint<2>> {
+        lattice
+            .sparse_points()
+            .into_iter()
+            .filter(|point| !self.rule.is_stable(lattice.get_state(point)))
+            .collect()
+    }
+
+    fn topple(
+        &self,
+        lattice: &mut SandpileLattice2,
+        mut frontier: BTreeSet<UniversalLatticePoint<2>>,
+    ) -> Avalanche {
+        let mut avalanche = Avalanche::default();
+        let mut toppled = BTreeSet::new();
+
+        while !frontier.is_empty() {
+            avalanche.duration += 1;
+            frontier = self.wave(lattice, frontier, &mut avalanche, &mut toppled);
+        }
+
+        avalanche.area = toppled.len();
+        avalanche
+    }
+
+    /// Topples every unstable cell of `frontier` once and returns the cells
+    /// left unstable afterwards.
+    fn wave(
+        &self,
+        lattice: &mut SandpileLattice2,
+        frontier: BTreeSet<UniversalLatticePoint<2>>,
+        avalanche: &mut Avalanche,
+        toppled: &mut BTreeSet<UniversalLatticePoint<2>>,
+    ) -> BTreeSet<UniversalLatticePoint<2>> {
+        let threshold = self.rule.threshold();
+        let offsets = VonNeumannNeighborhoodBuilder::offsets::<2>();
+        let mut next = BTreeSet::new();
+
+        for point in frontier {
+            let height = lattice.get_state(&point);
+
+            if self.rule.is_stable(height) {
+                continue;
+            }
+
+            lattice.set_state(&point, &(height - threshold));
+            avalanche.size += 1;
+            toppled.insert(point);
+
+            if !self.rule.is_stable(height - threshold) {
+                next.insert(point);
+            }
+
+            for offset in &offsets {
+                let neighbor = translate(&point, offset);
+
+                if !lattice.contains(&neighbor) {
+                    avalanche.dissipated += 1;
+                    continue;
+                }
+
+                let height = lattice.get_state(&neighbor) + 1;
+                lattice.set_state(&neighbor, &height);
+
+                if !self.rule.is_stable(height) {
+                    next.insert(neighbor);
+                }
+            }
+        }
+
+        next
+    }
+}
+
+impl CellularAutomaton for SandpileAutomaton {
+    type Lattice = SandpileLattice2;
+    type Rule = SandpileRule;
+    type NeighborhoodBuilder = VonNeumannNeighborhoodBuilder;
+
+    fn rule(&self) -> &Self::Rule {
+        &self.rule
+    }
+
+    fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+        VonNeumannNeighborhoodBuilder
+    }
+
+    /// One toppling wave that dissipates at the edge like
+    /// [`SandpileAutomaton::stabilize`], rather than applying the rule through
+    /// the lattice's boundary handling.
+    fn step_with_context(&self, lattice: &mut Self::Lattice, context: &mut StepContext) {
+        let frontier = self.unstable_points(lattice);
+        self.wave(
+            lattice,
+            frontier,
+            &mut Avalanche::default(),
+            &mut BTreeSet::new(),
+        );
+
+        context.advance();
+    }
+}
+
+#[cfg(feature = "wasm")]
+type InnerSize = UniversalLatticeSize<2>;
+#[cfg(feature = "wasm")]
+define_size_wrapper!(SandpileLatticeSize, InnerSize);
+
+#[cfg(feature = "wasm")]
+type InnerPoint = UniversalLatticePoint<2>;
+#[cfg(feature = "wasm")]
+define_point_wrapper!(SandpileLatticePoint, InnerPoint);
+
+#[cfg(feature = "wasm")]
+type InnerLattice = SandpileLattice2;
+#[cfg(feature = "wasm")]
+define_lattice_wrapper!(
+    SandpileLattice,
+    u32,
+    SandpileLatticePoint,
+    SandpileLatticeSize,
+    SandpileAutomaton,
+    InnerLattice,
+    InnerSize,
+    InnerPoint
+);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SandpileAutomaton {
+    #[wasm_bindgen(js_name = "stabilize")]
+    pub fn stabilize_wrapper(&self, lattice: &mut SandpileLattice) -> Avalanche {
+        self.stabilize(&mut lattice.inner)
+    }
+
+    #[wasm_bindgen(js_name = "addGrain")]
+    pub fn add_grain_wrapper(
+        &self,
+        lattice: &mut SandpileLattice,
+        point: &SandpileLatticePoint,
+    ) -> Avalanche {
+        self.add_grain(&mut lattice.inner, &(*point).into())
+    }
+
+    #[wasm_bindgen(js_name = "identity")]
+    pub fn identity_wrapper(&self, size: SandpileLatticeSize) -> SandpileLattice {
+        self.identity(size.into()).into()
+    }
+}
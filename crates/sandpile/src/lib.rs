@@ -0,0 +1,3 @@
+pub mod rule;
+pub mod utils;
+pub mod automaton;
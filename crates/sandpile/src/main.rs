@@ -0,0 +1,33 @@
+use sandpile::{automaton::SandpileAutomaton, rule::SandpileRule};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    prelude::*,
+};
+
+pub fn main() {
+    let ca = SandpileAutomaton::new(SandpileRule::default());
+    let size = UniversalLatticeSize::new([24, 24]);
+
+    let identity = ca.identity(size);
+
+    for y in 0..size[1] {
+        let row: String = (0..size[0])
+            .map(|x| {
+                let height = identity.get_state(&UniversalLatticePoint::new([x as i128, y as i128]));
+                [' ', '░', '▒', '█'][height.min(3) as usize]
+            })
+            .collect();
+
+        println!("{row}");
+    }
+
+    let mut lattice = identity.clone();
+    let statistics = ca.drive(&mut lattice, &mut StepContext::new(42), 5000);
+
+    println!(
+        "avalanches: {}, mean size: {:.2}, mean duration: {:.2}",
+        statistics.avalanches().len(),
+        statistics.mean_size(),
+        statistics.mean_duration()
+    );
+}
@@ -0,0 +1,79 @@
+use toolkit::prelude::*;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Lowest threshold at which a toppling removes at least the four grains it
+/// hands to its von Neumann neighbors. Below it topplings create grains and
+/// the pile never stabilises.
+pub const MIN_THRESHOLD: u32 = 4;
+
+/// One parallel toppling wave of the Abelian sandpile: every cell holding at
+/// least `threshold` grains gives one grain to each neighbor. Applied on its
+/// own it follows the boundary handling of the lattice, while
+/// [`crate::automaton::SandpileAutomaton`] dissipates grains at the edge.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SandpileRule {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_threshold"))]
+    threshold: u32,
+}
+
+impl Default for SandpileRule {
+    fn default() -> Self {
+        Self {
+            threshold: MIN_THRESHOLD,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl SandpileRule {
+    /// Thresholds below [`MIN_THRESHOLD`] are raised to it.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(MIN_THRESHOLD),
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn is_stable(&self, height: u32) -> bool {
+        height < self.threshold
+    }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_threshold<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    <u32 as serde::Deserialize>::deserialize(deserializer)
+        .map(|threshold| threshold.max(MIN_THRESHOLD))
+}
+
+impl Rule for SandpileRule {
+    type State = u32;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        let received = neighbors
+            .iter_states()
+            .filter(|height| !self.is_stable(**height))
+            .count() as u32;
+
+        if self.is_stable(*current_state) {
+            current_state + received
+        } else {
+            current_state - self.threshold + received
+        }
+    }
+}
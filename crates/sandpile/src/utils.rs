@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Avalanche {
+    /// Number of topplings.
+    pub size: usize,
+    /// Number of parallel toppling waves until the lattice became stable.
+    pub duration: usize,
+    /// Number of distinct cells that toppled at least once.
+    pub area: usize,
+    /// Grains that fell into the sink outside the lattice.
+    pub dissipated: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AvalancheStatistics {
+    avalanches: Vec<Avalanche>,
+}
+
+impl AvalancheStatistics {
+    pub fn new(avalanches: Vec<Avalanche>) -> Self {
+        Self { avalanches }
+    }
+
+    pub fn push(&mut self, avalanche: Avalanche) {
+        self.avalanches.push(avalanche);
+    }
+
+    pub fn avalanches(&self) -> &[Avalanche] {
+        &self.avalanches
+    }
+
+    pub fn size_distribution(&self) -> BTreeMap<usize, usize> {
+        histogram(self.avalanches.iter().map(|avalanche| avalanche.size))
+    }
+
+    pub fn duration_distribution(&self) -> BTreeMap<usize, usize> {
+        histogram(self.avalanches.iter().map(|avalanche| avalanche.duration))
+    }
+
+    pub fn mean_size(&self) -> f64 {
+        mean(self.avalanches.iter().map(|avalanche| avalanche.size))
+    }
+
+    pub fn mean_duration(&self) -> f64 {
+        mean(self.avalanches.iter().map(|avalanche| avalanche.duration))
+    }
+}
+
+fn histogram(values: impl Iterator<Item = usize>) -> BTreeMap<usize, usize> {
+    values.fold(BTreeMap::new(), |mut histogram, value| {
+        *histogram.entry(value).or_default() += 1;
+        histogram
+    })
+}
+
+fn mean(values: impl ExactSizeIterator<Item = usize>) -> f64 {
+    let count = values.len();
+
+    if count == 0 {
+        return 0.0;
+    }
+
+    values.sum::<usize>() as f64 / count as f64
+}
@@ -0,0 +1,139 @@
+use sandpile::{
+    automaton::{SandpileAutomaton, SandpileLattice2},
+    rule::SandpileRule,
+    utils::Avalanche,
+};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    types::{BoundaryHandling, BoundaryHandlingLattice, CellularAutomaton, Lattice},
+};
+
+fn grid(rows: &[[u32; 3]]) -> SandpileLattice2 {
+    SandpileLattice2::from_states(
+        rows.iter().flatten().copied().collect(),
+        UniversalLatticeSize::new([3, rows.len()]),
+    )
+}
+
+#[test]
+fn stabilize_topples_until_stable() {
+    let automaton = SandpileAutomaton::default();
+    let mut lattice = grid(&[[0, 0, 0], [0, 8, 0], [0, 0, 0]]);
+
+    let avalanche = automaton.stabilize(&mut lattice);
+
+    assert_eq!(
+        lattice.states(),
+        grid(&[[0, 2, 0], [2, 0, 2], [0, 2, 0]]).states()
+    );
+    assert!(automaton.is_stable(&lattice));
+    assert_eq!(
+        avalanche,
+        Avalanche {
+            size: 2,
+            duration: 2,
+            area: 1,
+            dissipated: 0,
+        }
+    );
+}
+
+#[test]
+fn identity_of_three_by_three_grid() {
+    let automaton = SandpileAutomaton::default();
+    let identity = automaton.identity(UniversalLatticeSize::new([3, 3]));
+
+    assert_eq!(
+        identity.states(),
+        grid(&[[2, 1, 2], [1, 0, 1], [2, 1, 2]]).states()
+    );
+
+    let maximal = grid(&[[3, 3, 3], [3, 3, 3], [3, 3, 3]]);
+    assert_eq!(
+        automaton.add(&maximal, &identity).states(),
+        maximal.states()
+    );
+}
+
+#[test]
+fn wrapped_lattice_still_dissipates() {
+    let automaton = SandpileAutomaton::default();
+    let mut lattice = grid(&[[3, 3, 3], [3, 3, 3], [3, 3, 3]]);
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    let avalanche = automaton.add_grain(&mut lattice, &UniversalLatticePoint::new([1, 1]));
+
+    assert!(automaton.is_stable(&lattice));
+    assert!(avalanche.dissipated > 0);
+}
+
+#[test]
+fn threshold_is_at_least_the_neighbor_count() {
+    assert_eq!(SandpileRule::new(1).threshold(), 4);
+    assert_eq!(SandpileRule::new(6).threshold(), 6);
+}
+
+#[test]
+fn step_dissipates_like_stabilize() {
+    let automaton = SandpileAutomaton::default();
+
+    for boundary_handling in [
+        BoundaryHandling::Default,
+        BoundaryHandling::Clamp,
+        BoundaryHandling::Wrap,
+    ] {
+        let mut stepped = grid(&[[9, 0, 3], [0, 2, 0], [3, 0, 7]]);
+        stepped.set_boundary_handling(boundary_handling);
+        let mut stabilized = stepped.clone();
+
+        automaton.step(&mut stepped);
+        assert_eq!(
+            stepped.states(),
+            grid(&[[5, 1, 3], [1, 2, 1], [3, 1, 3]]).states(),
+            "{boundary_handling:?}"
+        );
+
+        while !automaton.is_stable(&stepped) {
+            automaton.step(&mut stepped);
+        }
+        automaton.stabilize(&mut stabilized);
+
+        assert_eq!(
+            stepped.states(),
+            stabilized.states(),
+            "{boundary_handling:?}"
+        );
+    }
+}
+
+#[test]
+fn identity_holds_for_higher_thresholds() {
+    let automaton = SandpileAutomaton::new(SandpileRule::new(6));
+    let identity = automaton.identity(UniversalLatticeSize::new([3, 3]));
+
+    assert!(automaton.is_stable(&identity));
+    assert_eq!(
+        automaton.add(&identity, &identity).states(),
+        identity.states()
+    );
+
+    let maximal = grid(&[[5, 5, 5], [5, 5, 5], [5, 5, 5]]);
+    let recurrent = automaton.add(&maximal, &grid(&[[1, 0, 2], [0, 3, 0], [4, 0, 1]]));
+    assert_eq!(
+        automaton.add(&recurrent, &identity).states(),
+        recurrent.states()
+    );
+}
+
+#[test]
+fn add_saturates_heights() {
+    let automaton = SandpileAutomaton::new(SandpileRule::new(u32::MAX));
+    let left = grid(&[[u32::MAX - 1, 0, 0]]);
+    let right = grid(&[[5, 0, 0]]);
+
+    let sum = automaton.add(&left, &right);
+
+    // The saturated cell topples once, handing one grain to its only
+    // neighbor inside the lattice.
+    assert_eq!(sum.states(), [0, 1, 0]);
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeSet;
+
+use crate::aliases::UniversalLatticePoint;
+
+/// Box-counting dimension: the slope of `log N(s)` against `log (1 / s)`
+/// where `N(s)` counts boxes of side `s = 1, 2, 4, ...` holding a point.
+/// Boxes stop at a quarter of the extent, where the count saturates.
+pub fn box_counting_dimension<const D: usize>(points: &[UniversalLatticePoint<D>]) -> Option<f64> {
+    let (min, max) = bounds(points)?;
+    let extent = (0..D)
+        .map(|axis| max[axis] - min[axis] + 1)
+        .max()
+        .unwrap_or(1);
+
+    let samples: Vec<(f64, f64)> = std::iter::successors(Some(1i128), |side| Some(side * 2))
+        .take_while(|side| *side * 4 <= extent)
+        .map(|side| {
+            let boxes: BTreeSet<[i128; D]> = points
+                .iter()
+                .map(|point| {
+                    let mut index = [0; D];
+                    (0..D)
+                        .for_each(|axis| index[axis] = (point[axis] - min[axis]).div_euclid(side));
+                    index
+                })
+                .collect();
+
+            (-(side as f64).ln(), (boxes.len() as f64).ln())
+        })
+        .collect();
+
+    slope(&samples)
+}
+
+/// Mass-radius dimension: the slope of `log M(r)` against `log r` where
+/// `M(r)` counts points within distance `r` of `center`. Radii run in
+/// powers of two up to half the cluster radius, away from the edge effects.
+pub fn mass_radius_dimension<const D: usize>(
+    points: &[UniversalLatticePoint<D>],
+    center: &UniversalLatticePoint<D>,
+) -> Option<f64> {
+    let mut distances: Vec<f64> = points
+        .iter()
+        .map(|point| {
+            (0..D)
+                .map(|axis| ((point[axis] - center[axis]) as f64).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .collect();
+    distances.sort_by(f64::total_cmp);
+
+    let radius = *distances.last()?;
+
+    let samples: Vec<(f64, f64)> = std::iter::successors(Some(2.0f64), |radius| Some(radius * 2.0))
+        .take_while(|sample| *sample <= radius / 2.0)
+        .map(|sample| {
+            let mass = distances.partition_point(|distance| *distance <= sample);
+            (sample.ln(), (mass as f64).ln())
+        })
+        .collect();
+
+    slope(&samples)
+}
+
+fn bounds<const D: usize>(
+    points: &[UniversalLatticePoint<D>],
+) -> Option<(UniversalLatticePoint<D>, UniversalLatticePoint<D>)> {
+    let first = *points.first()?;
+
+    Some(
+        points
+            .iter()
+            .fold((first, first), |(mut min, mut max), point| {
+                for axis in 0..D {
+                    min[axis] = min[axis].min(point[axis]);
+                    max[axis] = max[axis].max(point[axis]);
+                }
+
+                (min, max)
+            }),
+    )
+}
+
+/// Least-squares slope; needs at least two distinct abscissas.
+fn slope(samples: &[(f64, f64)]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let count = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / count;
+
+    let covariance: f64 = samples
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = samples.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
@@ -0,0 +1,3 @@
+pub mod fractal;
+pub mod population;
+pub mod regions;
@@ -0,0 +1,20 @@
+use crate::types::Lattice;
+
+/// Cell counts per state, filled in by [`population`].
+pub trait Population<S>: Default {
+    fn add(&mut self, state: &S);
+}
+
+pub fn population<P, L>(lattice: &L) -> P
+where
+    L: Lattice,
+    P: Population<L::State>,
+{
+    lattice
+        .states()
+        .iter()
+        .fold(P::default(), |mut population, state| {
+            population.add(state);
+            population
+        })
+}
@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    neighborhood::{moore::MooreNeighborhoodBuilder, von_neumann::VonNeumannNeighborhoodBuilder},
+    types::{BoundaryHandling, BoundaryHandlingLattice},
+    utils::translate,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Connectivity {
+    #[default]
+    VonNeumann,
+    Moore,
+}
+
+impl Connectivity {
+    pub fn offsets<const D: usize>(&self) -> Vec<UniversalLatticePoint<D>> {
+        match self {
+            Self::VonNeumann => VonNeumannNeighborhoodBuilder::offsets::<D>(),
+            Self::Moore => MooreNeighborhoodBuilder::offsets::<D>(),
+        }
+    }
+}
+
+/// Connected set of cells sharing one state.
+///
+/// With [`BoundaryHandling::Wrap`] regions may cross the lattice edge: the
+/// bounding box then starts at `origin` and extends `extent` cells, possibly
+/// past the edge, and the centroid is taken over the unwrapped cells. Other
+/// boundary handlings treat the outside as empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region<const D: usize> {
+    pub label: usize,
+    pub points: Vec<UniversalLatticePoint<D>>,
+    pub origin: UniversalLatticePoint<D>,
+    pub extent: UniversalLatticeSize<D>,
+    pub centroid: [f64; D],
+    /// Number of cell faces shared with cells outside the region.
+    pub perimeter: usize,
+}
+
+impl<const D: usize> Region<D> {
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionLabels<const D: usize> {
+    labels: BTreeMap<UniversalLatticePoint<D>, usize>,
+    regions: Vec<Region<D>>,
+}
+
+impl<const D: usize> RegionLabels<D> {
+    pub fn label(&self, point: &UniversalLatticePoint<D>) -> Option<usize> {
+        self.labels.get(point).copied()
+    }
+
+    pub fn regions(&self) -> &[Region<D>] {
+        &self.regions
+    }
+
+    pub fn into_regions(self) -> Vec<Region<D>> {
+        self.regions
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+/// Cells reachable from `start` through neighbors in the same state.
+pub fn flood_fill<const D: usize, L>(
+    lattice: &L,
+    start: &UniversalLatticePoint<D>,
+    connectivity: Connectivity,
+) -> Vec<UniversalLatticePoint<D>>
+where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>, Size = UniversalLatticeSize<D>>,
+    L::State: PartialEq,
+{
+    let Some(start) = cell(lattice, start) else {
+        return Vec::new();
+    };
+
+    let state = lattice.get_state(&start);
+    let offsets = connectivity.offsets::<D>();
+
+    let mut visited = BTreeSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut points = Vec::new();
+
+    while let Some(point) = queue.pop_front() {
+        points.push(point);
+
+        for offset in &offsets {
+            let Some(neighbor) = cell(lattice, &translate(&point, offset)) else {
+                continue;
+            };
+
+            if !visited.contains(&neighbor) && lattice.get_state(&neighbor) == state {
+                visited.insert(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    points
+}
+
+/// Labels every connected region of cells whose state passes `include`.
+/// Labels follow lattice point order of each region's first cell.
+pub fn label_regions<const D: usize, L>(
+    lattice: &L,
+    connectivity: Connectivity,
+    include: impl Fn(&L::State) -> bool,
+) -> RegionLabels<D>
+where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>, Size = UniversalLatticeSize<D>>,
+    L::State: PartialEq,
+{
+    let mut labels = BTreeMap::new();
+    let mut regions = Vec::new();
+
+    for point in lattice.points() {
+        if labels.contains_key(&point) || !include(&lattice.get_state(&point)) {
+            continue;
+        }
+
+        let label = regions.len();
+        let points = flood_fill(lattice, &point, connectivity);
+        points.iter().for_each(|point| {
+            labels.insert(*point, label);
+        });
+
+        regions.push((label, points));
+    }
+
+    let regions = regions
+        .into_iter()
+        .map(|(label, points)| describe(lattice, label, points, &labels))
+        .collect();
+
+    RegionLabels { labels, regions }
+}
+
+fn describe<const D: usize, L>(
+    lattice: &L,
+    label: usize,
+    points: Vec<UniversalLatticePoint<D>>,
+    labels: &BTreeMap<UniversalLatticePoint<D>, usize>,
+) -> Region<D>
+where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>, Size = UniversalLatticeSize<D>>,
+{
+    let size = lattice.size();
+    let wrap = lattice.boundary_handling() == BoundaryHandling::Wrap;
+
+    let mut origin = [0; D];
+    let mut extent = [0; D];
+    let mut centroid = [0.0; D];
+
+    for axis in 0..D {
+        let coords: Vec<i128> = points.iter().map(|point| point[axis]).collect();
+        let (start, length) = if wrap {
+            wrapped_interval(&coords, size[axis])
+        } else {
+            let min = coords.iter().copied().min().unwrap_or_default();
+            let max = coords.iter().copied().max().unwrap_or_default();
+            (min, (max - min + 1) as usize)
+        };
+
+        origin[axis] = start;
+        extent[axis] = length;
+        // Unwrapping relative to the interval start keeps the mean exact for
+        // regions crossing the edge.
+        let ring = size[axis].max(1) as i128;
+        let unwrapped = coords.iter().map(|coord| {
+            if wrap {
+                start + (coord - start).rem_euclid(ring)
+            } else {
+                *coord
+            }
+        });
+        let mean = unwrapped.sum::<i128>() as f64 / coords.len().max(1) as f64;
+
+        centroid[axis] = if wrap { mean % ring as f64 } else { mean };
+    }
+
+    let faces = VonNeumannNeighborhoodBuilder::offsets::<D>();
+    let perimeter = points
+        .iter()
+        .map(|point| {
+            faces
+                .iter()
+                .filter(|offset| {
+                    cell(lattice, &translate(point, offset))
+                        .and_then(|neighbor| labels.get(&neighbor))
+                        != Some(&label)
+                })
+                .count()
+        })
+        .sum();
+
+    Region {
+        label,
+        points,
+        origin: UniversalLatticePoint::new(origin),
+        extent: UniversalLatticeSize::new(extent),
+        centroid,
+        perimeter,
+    }
+}
+
+/// Maps `point` onto a lattice cell, or `None` when it lies outside and the
+/// lattice does not wrap.
+fn cell<const D: usize, L>(
+    lattice: &L,
+    point: &UniversalLatticePoint<D>,
+) -> Option<UniversalLatticePoint<D>>
+where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>, Size = UniversalLatticeSize<D>>,
+{
+    let point = match lattice.boundary_handling() {
+        BoundaryHandling::Wrap => lattice.transform_point(point),
+        _ => *point,
+    };
+
+    let size = lattice.size();
+    (0..D)
+        .all(|axis| point[axis] >= 0 && point[axis] < size[axis] as i128)
+        .then_some(point)
+}
+
+/// Shortest wrapped interval covering `coords` on a ring of `size` cells,
+/// found as the complement of the largest empty gap.
+fn wrapped_interval(coords: &[i128], size: usize) -> (i128, usize) {
+    let mut occupied: Vec<i128> = coords.to_vec();
+    occupied.sort_unstable();
+    occupied.dedup();
+
+    let (Some(first), Some(last)) = (occupied.first().copied(), occupied.last().copied()) else {
+        return (0, 0);
+    };
+
+    let mut start = first;
+    let mut largest_gap = first + size as i128 - last - 1;
+
+    for pair in occupied.windows(2) {
+        let gap = pair[1] - pair[0] - 1;
+
+        if gap > largest_gap {
+            largest_gap = gap;
+            start = pair[1];
+        }
+    }
+
+    (start, (size as i128 - largest_gap) as usize)
+}
+
+/// Dimension-erased [`Region`] for JavaScript callers.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(getter_with_clone))]
+pub struct RegionSummary {
+    pub label: usize,
+    pub area: usize,
+    pub origin: Vec<isize>,
+    pub extent: Vec<usize>,
+    pub centroid: Vec<f64>,
+    pub perimeter: usize,
+}
+
+impl<const D: usize> From<&Region<D>> for RegionSummary {
+    fn from(region: &Region<D>) -> Self {
+        Self {
+            label: region.label,
+            area: region.area(),
+            origin: region
+                .origin
+                .values()
+                .iter()
+                .map(|coord| *coord as isize)
+                .collect(),
+            extent: region.extent.values().to_vec(),
+            centroid: region.centroid.to_vec(),
+            perimeter: region.perimeter,
+        }
+    }
+}
+
+/// Per-cell labels in lattice point order (`-1` outside every region) plus
+/// the region summaries.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(getter_with_clone))]
+pub struct RegionLabelling {
+    pub labels: Vec<i32>,
+    pub regions: Vec<RegionSummary>,
+}
+
+impl<const D: usize> RegionLabels<D> {
+    pub fn summarize(&self, points: &[UniversalLatticePoint<D>]) -> RegionLabelling {
+        RegionLabelling {
+            labels: points
+                .iter()
+                .map(|point| self.label(point).map_or(-1, |label| label as i32))
+                .collect(),
+            regions: self.regions.iter().map(RegionSummary::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub type StepRng = ChaCha8Rng;
+
+/// Seed and generation counter threaded through a stochastic step.
+///
+/// Every random stream is derived from `(seed, generation, stream)` only, so a
+/// run is replayed bit for bit from the same seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct StepContext {
+    seed: u64,
+    generation: u64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl StepContext {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            generation: 0,
+        }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn advance(&mut self) {
+        self.generation += 1;
+    }
+}
+
+impl StepContext {
+    pub fn with_generation(seed: u64, generation: u64) -> Self {
+        Self { seed, generation }
+    }
+
+    pub fn rng(&self, stream: u64) -> StepRng {
+        let key = split_mix(split_mix(split_mix(self.seed) ^ self.generation) ^ stream);
+        StepRng::seed_from_u64(key)
+    }
+
+    /// Independent context for the `index`-th phase of the same generation.
+    pub fn substream(&self, index: u64) -> Self {
+        Self {
+            seed: split_mix(self.seed ^ split_mix(index)),
+            generation: self.generation,
+        }
+    }
+
+    pub fn cell(&self, index: usize) -> CellContext {
+        CellContext {
+            step: *self,
+            index: index as u64,
+        }
+    }
+}
+
+/// Per-cell view of a [`StepContext`] handed to [`crate::types::Rule::apply_in_context`].
+///
+/// The random stream depends on the cell index rather than on the order the
+/// cells are visited in, so stochastic rules replay identically under any
+/// update scheme or parallel split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellContext {
+    step: StepContext,
+    index: u64,
+}
+
+impl CellContext {
+    pub fn step(&self) -> &StepContext {
+        &self.step
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn rng(&self) -> StepRng {
+        self.step.rng(self.index)
+    }
+}
+
+pub(crate) fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
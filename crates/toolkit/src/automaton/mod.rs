@@ -0,0 +1,5 @@
+pub mod context;
+pub mod pipeline;
+pub mod second_order;
+mod stochastic;
+pub mod update_scheme;
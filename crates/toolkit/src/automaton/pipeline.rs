@@ -0,0 +1,134 @@
+use crate::{
+    automaton::context::StepContext,
+    types::{CellularAutomaton, Lattice, NeighborhoodBuilder, Rule},
+};
+
+/// One stage of a generation, e.g. the collision or the streaming half of a
+/// lattice-gas step.
+pub trait StepPhase<L: Lattice> {
+    fn apply(&mut self, lattice: &mut L, context: &StepContext);
+}
+
+/// Ordered list of phases that together make up one generation.
+///
+/// Every phase sees its own derived [`StepContext`], so random streams of
+/// different phases are independent while staying reproducible.
+pub struct Pipeline<L: Lattice> {
+    phases: Vec<Box<dyn StepPhase<L>>>,
+}
+
+impl<L: Lattice> Default for Pipeline<L> {
+    fn default() -> Self {
+        Self { phases: Vec::new() }
+    }
+}
+
+impl<L: Lattice> Pipeline<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_phase(mut self, phase: impl StepPhase<L> + 'static) -> Self {
+        self.push_phase(phase);
+        self
+    }
+
+    pub fn push_phase(&mut self, phase: impl StepPhase<L> + 'static) {
+        self.phases.push(Box::new(phase));
+    }
+
+    pub fn len(&self) -> usize {
+        self.phases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty()
+    }
+
+    pub fn step(&mut self, lattice: &mut L, context: &mut StepContext) {
+        for (index, phase) in self.phases.iter_mut().enumerate() {
+            phase.apply(lattice, &context.substream(index as u64));
+        }
+
+        context.advance();
+    }
+}
+
+/// Applies a rule synchronously over the whole lattice.
+#[derive(Debug, Clone)]
+pub struct RulePhase<R, B> {
+    rule: R,
+    builder: B,
+}
+
+impl<R, B> RulePhase<R, B> {
+    pub fn new(rule: R, builder: B) -> Self {
+        Self { rule, builder }
+    }
+}
+
+impl<L, R, B> StepPhase<L> for RulePhase<R, B>
+where
+    L: Lattice,
+    R: Rule<State = L::State>,
+    B: NeighborhoodBuilder<L>,
+{
+    fn apply(&mut self, lattice: &mut L, context: &StepContext) {
+        let points = lattice.points();
+        let activations = vec![true; points.len()];
+
+        synchronous_update(
+            &self.rule,
+            &self.builder,
+            lattice,
+            points,
+            &activations,
+            context,
+        );
+    }
+}
+
+/// Runs a whole [`CellularAutomaton`] generation as a single phase.
+#[derive(Debug, Clone)]
+pub struct AutomatonPhase<A>(pub A);
+
+impl<A: CellularAutomaton> StepPhase<A::Lattice> for AutomatonPhase<A> {
+    fn apply(&mut self, lattice: &mut A::Lattice, context: &StepContext) {
+        self.0.step_with_context(lattice, &mut context.clone());
+    }
+}
+
+pub(crate) fn synchronous_update<L, R, B>(
+    rule: &R,
+    builder: &B,
+    lattice: &mut L,
+    points: Vec<L::Point>,
+    activations: &[bool],
+    context: &StepContext,
+) where
+    L: Lattice,
+    R: Rule<State = L::State>,
+    B: NeighborhoodBuilder<L>,
+{
+    let mut new_states = Vec::with_capacity(points.len());
+
+    // TODO: iterate over all points based on size
+    for (index, (point, active)) in points.iter().zip(activations).enumerate() {
+        if !active {
+            new_states.push(None);
+            continue;
+        }
+
+        let neighborhood = builder.build_neighborhood(point, lattice);
+        let current_state = lattice.get_state(point);
+
+        let new_state = rule.apply_in_context(&current_state, &neighborhood, &context.cell(index));
+        new_states.push(Some(new_state));
+    }
+
+    for (point, new_state) in points.into_iter().zip(new_states) {
+        if let Some(new_state) = new_state {
+            lattice.set_state(&point, &new_state);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::types::{CellularAutomaton, Lattice, NeighborhoodBuilder, Rule};
+
+/// Fredkin-style second-order wrapper: `next = f(neighborhood) XOR previous`.
+///
+/// Keeps the previous generation next to the automaton, which makes every
+/// binary rule reversible and lets [`SecondOrderAutomaton::step_back`] undo
+/// [`SecondOrderAutomaton::step`] exactly.
+#[derive(Debug, Clone)]
+pub struct SecondOrderAutomaton<A: CellularAutomaton> {
+    automaton: A,
+    previous: A::Lattice,
+}
+
+impl<A> SecondOrderAutomaton<A>
+where
+    A: CellularAutomaton,
+    A::Lattice: Clone,
+    <A::Lattice as Lattice>::State: Into<bool> + From<bool>,
+{
+    pub fn new(automaton: A, previous: A::Lattice) -> Self {
+        Self {
+            automaton,
+            previous,
+        }
+    }
+
+    pub fn automaton(&self) -> &A {
+        &self.automaton
+    }
+
+    pub fn previous(&self) -> &A::Lattice {
+        &self.previous
+    }
+
+    pub fn set_previous(&mut self, previous: A::Lattice) {
+        self.previous = previous;
+    }
+
+    pub fn step(&mut self, lattice: &mut A::Lattice) {
+        let next = self.evolve(lattice, &self.previous);
+        self.previous = std::mem::replace(lattice, next);
+    }
+
+    pub fn step_back(&mut self, lattice: &mut A::Lattice) {
+        let before = self.evolve(&self.previous, lattice);
+        *lattice = std::mem::replace(&mut self.previous, before);
+    }
+
+    fn evolve(&self, current: &A::Lattice, previous: &A::Lattice) -> A::Lattice {
+        let rule = self.automaton.rule();
+        let builder = self.automaton.neighborhood_builder();
+        let mut next = current.clone();
+
+        for point in current.points() {
+            let neighborhood = builder.build_neighborhood(&point, current);
+            let current_state = current.get_state(&point);
+
+            let applied: bool = rule.apply(&current_state, &neighborhood).into();
+            let previous: bool = previous.get_state(&point).into();
+
+            next.set_state(&point, &(applied ^ previous).into());
+        }
+
+        next
+    }
+}
@@ -0,0 +1,100 @@
+/// Defines an automaton that steps a [`crate::types::StochasticRule`] with a
+/// unit-struct neighborhood builder, keeps its own
+/// [`crate::automaton::context::StepContext`] so that repeated
+/// [`crate::types::CellularAutomaton::step`] calls draw fresh randomness, and
+/// has a selectable [`crate::automaton::update_scheme::UpdateScheme`].
+///
+/// ```ignore
+/// toolkit::define_stochastic_automaton!(
+///     /// Doc comment of the automaton.
+///     ForestFireAutomaton,
+///     ForestFireRule,
+///     UniversalLattice<2, ForestFireState>,
+///     VonNeumannNeighborhoodBuilder
+/// );
+/// ```
+///
+/// The `wasm` and `serde` features of the calling crate decide whether the
+/// automaton is exported and serializable, with `wasm_bindgen` in scope.
+#[macro_export]
+macro_rules! define_stochastic_automaton {
+    ($(#[$meta:meta])* $name:ident, $rule:ty, $lattice:ty, $builder:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
+        pub struct $name {
+            rule: $rule,
+            update_scheme: $crate::automaton::update_scheme::UpdateScheme,
+            context: ::std::cell::Cell<$crate::automaton::context::StepContext>,
+        }
+
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
+        impl $name {
+            #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+            pub fn new(rule: $rule) -> Self {
+                Self {
+                    rule,
+                    update_scheme: ::std::default::Default::default(),
+                    context: ::std::default::Default::default(),
+                }
+            }
+
+            /// Restarts the context [`CellularAutomaton::step`] advances.
+            ///
+            /// [`CellularAutomaton::step`]: $crate::types::CellularAutomaton::step
+            #[cfg_attr(feature = "wasm", wasm_bindgen)]
+            pub fn set_seed(&mut self, seed: u64) {
+                self.context
+                    .set($crate::automaton::context::StepContext::new(seed));
+            }
+        }
+
+        impl $name {
+            pub fn with_update_scheme(
+                mut self,
+                update_scheme: $crate::automaton::update_scheme::UpdateScheme,
+            ) -> Self {
+                self.update_scheme = update_scheme;
+                self
+            }
+
+            pub fn set_update_scheme(
+                &mut self,
+                update_scheme: $crate::automaton::update_scheme::UpdateScheme,
+            ) {
+                self.update_scheme = update_scheme;
+            }
+
+            pub fn with_seed(mut self, seed: u64) -> Self {
+                self.set_seed(seed);
+                self
+            }
+        }
+
+        impl $crate::types::CellularAutomaton for $name {
+            type Lattice = $lattice;
+            type Rule = $rule;
+            type NeighborhoodBuilder = $builder;
+
+            fn rule(&self) -> &Self::Rule {
+                &self.rule
+            }
+
+            fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder {
+                $builder
+            }
+
+            fn update_scheme(&self) -> $crate::automaton::update_scheme::UpdateScheme {
+                self.update_scheme
+            }
+
+            fn step_context(
+                &self,
+            ) -> ::std::option::Option<&::std::cell::Cell<$crate::automaton::context::StepContext>>
+            {
+                ::std::option::Option::Some(&self.context)
+            }
+        }
+    };
+}
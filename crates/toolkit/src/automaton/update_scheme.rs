@@ -0,0 +1,57 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::automaton::context::StepContext;
+
+const ORDER_STREAM: u64 = u64::MAX;
+const ACTIVATION_STREAM: u64 = u64::MAX - 1;
+
+/// Order in which [`crate::types::CellularAutomaton::step_with_context`]
+/// visits and updates the cells of a lattice.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateScheme {
+    /// Every cell reads the previous generation.
+    #[default]
+    Synchronous,
+    /// Cells are updated one by one in a fresh random order each step.
+    RandomSequential,
+    /// Cells are updated one by one in lattice order.
+    LineByLine,
+    /// Cells are updated synchronously, each with probability `alpha`,
+    /// clamped to `0..=1`. NaN updates no cell.
+    Probabilistic(f64),
+    /// Cells are updated one by one in a random order fixed by the seed.
+    Cyclic,
+}
+
+impl UpdateScheme {
+    pub fn is_sequential(&self) -> bool {
+        matches!(self, Self::RandomSequential | Self::LineByLine | Self::Cyclic)
+    }
+
+    pub fn order<P>(&self, mut points: Vec<P>, context: &StepContext) -> Vec<P> {
+        match self {
+            Self::RandomSequential => points.shuffle(&mut context.rng(ORDER_STREAM)),
+            Self::Cyclic => points.shuffle(&mut StepContext::new(context.seed()).rng(ORDER_STREAM)),
+            _ => {}
+        }
+
+        points
+    }
+
+    pub fn activations(&self, count: usize, context: &StepContext) -> Vec<bool> {
+        match self {
+            Self::Probabilistic(alpha) => {
+                let alpha = if alpha.is_nan() {
+                    0.0
+                } else {
+                    alpha.clamp(0.0, 1.0)
+                };
+                let mut rng = context.rng(ACTIVATION_STREAM);
+
+                (0..count).map(|_| rng.gen_bool(alpha)).collect()
+            }
+            _ => vec![true; count],
+        }
+    }
+}
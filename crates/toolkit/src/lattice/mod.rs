@@ -1,3 +1,5 @@
 pub mod universal_point;
 pub mod universal_point_generator;
 pub mod universal_lattice;
+pub mod npy;
+pub mod snapshot;
@@ -0,0 +1,424 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use crate::types::BoundaryHandlingLattice;
+
+use super::snapshot::SnapshotState;
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+/// Longest header [`read_npy`] accepts, numpy's own default limit. The length
+/// field is untrusted and could otherwise ask for a 4 GiB allocation.
+pub const MAX_HEADER_LENGTH: usize = 10_000;
+
+// Arrays are C-ordered with the lattice dimensions reversed, so a 2D lattice
+// of size `[width, height]` becomes an array of shape `(height, width)`
+// indexed `[y, x]`, and a history of `T` such lattices `(T, height, width)`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpyError {
+    /// The data does not start with the `.npy` magic bytes.
+    NotNpy,
+    UnsupportedVersion(u8, u8),
+    InvalidHeader(String),
+    DtypeMismatch {
+        expected: String,
+        found: String,
+    },
+    /// Column-major arrays are not supported.
+    FortranOrder,
+    /// The array has a different number of axes than the lattice, plus one
+    /// for histories.
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The lattices of a history differ in size.
+    ShapeMismatch {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    /// A history without lattices has no shape to write.
+    EmptyHistory,
+    /// The data ends before the last element.
+    Truncated,
+    /// The element at this flat index does not map to a state.
+    InvalidValue(usize),
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotNpy => write!(f, "not a .npy file"),
+            Self::UnsupportedVersion(major, minor) => {
+                write!(f, "unsupported .npy version {major}.{minor}")
+            }
+            Self::InvalidHeader(header) => write!(f, "invalid .npy header `{header}`"),
+            Self::DtypeMismatch { expected, found } => {
+                write!(f, "array has dtype `{found}`, expected `{expected}`")
+            }
+            Self::FortranOrder => write!(f, "Fortran-ordered arrays are not supported"),
+            Self::DimensionMismatch { expected, found } => {
+                write!(f, "array has {found} axes, expected {expected}")
+            }
+            Self::ShapeMismatch { expected, found } => {
+                write!(f, "array has shape {found:?}, expected {expected:?}")
+            }
+            Self::EmptyHistory => write!(f, "history has no lattices"),
+            Self::Truncated => write!(f, "array data is truncated"),
+            Self::InvalidValue(index) => write!(f, "element {index} is not a valid state"),
+            Self::Io(kind) => write!(f, ".npy I/O failed: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<io::Error> for NpyError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.kind())
+    }
+}
+
+/// Element type of an array, stored little-endian.
+pub trait NpyElement: SnapshotState + Copy {
+    /// NumPy type string such as `<u4`.
+    const DESCR: &'static str;
+}
+
+macro_rules! impl_npy_element {
+    ($($ty:ty => $descr:literal),*) => {
+        $(
+            impl NpyElement for $ty {
+                const DESCR: &'static str = $descr;
+            }
+        )*
+    };
+}
+
+impl_npy_element!(
+    bool => "|b1",
+    u8 => "|u1",
+    u16 => "<u2",
+    u32 => "<u4",
+    u64 => "<u8",
+    i8 => "|i1",
+    i16 => "<i2",
+    i32 => "<i4",
+    i64 => "<i8",
+    f32 => "<f4",
+    f64 => "<f8"
+);
+
+/// Writes the states of `lattice` as one array, mapped to elements by
+/// `element`, e.g. `|state| u8::from(*state)` for enum states.
+pub fn write_npy<L, T>(
+    lattice: &L,
+    writer: impl Write,
+    element: impl Fn(&L::State) -> T,
+) -> Result<(), NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::Size: IntoIterator<Item = usize>,
+    T: NpyElement,
+{
+    write_lattices(std::slice::from_ref(lattice), false, writer, element)
+}
+
+/// Writes a `(T, ...dims)` array of lattices that all have the same size.
+pub fn write_npy_history<L, T>(
+    history: &[L],
+    writer: impl Write,
+    element: impl Fn(&L::State) -> T,
+) -> Result<(), NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::Size: IntoIterator<Item = usize>,
+    T: NpyElement,
+{
+    write_lattices(history, true, writer, element)
+}
+
+fn write_lattices<L, T>(
+    lattices: &[L],
+    history: bool,
+    mut writer: impl Write,
+    element: impl Fn(&L::State) -> T,
+) -> Result<(), NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::Size: IntoIterator<Item = usize>,
+    T: NpyElement,
+{
+    let first = lattices.first().ok_or(NpyError::EmptyHistory)?;
+    let shape = array_shape(first);
+
+    let mut data = Vec::new();
+    for lattice in lattices {
+        let found = array_shape(lattice);
+        if found != shape {
+            return Err(NpyError::ShapeMismatch {
+                expected: shape,
+                found,
+            });
+        }
+
+        lattice
+            .states()
+            .iter()
+            .for_each(|state| element(state).write_bytes(&mut data));
+    }
+
+    let shape = if history {
+        std::iter::once(lattices.len()).chain(shape).collect()
+    } else {
+        shape
+    };
+
+    writer.write_all(&header(T::DESCR, &shape))?;
+    Ok(writer.write_all(&data)?)
+}
+
+/// Reads an array with as many axes as the lattice has dimensions. Elements
+/// are mapped back to states by `state`.
+pub fn read_npy<L, T>(
+    reader: impl Read,
+    state: impl Fn(T) -> Option<L::State>,
+) -> Result<L, NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::State: Default + PartialEq,
+    L::Size: IntoIterator<Item = usize> + FromIterator<usize>,
+    T: NpyElement,
+{
+    let (shape, data) = read_array::<T>(reader)?;
+    if shape.len() != L::DIMENSION {
+        return Err(NpyError::DimensionMismatch {
+            expected: L::DIMENSION,
+            found: shape.len(),
+        });
+    }
+
+    lattice_from_elements(&shape, &data, 0, &state)
+}
+
+/// Reads a `(T, ...dims)` array into `T` lattices.
+pub fn read_npy_history<L, T>(
+    reader: impl Read,
+    state: impl Fn(T) -> Option<L::State>,
+) -> Result<Vec<L>, NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::State: Default + PartialEq,
+    L::Size: IntoIterator<Item = usize> + FromIterator<usize>,
+    T: NpyElement,
+{
+    let (shape, data) = read_array::<T>(reader)?;
+    if shape.len() != L::DIMENSION + 1 {
+        return Err(NpyError::DimensionMismatch {
+            expected: L::DIMENSION + 1,
+            found: shape.len(),
+        });
+    }
+
+    // An empty history passes the length check in `read_array` whatever the
+    // other extents are, so their product may still overflow.
+    let cell_count = shape[1..]
+        .iter()
+        .try_fold(1usize, |count, extent| count.checked_mul(*extent))
+        .ok_or_else(|| NpyError::InvalidHeader(format!("shape {shape:?}")))?;
+    (0..shape[0])
+        .map(|time| lattice_from_elements(&shape[1..], &data, time * cell_count, &state))
+        .collect()
+}
+
+fn array_shape<L>(lattice: &L) -> Vec<usize>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::Size: IntoIterator<Item = usize>,
+{
+    let mut shape: Vec<usize> = lattice.size().into_iter().collect();
+    shape.reverse();
+    shape
+}
+
+fn lattice_from_elements<L, T>(
+    shape: &[usize],
+    data: &[T],
+    offset: usize,
+    state: &impl Fn(T) -> Option<L::State>,
+) -> Result<L, NpyError>
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::State: Default + PartialEq,
+    L::Size: FromIterator<usize>,
+    T: NpyElement,
+{
+    let mut lattice = L::from(L::Size::from_iter(shape.iter().rev().copied()));
+    let default = L::State::default();
+
+    for (index, point) in lattice.points().iter().enumerate() {
+        let value = state(data[offset + index]).ok_or(NpyError::InvalidValue(offset + index))?;
+
+        if value != default {
+            lattice.set_state(point, &value);
+        }
+    }
+
+    Ok(lattice)
+}
+
+fn header(descr: &str, shape: &[usize]) -> Vec<u8> {
+    let shape = match shape {
+        [extent] => format!("({extent},)"),
+        _ => {
+            let extents: Vec<String> = shape.iter().map(usize::to_string).collect();
+            format!("({})", extents.join(", "))
+        }
+    };
+    let mut dict = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+
+    // Version 2.0 only widens the header length from two to four bytes.
+    let version = if dict.len() + 64 <= u16::MAX as usize {
+        1
+    } else {
+        2
+    };
+    let prefix_length = MAGIC.len() + 2 + if version == 1 { 2 } else { 4 };
+
+    // The header is padded with spaces to a multiple of 64 bytes and ends in
+    // a newline.
+    let total_length = (prefix_length + dict.len() + 1).div_ceil(64) * 64;
+    dict.extend(std::iter::repeat_n(
+        ' ',
+        total_length - prefix_length - dict.len() - 1,
+    ));
+    dict.push('\n');
+
+    let mut bytes = Vec::with_capacity(total_length);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        bytes.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    } else {
+        bytes.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+    }
+    bytes.extend_from_slice(dict.as_bytes());
+    bytes
+}
+
+fn read_array<T: NpyElement>(mut reader: impl Read) -> Result<(Vec<usize>, Vec<T>), NpyError> {
+    let mut prefix = [0; 8];
+    reader
+        .read_exact(&mut prefix)
+        .map_err(|_| NpyError::NotNpy)?;
+    if !prefix.starts_with(MAGIC) {
+        return Err(NpyError::NotNpy);
+    }
+
+    let header_length = match (prefix[6], prefix[7]) {
+        (1, 0) => {
+            let mut length = [0; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_le_bytes(length) as usize
+        }
+        (2 | 3, 0) => {
+            let mut length = [0; 4];
+            reader.read_exact(&mut length)?;
+            u32::from_le_bytes(length) as usize
+        }
+        (major, minor) => return Err(NpyError::UnsupportedVersion(major, minor)),
+    };
+    if header_length > MAX_HEADER_LENGTH {
+        return Err(NpyError::InvalidHeader(format!(
+            "{header_length} bytes long"
+        )));
+    }
+
+    let mut header = vec![0; header_length];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header).trim().to_string();
+    let (descr, fortran_order, shape) =
+        parse_header(&header).ok_or_else(|| NpyError::InvalidHeader(header.clone()))?;
+
+    if !descr_matches(&descr, T::DESCR) {
+        return Err(NpyError::DtypeMismatch {
+            expected: T::DESCR.to_string(),
+            found: descr,
+        });
+    }
+    if fortran_order {
+        return Err(NpyError::FortranOrder);
+    }
+
+    let length = shape
+        .iter()
+        .try_fold(T::WIDTH, |length, extent| length.checked_mul(*extent))
+        .ok_or_else(|| NpyError::InvalidHeader(header.clone()))?;
+    let mut bytes = Vec::new();
+    reader.take(length as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != length {
+        return Err(NpyError::Truncated);
+    }
+
+    let data = bytes
+        .chunks_exact(T::WIDTH)
+        .enumerate()
+        .map(|(index, bytes)| T::read_bytes(bytes).ok_or(NpyError::InvalidValue(index)))
+        .collect::<Result<_, _>>()?;
+
+    Ok((shape, data))
+}
+
+/// Single-byte types have no byte order, `|u1` and `<u1` are the same.
+fn descr_matches(found: &str, expected: &str) -> bool {
+    let single_byte = expected.starts_with('|');
+
+    match (found.split_at_checked(1), expected.split_at_checked(1)) {
+        (Some((order, kind)), Some((_, expected_kind))) if single_byte => {
+            kind == expected_kind && matches!(order, "|" | "<" | ">" | "=")
+        }
+        _ => found == expected,
+    }
+}
+
+/// `{'descr': '<u1', 'fortran_order': False, 'shape': (3, 4), }`
+fn parse_header(header: &str) -> Option<(String, bool, Vec<usize>)> {
+    let body = header.strip_prefix('{')?.strip_suffix('}')?;
+
+    let value = |key: &str| {
+        let start = body.find(&format!("'{key}'"))? + key.len() + 2;
+        body[start..]
+            .trim_start()
+            .strip_prefix(':')
+            .map(str::trim_start)
+    };
+
+    let descr = value("descr")?.strip_prefix('\'')?;
+    let descr = descr[..descr.find('\'')?].to_string();
+
+    let fortran_order = match value("fortran_order")? {
+        rest if rest.starts_with("True") => true,
+        rest if rest.starts_with("False") => false,
+        _ => return None,
+    };
+
+    let shape = value("shape")?.strip_prefix('(')?;
+    let shape = shape[..shape.find(')')?]
+        .split(',')
+        .map(str::trim)
+        .filter(|extent| !extent.is_empty())
+        .map(|extent| extent.parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+
+    Some((descr, fortran_order, shape))
+}
@@ -0,0 +1,329 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::types::{BoundaryHandling, BoundaryHandlingLattice};
+
+const MAGIC: &[u8; 4] = b"CASN";
+
+/// Bumped on every incompatible change of the layout below.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+// Layout, little-endian:
+//
+//   magic "CASN" | version u16 | dimension u16 | size u64 × dimension
+//   | boundary handling u8 | state width u16 | rule length u16 | rule UTF-8
+//   | generation u64 | zlib compressed states | CRC-32 u32
+//
+// States are stored densely in the order of `Lattice::points`, the CRC
+// covers every byte before it.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The data does not start with the snapshot magic bytes.
+    NotASnapshot,
+    UnsupportedVersion(u16),
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The snapshot was written for a state type of a different byte width.
+    StateWidthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    InvalidBoundaryHandling(u8),
+    /// The rule identifier is longer than 65535 bytes or not UTF-8.
+    InvalidRule,
+    ChecksumMismatch {
+        expected: u32,
+        found: u32,
+    },
+    /// The data ends early, has trailing bytes or holds an invalid state.
+    Corrupted,
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotASnapshot => write!(f, "not a lattice snapshot"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"
+            ),
+            Self::DimensionMismatch { expected, found } => write!(
+                f,
+                "snapshot has {found} dimensions, the lattice has {expected}"
+            ),
+            Self::StateWidthMismatch { expected, found } => write!(
+                f,
+                "snapshot stores {found}-byte states, the lattice uses {expected}-byte states"
+            ),
+            Self::InvalidBoundaryHandling(tag) => write!(f, "invalid boundary handling {tag}"),
+            Self::InvalidRule => write!(f, "invalid rule identifier"),
+            Self::ChecksumMismatch { expected, found } => write!(
+                f,
+                "checksum mismatch, expected {expected:#010x}, found {found:#010x}"
+            ),
+            Self::Corrupted => write!(f, "snapshot data is corrupted"),
+            Self::Io(kind) => write!(f, "snapshot I/O failed: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.kind())
+    }
+}
+
+/// Fixed-width little-endian encoding of a cell state.
+pub trait SnapshotState: Sized {
+    const WIDTH: usize;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+
+    /// `bytes` holds exactly `WIDTH` bytes.
+    fn read_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_snapshot_state {
+    ($($ty:ty),*) => {
+        $(
+            impl SnapshotState for $ty {
+                const WIDTH: usize = std::mem::size_of::<$ty>();
+
+                fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_bytes(bytes: &[u8]) -> Option<Self> {
+                    Some(<$ty>::from_le_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_state!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl SnapshotState for bool {
+    const WIDTH: usize = 1;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// What a snapshot records besides the lattice itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// Free-form rule identifier such as `B3/S23` or `110`.
+    pub rule: String,
+    pub generation: u64,
+}
+
+impl SnapshotInfo {
+    pub fn new(rule: impl Into<String>, generation: u64) -> Self {
+        Self {
+            rule: rule.into(),
+            generation,
+        }
+    }
+}
+
+/// Versioned, compressed and checksummed on-disk form of a lattice.
+pub trait Snapshot: BoundaryHandlingLattice + Sized
+where
+    Self::Point: Clone,
+{
+    fn save_snapshot(&self, writer: impl Write, info: &SnapshotInfo) -> Result<(), SnapshotError>;
+
+    fn load_snapshot(reader: impl Read) -> Result<(Self, SnapshotInfo), SnapshotError>;
+}
+
+impl<L> Snapshot for L
+where
+    L: BoundaryHandlingLattice,
+    L::Point: Clone,
+    L::State: SnapshotState + Default + PartialEq,
+    L::Size: IntoIterator<Item = usize> + FromIterator<usize>,
+{
+    fn save_snapshot(
+        &self,
+        mut writer: impl Write,
+        info: &SnapshotInfo,
+    ) -> Result<(), SnapshotError> {
+        let size: Vec<usize> = self.size().into_iter().collect();
+        let rule_length = u16::try_from(info.rule.len()).map_err(|_| SnapshotError::InvalidRule)?;
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(size.len() as u16).to_le_bytes());
+        size.iter()
+            .for_each(|extent| bytes.extend_from_slice(&(*extent as u64).to_le_bytes()));
+        bytes.push(boundary_handling_tag(self.boundary_handling()));
+        bytes.extend_from_slice(&(L::State::WIDTH as u16).to_le_bytes());
+        bytes.extend_from_slice(&rule_length.to_le_bytes());
+        bytes.extend_from_slice(info.rule.as_bytes());
+        bytes.extend_from_slice(&info.generation.to_le_bytes());
+
+        let mut states = Vec::new();
+        self.states()
+            .iter()
+            .for_each(|state| state.write_bytes(&mut states));
+
+        let mut encoder = ZlibEncoder::new(bytes, Compression::default());
+        encoder.write_all(&states)?;
+        let mut bytes = encoder.finish()?;
+
+        let checksum = crc32fast::hash(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        Ok(writer.write_all(&bytes)?)
+    }
+
+    fn load_snapshot(mut reader: impl Read) -> Result<(Self, SnapshotInfo), SnapshotError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        if !bytes.starts_with(MAGIC) {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let mut input = Input(&bytes[MAGIC.len()..]);
+
+        let version = input.u16()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        if bytes.len() < MAGIC.len() + 2 + 4 {
+            return Err(SnapshotError::Corrupted);
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 4);
+        let expected = u32::from_le_bytes(checksum.try_into().unwrap());
+        let found = crc32fast::hash(body);
+        if expected != found {
+            return Err(SnapshotError::ChecksumMismatch { expected, found });
+        }
+        let mut input = Input(&body[MAGIC.len() + 2..]);
+
+        let dimension = input.u16()? as usize;
+        let size = (0..dimension)
+            .map(|_| usize::try_from(input.u64()?).map_err(|_| SnapshotError::Corrupted))
+            .collect::<Result<Vec<_>, _>>()?;
+        if dimension != L::DIMENSION {
+            return Err(SnapshotError::DimensionMismatch {
+                expected: L::DIMENSION,
+                found: dimension,
+            });
+        }
+
+        let boundary_handling = boundary_handling_from_tag(input.u8()?)?;
+
+        let state_width = input.u16()? as usize;
+        if state_width != L::State::WIDTH {
+            return Err(SnapshotError::StateWidthMismatch {
+                expected: L::State::WIDTH,
+                found: state_width,
+            });
+        }
+
+        let rule_length = input.u16()? as usize;
+        let rule = String::from_utf8(input.take(rule_length)?.to_vec())
+            .map_err(|_| SnapshotError::InvalidRule)?;
+        let generation = input.u64()?;
+
+        let state_bytes = size
+            .iter()
+            .try_fold(1usize, |count, extent| count.checked_mul(*extent))
+            .and_then(|count| count.checked_mul(state_width))
+            .ok_or(SnapshotError::Corrupted)?;
+
+        let mut states = Vec::new();
+        ZlibDecoder::new(input.0)
+            .take(state_bytes as u64 + 1)
+            .read_to_end(&mut states)
+            .map_err(|_| SnapshotError::Corrupted)?;
+        if states.len() != state_bytes {
+            return Err(SnapshotError::Corrupted);
+        }
+
+        let states = states
+            .chunks_exact(state_width.max(1))
+            .map(L::State::read_bytes)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SnapshotError::Corrupted)?;
+
+        // Default states are left out so that the lattice stays sparse.
+        let mut lattice = L::from(L::Size::from_iter(size));
+        let default = L::State::default();
+        lattice
+            .points()
+            .iter()
+            .zip(states)
+            .filter(|(_, state)| *state != default)
+            .for_each(|(point, state)| lattice.set_state(point, &state));
+        lattice.set_boundary_handling(boundary_handling);
+
+        Ok((lattice, SnapshotInfo { rule, generation }))
+    }
+}
+
+fn boundary_handling_tag(boundary_handling: BoundaryHandling) -> u8 {
+    match boundary_handling {
+        BoundaryHandling::Default => 0,
+        BoundaryHandling::Wrap => 1,
+        BoundaryHandling::Clamp => 2,
+    }
+}
+
+fn boundary_handling_from_tag(tag: u8) -> Result<BoundaryHandling, SnapshotError> {
+    match tag {
+        0 => Ok(BoundaryHandling::Default),
+        1 => Ok(BoundaryHandling::Wrap),
+        2 => Ok(BoundaryHandling::Clamp),
+        other => Err(SnapshotError::InvalidBoundaryHandling(other)),
+    }
+}
+
+struct Input<'a>(&'a [u8]);
+
+impl<'a> Input<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.0.len() < length {
+            return Err(SnapshotError::Corrupted);
+        }
+
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
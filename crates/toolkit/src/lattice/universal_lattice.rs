@@ -6,6 +6,8 @@ use crate::{
     types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
     utils::{clamp_coordinate, point_from_flat_index, wrap_coordinate},
 };
+#[cfg(feature = "serde")]
+use crate::utils::flat_index_from_point;
 
 #[derive(Debug, Clone)]
 pub struct UniversalLattice<const D: usize, S> {
@@ -24,6 +26,32 @@ impl<const D: usize, S> From<UniversalLatticeSize<D>> for UniversalLattice<D, S>
     }
 }
 
+impl<const D: usize, S> UniversalLattice<D, S> {
+    pub fn contains(&self, point: &UniversalLatticePoint<D>) -> bool {
+        point
+            .into_iter()
+            .zip(self.size)
+            .all(|(coord, size)| coord >= 0 && coord < size as i128)
+    }
+}
+
+impl<S: Clone + Default> UniversalLattice<2, S> {
+    /// One line of `glyph`s per row, each ending in a newline.
+    pub fn to_text(&self, glyph: impl Fn(&S) -> char) -> String {
+        let mut text = String::new();
+
+        for y in 0..self.size[1] {
+            for x in 0..self.size[0] {
+                let point = UniversalLatticePoint::new([x as i128, y as i128]);
+                text.push(glyph(&self.get_state(&point)));
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
 impl<const D: usize, S> IntoIterator for UniversalLattice<D, S> {
     type Item = (UniversalLatticePoint<D>, S);
     type IntoIter = std::collections::btree_map::IntoIter<UniversalLatticePoint<D>, S>;
@@ -65,6 +93,8 @@ impl<const D: usize, S: Clone + Default> Lattice for UniversalLattice<D, S> {
 impl<const D: usize, S: Clone + Default> BoundaryHandlingLattice for UniversalLattice<D, S> {
     type Size = UniversalLatticeSize<D>;
 
+    const DIMENSION: usize = D;
+
     fn from_states(states: Vec<Self::State>, size: Self::Size) -> Self {
         let mut lattice = Self::from(size);
 
@@ -116,3 +146,168 @@ impl<const D: usize, S: Clone + Default> BoundaryHandlingLattice for UniversalLa
             });
     }
 }
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "UniversalLattice")]
+struct LatticeRepr<const D: usize, S> {
+    size: UniversalLatticeSize<D>,
+    boundary_handling: BoundaryHandling,
+    cells: CellsRepr<D, S>,
+}
+
+/// Most cells a dense encoding may store once expanded. Longer runs of
+/// non-default states are rejected instead of being materialized.
+#[cfg(feature = "serde")]
+pub const MAX_DENSE_CELLS: usize = 1 << 24;
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CellsRepr<const D: usize, S> {
+    /// The stored cells as `(point, state)` pairs.
+    Sparse(Vec<(UniversalLatticePoint<D>, S)>),
+    /// Every cell in flat index order as `(run length, state)` pairs.
+    Dense(Vec<(usize, S)>),
+}
+
+#[cfg(feature = "serde")]
+impl<const D: usize, S: Clone + Default + PartialEq> UniversalLattice<D, S> {
+    /// Whichever of the sparse and the run-length encoded dense form is
+    /// shorter. Dense runs are only used when every stored cell lies inside
+    /// the lattice.
+    fn cells_repr(&self) -> CellsRepr<D, S> {
+        let sparse = || {
+            CellsRepr::Sparse(
+                self.points
+                    .iter()
+                    .map(|(point, state)| (*point, state.clone()))
+                    .collect(),
+            )
+        };
+
+        let cell_count = self.size.into_iter().try_fold(1usize, usize::checked_mul);
+        let Some(cell_count) = cell_count else {
+            return sparse();
+        };
+        if self.points.len() > MAX_DENSE_CELLS
+            || !self.points.keys().all(|point| self.contains(point))
+        {
+            return sparse();
+        }
+
+        let mut indexed: Vec<(usize, &S)> = self
+            .points
+            .iter()
+            .map(|(point, state)| (flat_index_from_point(point, self.size), state))
+            .collect();
+        indexed.sort_unstable_by_key(|(index, _)| *index);
+
+        let default = S::default();
+        let mut runs: Vec<(usize, S)> = Vec::new();
+        let mut push = |length: usize, state: &S| match runs.last_mut() {
+            Some((count, last)) if last == state => *count += length,
+            _ => runs.push((length, state.clone())),
+        };
+
+        let mut next = 0;
+        for (index, state) in indexed {
+            if index > next {
+                push(index - next, &default);
+            }
+            push(1, state);
+            next = index + 1;
+        }
+        if cell_count > next {
+            push(cell_count - next, &default);
+        }
+
+        if runs.len() * 2 < self.points.len() * (D + 1) {
+            CellsRepr::Dense(runs)
+        } else {
+            sparse()
+        }
+    }
+}
+
+/// Size, boundary handling and either the sparse cells or run-length encoded
+/// dense cells, whichever is shorter.
+#[cfg(feature = "serde")]
+impl<const D: usize, S> serde::Serialize for UniversalLattice<D, S>
+where
+    S: serde::Serialize + Clone + Default + PartialEq,
+{
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        LatticeRepr {
+            size: self.size,
+            boundary_handling: self.boundary_handling,
+            cells: self.cells_repr(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Dense runs of the default state are not stored. Sparse cells outside the
+/// lattice and dense cells storing more than [`MAX_DENSE_CELLS`] cells are
+/// rejected.
+#[cfg(feature = "serde")]
+impl<'de, const D: usize, S> serde::Deserialize<'de> for UniversalLattice<D, S>
+where
+    S: serde::Deserialize<'de> + Clone + Default + PartialEq,
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        use serde::de::Error;
+
+        let repr = LatticeRepr::<D, S>::deserialize(deserializer)?;
+        let mut lattice = Self::from(repr.size);
+        lattice.boundary_handling = repr.boundary_handling;
+
+        match repr.cells {
+            CellsRepr::Sparse(cells) => {
+                if let Some((point, _)) = cells.iter().find(|(point, _)| !lattice.contains(point)) {
+                    return Err(De::Error::custom(format!(
+                        "sparse cell {:?} lies outside the lattice",
+                        point.values()
+                    )));
+                }
+
+                lattice.points = cells.into_iter().collect();
+            }
+            CellsRepr::Dense(runs) => {
+                let cell_count = repr.size.into_iter().try_fold(1usize, usize::checked_mul);
+                let covered = runs
+                    .iter()
+                    .try_fold(0usize, |total, (length, _)| total.checked_add(*length));
+                if cell_count.is_none() || covered != cell_count {
+                    return Err(De::Error::custom(
+                        "dense runs do not cover the lattice exactly",
+                    ));
+                }
+
+                let default = S::default();
+                let stored = runs
+                    .iter()
+                    .filter(|(_, state)| *state != default)
+                    .try_fold(0usize, |total, (length, _)| total.checked_add(*length));
+                if stored.is_none_or(|stored| stored > MAX_DENSE_CELLS) {
+                    return Err(De::Error::custom(format!(
+                        "dense runs store more than {MAX_DENSE_CELLS} cells"
+                    )));
+                }
+
+                let mut index = 0;
+                for (length, state) in runs {
+                    if state != default {
+                        for offset in index..index + length {
+                            let point = point_from_flat_index(offset, repr.size);
+                            lattice.points.insert(point, state.clone());
+                        }
+                    }
+                    index += length;
+                }
+            }
+        }
+
+        Ok(lattice)
+    }
+}
@@ -72,3 +72,54 @@ impl<const D: usize, C> IndexMut<usize> for UniversalPoint<C, D> {
         self.0.index_mut(index)
     }
 }
+
+/// Points are written as a plain array of their `D` coordinates.
+#[cfg(feature = "serde")]
+impl<const D: usize, C: serde::Serialize> serde::Serialize for UniversalPoint<C, D> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(D)?;
+        for value in &self.0 {
+            tuple.serialize_element(value)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const D: usize, C: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for UniversalPoint<C, D>
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct PointVisitor<C, const D: usize>(std::marker::PhantomData<C>);
+
+        impl<'de, const D: usize, C: serde::Deserialize<'de>> Visitor<'de> for PointVisitor<C, D> {
+            type Value = UniversalPoint<C, D>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "an array of {D} coordinates")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::with_capacity(D);
+
+                while values.len() < D {
+                    match seq.next_element()? {
+                        Some(value) => values.push(value),
+                        None => return Err(A::Error::invalid_length(values.len(), &self)),
+                    }
+                }
+
+                match values.try_into() {
+                    Ok(values) => Ok(UniversalPoint(values)),
+                    Err(_) => unreachable!("exactly D coordinates were read"),
+                }
+            }
+        }
+
+        deserializer.deserialize_tuple(D, PointVisitor(std::marker::PhantomData))
+    }
+}
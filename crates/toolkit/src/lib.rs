@@ -3,5 +3,10 @@ pub mod aliases;
 pub mod types;
 pub mod utils;
 
+pub mod analysis;
+pub mod automaton;
 pub mod lattice;
 pub mod neighborhood;
+pub mod noise;
+#[cfg(feature = "svg")]
+pub mod render;
@@ -0,0 +1,79 @@
+use crate::{
+    aliases::UniversalLatticePoint,
+    lattice::universal_lattice::UniversalLattice,
+    types::{Lattice, Neighborhood, NeighborhoodBuilder},
+};
+
+#[derive(Debug, Clone)]
+pub struct HexagonalNeighborhood<S> {
+    states: Vec<S>,
+}
+
+impl<S> Neighborhood for HexagonalNeighborhood<S> {
+    type State = S;
+    type Iter<'a>
+        = std::slice::Iter<'a, S>
+    where
+        S: 'a;
+
+    fn iter_states(&self) -> Self::Iter<'_> {
+        self.states.iter()
+    }
+}
+
+/// Six neighbors of a hexagonal grid stored in "odd-r" offset coordinates,
+/// where odd rows are shifted half a cell to the right.
+///
+/// Neighbors are ordered counter-clockwise starting east: east, north-east,
+/// north-west, west, south-west, south-east. With
+/// [`crate::types::BoundaryHandling::Wrap`] the lattice height must be even.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HexagonalNeighborhoodBuilder;
+
+impl HexagonalNeighborhoodBuilder {
+    pub const DIRECTIONS: usize = 6;
+
+    pub fn offsets(row: i128) -> [(i128, i128); 6] {
+        if row.rem_euclid(2) == 0 {
+            [(1, 0), (0, -1), (-1, -1), (-1, 0), (-1, 1), (0, 1)]
+        } else {
+            [(1, 0), (1, -1), (0, -1), (-1, 0), (0, 1), (1, 1)]
+        }
+    }
+
+    pub fn neighbor_point(
+        point: &UniversalLatticePoint<2>,
+        direction: usize,
+    ) -> UniversalLatticePoint<2> {
+        let (dx, dy) = Self::offsets(point[1])[direction % Self::DIRECTIONS];
+        UniversalLatticePoint::new([point[0] + dx, point[1] + dy])
+    }
+
+    /// Cartesian center of a cell for unit spacing between neighbors.
+    pub fn center(point: &UniversalLatticePoint<2>) -> (f64, f64) {
+        let shift = if point[1].rem_euclid(2) == 0 {
+            0.0
+        } else {
+            0.5
+        };
+        (point[0] as f64 + shift, point[1] as f64 * 3f64.sqrt() / 2.0)
+    }
+}
+
+impl<S: Clone + Default> NeighborhoodBuilder<UniversalLattice<2, S>>
+    for HexagonalNeighborhoodBuilder
+{
+    type Neighborhood = HexagonalNeighborhood<S>;
+
+    fn build_neighborhood(
+        &self,
+        point: &<UniversalLattice<2, S> as Lattice>::Point,
+        lattice: &UniversalLattice<2, S>,
+    ) -> Self::Neighborhood {
+        let states = (0..Self::DIRECTIONS)
+            .map(|direction| lattice.get_state(&Self::neighbor_point(point, direction)))
+            .collect();
+
+        HexagonalNeighborhood { states }
+    }
+}
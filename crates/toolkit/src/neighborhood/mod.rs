@@ -1,2 +1,4 @@
+pub mod hexagonal;
 pub mod moore;
 pub mod nearest;
+pub mod von_neumann;
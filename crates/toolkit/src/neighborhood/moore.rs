@@ -1,7 +1,7 @@
 use crate::{
     aliases::UniversalLatticePoint,
     lattice::universal_lattice::UniversalLattice,
-    types::{Lattice, Neighborhood, NeighborhoodBuilder},
+    types::{Lattice, Neighborhood, NeighborhoodBuilder, OffsetNeighborhoodBuilder},
 };
 
 #[derive(Debug)]
@@ -21,9 +21,35 @@ impl<S> Neighborhood for MooreNeighborhood<S> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct MooreNeighborhoodBuilder;
 
+impl MooreNeighborhoodBuilder {
+    /// All `3^D - 1` offsets of the Moore neighborhood in `D` dimensions.
+    pub fn offsets<const D: usize>() -> Vec<UniversalLatticePoint<D>> {
+        (0..3usize.pow(D as u32))
+            .map(|index| {
+                let mut remaining = index;
+                let mut coords = [0; D];
+
+                for coord in coords.iter_mut() {
+                    *coord = (remaining % 3) as i128 - 1;
+                    remaining /= 3;
+                }
+
+                UniversalLatticePoint::new(coords)
+            })
+            .filter(|offset| offset.values().iter().any(|coord| *coord != 0))
+            .collect()
+    }
+}
+
+impl<const D: usize> OffsetNeighborhoodBuilder<D> for MooreNeighborhoodBuilder {
+    fn neighbor_offsets(&self) -> Vec<UniversalLatticePoint<D>> {
+        Self::offsets::<D>()
+    }
+}
+
 impl<S: Clone + Default> NeighborhoodBuilder<UniversalLattice<2, S>> for MooreNeighborhoodBuilder {
     type Neighborhood = MooreNeighborhood<S>;
 
@@ -0,0 +1,69 @@
+use crate::{
+    aliases::UniversalLatticePoint,
+    lattice::universal_lattice::UniversalLattice,
+    types::{Lattice, Neighborhood, NeighborhoodBuilder, OffsetNeighborhoodBuilder},
+    utils::translate,
+};
+
+#[derive(Debug, Clone)]
+pub struct VonNeumannNeighborhood<S> {
+    states: Vec<S>,
+}
+
+impl<S> Neighborhood for VonNeumannNeighborhood<S> {
+    type State = S;
+    type Iter<'a>
+        = std::slice::Iter<'a, S>
+    where
+        S: 'a;
+
+    fn iter_states(&self) -> Self::Iter<'_> {
+        self.states.iter()
+    }
+}
+
+/// Builds the `2 * D` axis-aligned neighbors of a point, ordered per axis as
+/// `-1` then `+1` (for `D = 2`: west, east, north, south).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VonNeumannNeighborhoodBuilder;
+
+impl VonNeumannNeighborhoodBuilder {
+    pub fn offsets<const D: usize>() -> Vec<UniversalLatticePoint<D>> {
+        let mut offsets = Vec::with_capacity(2 * D);
+
+        for axis in 0..D {
+            for delta in [-1, 1] {
+                let mut coords = [0; D];
+                coords[axis] = delta;
+                offsets.push(UniversalLatticePoint::new(coords));
+            }
+        }
+
+        offsets
+    }
+}
+
+impl<const D: usize> OffsetNeighborhoodBuilder<D> for VonNeumannNeighborhoodBuilder {
+    fn neighbor_offsets(&self) -> Vec<UniversalLatticePoint<D>> {
+        Self::offsets::<D>()
+    }
+}
+
+impl<const D: usize, S: Clone + Default> NeighborhoodBuilder<UniversalLattice<D, S>>
+    for VonNeumannNeighborhoodBuilder
+{
+    type Neighborhood = VonNeumannNeighborhood<S>;
+
+    fn build_neighborhood(
+        &self,
+        point: &<UniversalLattice<D, S> as Lattice>::Point,
+        lattice: &UniversalLattice<D, S>,
+    ) -> Self::Neighborhood {
+        let states = Self::offsets::<D>()
+            .into_iter()
+            .map(|offset| lattice.get_state(&translate(point, &offset)))
+            .collect();
+
+        VonNeumannNeighborhood { states }
+    }
+}
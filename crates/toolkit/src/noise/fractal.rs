@@ -0,0 +1,63 @@
+use super::Noise;
+
+/// Fractal Brownian motion: octaves of a base noise at rising frequency and
+/// falling amplitude, normalised back into `[-1, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fractal<N> {
+    noise: N,
+    octaves: usize,
+    lacunarity: f64,
+    persistence: f64,
+}
+
+impl<N> Fractal<N> {
+    pub fn new(noise: N) -> Self {
+        Self {
+            noise,
+            octaves: 4,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        }
+    }
+
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    pub fn with_lacunarity(mut self, lacunarity: f64) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_persistence(mut self, persistence: f64) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    pub fn noise(&self) -> &N {
+        &self.noise
+    }
+}
+
+impl<const D: usize, N: Noise<D>> Noise<D> for Fractal<N> {
+    fn sample(&self, position: [f64; D]) -> f64 {
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let (mut total, mut norm) = (0.0, 0.0);
+
+        for octave in 0..self.octaves {
+            // Shifting each octave keeps lattice-aligned zeros from stacking up.
+            let shift = octave as f64 * 19.19;
+            total += amplitude
+                * self
+                    .noise
+                    .sample(position.map(|coord| coord * frequency + shift));
+            norm += amplitude;
+
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+
+        total / norm
+    }
+}
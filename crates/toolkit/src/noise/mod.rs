@@ -0,0 +1,114 @@
+pub mod fractal;
+pub mod open_simplex;
+pub mod perlin;
+pub mod worley;
+
+use crate::{
+    aliases::UniversalLatticePoint, automaton::context::split_mix, types::BoundaryHandlingLattice,
+};
+
+pub trait Noise<const D: usize> {
+    /// Value at `position`, roughly within `[-1, 1]`.
+    fn sample(&self, position: [f64; D]) -> f64;
+
+    fn sample_point(&self, point: &UniversalLatticePoint<D>, frequency: f64) -> f64 {
+        self.sample(point.values().map(|coord| coord as f64 * frequency))
+    }
+}
+
+pub fn fill_with<const D: usize, L, N, F>(lattice: &mut L, noise: &N, frequency: f64, map: F)
+where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>>,
+    N: Noise<D>,
+    F: Fn(f64) -> L::State,
+{
+    for point in lattice.points() {
+        let state = map(noise.sample_point(&point, frequency));
+        lattice.set_state(&point, &state);
+    }
+}
+
+/// Cells whose noise value is at least `threshold` get `above`, the rest `below`.
+pub fn fill_threshold<const D: usize, L, N>(
+    lattice: &mut L,
+    noise: &N,
+    frequency: f64,
+    threshold: f64,
+    below: L::State,
+    above: L::State,
+) where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>>,
+    L::State: Clone,
+    N: Noise<D>,
+{
+    fill_with(lattice, noise, frequency, |value| {
+        if value >= threshold {
+            above.clone()
+        } else {
+            below.clone()
+        }
+    });
+}
+
+/// Splits `[-1, 1]` into equal bands, one per state, lowest band first.
+pub fn fill_quantized<const D: usize, L, N>(
+    lattice: &mut L,
+    noise: &N,
+    frequency: f64,
+    levels: &[L::State],
+) where
+    L: BoundaryHandlingLattice<Point = UniversalLatticePoint<D>>,
+    L::State: Clone,
+    N: Noise<D>,
+{
+    if levels.is_empty() {
+        return;
+    }
+
+    fill_with(lattice, noise, frequency, |value| {
+        let band = ((value + 1.0) / 2.0 * levels.len() as f64).floor() as usize;
+        levels[band.min(levels.len() - 1)].clone()
+    });
+}
+
+pub(crate) fn hash_cell<const D: usize>(seed: u64, cell: &[i64; D]) -> u64 {
+    cell.iter().fold(split_mix(seed), |hash, coord| {
+        split_mix(hash ^ *coord as u64)
+    })
+}
+
+/// `D` uniform values in `[0, 1)` derived from a hash.
+pub(crate) fn unit_values<const D: usize>(hash: u64) -> [f64; D] {
+    let mut state = hash;
+
+    [0.0; D].map(|_| {
+        state = split_mix(state);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    })
+}
+
+pub(crate) fn gradient<const D: usize>(hash: u64) -> [f64; D] {
+    let mut gradient = unit_values::<D>(hash).map(|value| value * 2.0 - 1.0);
+    let length = gradient
+        .iter()
+        .map(|value| value * value)
+        .sum::<f64>()
+        .sqrt();
+
+    if length == 0.0 {
+        gradient = [0.0; D];
+        if let Some(first) = gradient.first_mut() {
+            *first = 1.0;
+        }
+        return gradient;
+    }
+
+    gradient.map(|value| value / length)
+}
+
+pub(crate) fn dot<const D: usize>(left: &[f64; D], right: &[f64; D]) -> f64 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left * right)
+        .sum()
+}
@@ -0,0 +1,55 @@
+use super::{Noise, dot, gradient, hash_cell};
+
+const RADIUS_SQUARED: f64 = 2.0;
+
+/// OpenSimplex-style noise in any dimension: gradients sit on the stretched
+/// hypercubic (simplectic) lattice and every vertex within the kernel radius
+/// contributes `(r² - d²)^4 (g · d)`, so there are no axis-aligned artifacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OpenSimplexNoise {
+    seed: u64,
+}
+
+impl OpenSimplexNoise {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl<const D: usize> Noise<D> for OpenSimplexNoise {
+    fn sample(&self, position: [f64; D]) -> f64 {
+        let dimensions = D as f64;
+        let stretch = (1.0 / (dimensions + 1.0).sqrt() - 1.0) / dimensions;
+        let squish = ((dimensions + 1.0).sqrt() - 1.0) / dimensions;
+
+        let stretch_offset = position.iter().sum::<f64>() * stretch;
+        let base = position.map(|coord| (coord + stretch_offset).floor() as i64);
+
+        // The kernel reaches at most ~1.3 cells in stretched space, so two
+        // cells either side cover every contributing vertex.
+        let total: f64 = (0..5usize.pow(D as u32))
+            .filter_map(|index| {
+                let mut vertex = base;
+                let mut remaining = index;
+
+                for coord in vertex.iter_mut() {
+                    *coord += (remaining % 5) as i64 - 2;
+                    remaining /= 5;
+                }
+
+                let squish_offset = vertex.iter().sum::<i64>() as f64 * squish;
+                let mut offset = [0.0; D];
+                (0..D).for_each(|axis| {
+                    offset[axis] = position[axis] - (vertex[axis] as f64 + squish_offset)
+                });
+
+                let attenuation = RADIUS_SQUARED - dot(&offset, &offset);
+                (attenuation > 0.0).then(|| {
+                    attenuation.powi(4) * dot(&gradient(hash_cell(self.seed, &vertex)), &offset)
+                })
+            })
+            .sum();
+
+        (total / 7.5).clamp(-1.0, 1.0)
+    }
+}
@@ -0,0 +1,53 @@
+use super::{Noise, dot, gradient, hash_cell};
+
+// Peak amplitude sits near 0.7 for D >= 2; this maps it onto roughly ±1.
+const NORMALIZATION: f64 = 1.45;
+
+/// Classic gradient noise: random unit gradients at integer points blended
+/// over the `2^D` corners of the enclosing cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PerlinNoise {
+    seed: u64,
+}
+
+impl PerlinNoise {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl<const D: usize> Noise<D> for PerlinNoise {
+    fn sample(&self, position: [f64; D]) -> f64 {
+        let cell = position.map(f64::floor);
+        let mut local = [0.0; D];
+        (0..D).for_each(|axis| local[axis] = position[axis] - cell[axis]);
+        let faded = local.map(fade);
+
+        let total: f64 = (0..1usize << D)
+            .map(|corner| {
+                let mut vertex = [0i64; D];
+                let mut offset = [0.0; D];
+                let mut weight = 1.0;
+
+                for axis in 0..D {
+                    let bit = (corner >> axis) & 1;
+                    vertex[axis] = cell[axis] as i64 + bit as i64;
+                    offset[axis] = local[axis] - bit as f64;
+                    weight *= if bit == 1 {
+                        faded[axis]
+                    } else {
+                        1.0 - faded[axis]
+                    };
+                }
+
+                weight * dot(&gradient(hash_cell(self.seed, &vertex)), &offset)
+            })
+            .sum();
+
+        (total * NORMALIZATION).clamp(-1.0, 1.0)
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
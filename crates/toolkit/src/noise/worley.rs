@@ -0,0 +1,96 @@
+use super::{Noise, hash_cell, unit_values};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorleyDistance {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl WorleyDistance {
+    fn measure<const D: usize>(&self, from: &[f64; D], to: &[f64; D]) -> f64 {
+        let deltas = from.iter().zip(to).map(|(from, to)| (from - to).abs());
+
+        match self {
+            Self::Euclidean => deltas.map(|delta| delta * delta).sum::<f64>().sqrt(),
+            Self::Manhattan => deltas.sum(),
+            Self::Chebyshev => deltas.fold(0.0, f64::max),
+        }
+    }
+}
+
+/// Which nearest feature distance the noise reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorleyFeature {
+    #[default]
+    F1,
+    F2,
+    F2MinusF1,
+}
+
+/// Cellular noise: one random feature point per unit cell, sampled as the
+/// distance to the nearest ones and mapped from `[0, 1]` onto `[-1, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WorleyNoise {
+    seed: u64,
+    distance: WorleyDistance,
+    feature: WorleyFeature,
+}
+
+impl WorleyNoise {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_distance(mut self, distance: WorleyDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_feature(mut self, feature: WorleyFeature) -> Self {
+        self.feature = feature;
+        self
+    }
+}
+
+impl<const D: usize> Noise<D> for WorleyNoise {
+    fn sample(&self, position: [f64; D]) -> f64 {
+        let cell = position.map(|coord| coord.floor() as i64);
+        let (mut nearest, mut second) = (f64::INFINITY, f64::INFINITY);
+
+        for index in 0..3usize.pow(D as u32) {
+            let mut neighbor = cell;
+            let mut remaining = index;
+
+            for coord in neighbor.iter_mut() {
+                *coord += (remaining % 3) as i64 - 1;
+                remaining /= 3;
+            }
+
+            let jitter = unit_values::<D>(hash_cell(self.seed, &neighbor));
+            let mut feature = [0.0; D];
+            (0..D).for_each(|axis| feature[axis] = neighbor[axis] as f64 + jitter[axis]);
+
+            let distance = self.distance.measure(&position, &feature);
+
+            if distance < nearest {
+                second = nearest;
+                nearest = distance;
+            } else if distance < second {
+                second = distance;
+            }
+        }
+
+        let value = match self.feature {
+            WorleyFeature::F1 => nearest,
+            WorleyFeature::F2 => second,
+            WorleyFeature::F2MinusF1 => second - nearest,
+        };
+
+        (value.min(1.0) * 2.0 - 1.0).clamp(-1.0, 1.0)
+    }
+}
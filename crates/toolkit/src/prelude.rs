@@ -1,7 +1,14 @@
+pub use crate::automaton::context::CellContext;
+pub use crate::automaton::context::StepContext;
+pub use crate::automaton::context::StepRng;
+pub use crate::automaton::update_scheme::UpdateScheme;
+pub use crate::noise::Noise;
 pub use crate::types::BoundaryHandling;
 pub use crate::types::BoundaryHandlingLattice;
 pub use crate::types::CellularAutomaton;
 pub use crate::types::Lattice;
 pub use crate::types::Neighborhood;
 pub use crate::types::NeighborhoodBuilder;
+pub use crate::types::OffsetNeighborhoodBuilder;
 pub use crate::types::Rule;
+pub use crate::types::StochasticRule;
@@ -0,0 +1,249 @@
+use std::{fmt, io::Write, time::Duration};
+
+use crate::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    automaton::context::StepContext,
+    types::{BoundaryHandlingLattice, CellularAutomaton, Lattice},
+};
+
+use super::{Renderer, image::ImageBuffer, palette::Palette};
+
+#[derive(Debug)]
+pub enum AnimationError {
+    NoFrames,
+    /// Frames must all have the size of the first one.
+    FrameSizeMismatch,
+    /// GIF dimensions are limited to 65535 pixels.
+    ImageTooLarge,
+    Gif(gif::EncodingError),
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for AnimationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoFrames => write!(f, "animation has no frames"),
+            Self::FrameSizeMismatch => write!(f, "frames differ in size"),
+            Self::ImageTooLarge => write!(f, "frames are too large for the format"),
+            Self::Gif(error) => write!(f, "GIF encoding failed: {error}"),
+            Self::Png(error) => write!(f, "APNG encoding failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for AnimationError {}
+
+impl From<gif::EncodingError> for AnimationError {
+    fn from(error: gif::EncodingError) -> Self {
+        Self::Gif(error)
+    }
+}
+
+impl From<png::EncodingError> for AnimationError {
+    fn from(error: png::EncodingError) -> Self {
+        Self::Png(error)
+    }
+}
+
+/// How many times the animation plays in total.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoopCount {
+    #[default]
+    Infinite,
+    Finite(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    frames: Vec<ImageBuffer>,
+    frame_delay: Duration,
+    loop_count: LoopCount,
+}
+
+impl Animation {
+    pub fn new(frame_delay: Duration, loop_count: LoopCount) -> Self {
+        Self {
+            frames: Vec::new(),
+            frame_delay,
+            loop_count,
+        }
+    }
+
+    pub fn push_frame(&mut self, frame: ImageBuffer) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[ImageBuffer] {
+        &self.frames
+    }
+
+    pub fn frame_delay(&self) -> Duration {
+        self.frame_delay
+    }
+
+    pub fn loop_count(&self) -> LoopCount {
+        self.loop_count
+    }
+
+    /// GIF has a 1-bit alpha channel and at most 256 colors per frame; frames
+    /// with more colors are quantized. The delay is truncated to whole
+    /// centiseconds but kept at one or more, since viewers play a zero delay
+    /// at their own speed.
+    pub fn write_gif(&self, writer: impl Write) -> Result<(), AnimationError> {
+        let (width, height) = self.frame_size()?;
+        let width = u16::try_from(width).map_err(|_| AnimationError::ImageTooLarge)?;
+        let height = u16::try_from(height).map_err(|_| AnimationError::ImageTooLarge)?;
+        let delay = (self.frame_delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+
+        let mut encoder = gif::Encoder::new(writer, width, height, &[])?;
+        match self.loop_count {
+            LoopCount::Infinite => encoder.set_repeat(gif::Repeat::Infinite)?,
+            // The NETSCAPE extension counts repeats after the first play.
+            LoopCount::Finite(plays) if plays > 1 => {
+                encoder.set_repeat(gif::Repeat::Finite(plays - 1))?
+            }
+            LoopCount::Finite(_) => {}
+        }
+
+        for image in &self.frames {
+            let mut pixels = image.pixels().to_vec();
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+
+            frame.delay = delay;
+            frame.dispose = gif::DisposalMethod::Background;
+            encoder.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// The delay is stored in milliseconds, capped at 65.535 seconds.
+    pub fn write_apng(&self, writer: impl Write) -> Result<(), AnimationError> {
+        let (width, height) = self.frame_size()?;
+        let plays = match self.loop_count {
+            LoopCount::Infinite => 0,
+            LoopCount::Finite(plays) => plays.max(1) as u32,
+        };
+        let delay = self.frame_delay.as_millis().min(u16::MAX as u128) as u16;
+
+        let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(self.frames.len() as u32, plays)?;
+        encoder.set_frame_delay(delay, 1000)?;
+
+        let mut writer = encoder.write_header()?;
+        for image in &self.frames {
+            writer.write_image_data(image.pixels())?;
+        }
+
+        Ok(writer.finish()?)
+    }
+
+    fn frame_size(&self) -> Result<(usize, usize), AnimationError> {
+        let first = self.frames.first().ok_or(AnimationError::NoFrames)?;
+        let size = (first.width(), first.height());
+
+        if self
+            .frames
+            .iter()
+            .any(|frame| (frame.width(), frame.height()) != size)
+        {
+            return Err(AnimationError::FrameSizeMismatch);
+        }
+
+        Ok(size)
+    }
+}
+
+/// Runs an automaton headlessly and renders every generation as a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationRecorder {
+    renderer: Renderer,
+    crop: Option<(UniversalLatticePoint<2>, UniversalLatticeSize<2>)>,
+    frame_delay: Duration,
+    loop_count: LoopCount,
+}
+
+impl Default for AnimationRecorder {
+    fn default() -> Self {
+        Self {
+            renderer: Renderer::default(),
+            crop: None,
+            frame_delay: Duration::from_millis(100),
+            loop_count: LoopCount::default(),
+        }
+    }
+}
+
+impl AnimationRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_renderer(mut self, renderer: Renderer) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// Renders only `size` cells starting at `corner`.
+    pub fn with_crop(
+        mut self,
+        corner: UniversalLatticePoint<2>,
+        size: UniversalLatticeSize<2>,
+    ) -> Self {
+        self.crop = Some((corner, size));
+        self
+    }
+
+    pub fn with_frame_delay(mut self, frame_delay: Duration) -> Self {
+        self.frame_delay = frame_delay;
+        self
+    }
+
+    pub fn with_loop_count(mut self, loop_count: LoopCount) -> Self {
+        self.loop_count = loop_count;
+        self
+    }
+
+    /// The current state and `generations` steps after it, `generations + 1`
+    /// frames in total. The lattice is left at the last generation.
+    pub fn record<A>(
+        &self,
+        automaton: &A,
+        lattice: &mut A::Lattice,
+        context: &mut StepContext,
+        generations: usize,
+        palette: &impl Palette<<A::Lattice as Lattice>::State>,
+    ) -> Animation
+    where
+        A: CellularAutomaton,
+        A::Lattice: BoundaryHandlingLattice<
+                Point = UniversalLatticePoint<2>,
+                Size = UniversalLatticeSize<2>,
+            >,
+    {
+        let mut animation = Animation::new(self.frame_delay, self.loop_count);
+        animation.push_frame(self.render(lattice, palette));
+
+        for _ in 0..generations {
+            automaton.step_with_context(lattice, context);
+            animation.push_frame(self.render(lattice, palette));
+        }
+
+        animation
+    }
+
+    fn render<L>(&self, lattice: &L, palette: &impl Palette<L::State>) -> ImageBuffer
+    where
+        L: BoundaryHandlingLattice<
+                Point = UniversalLatticePoint<2>,
+                Size = UniversalLatticeSize<2>,
+            >,
+    {
+        match &self.crop {
+            Some((corner, size)) => self.renderer.render_region(lattice, corner, size, palette),
+            None => self.renderer.render(lattice, palette),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::{self, Write};
+
+use super::palette::{Rgba, TRANSPARENT};
+
+/// Row-major RGBA pixels, four bytes per pixel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageBuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl ImageBuffer {
+    pub fn new(width: usize, height: usize, fill: Rgba) -> Self {
+        Self {
+            width,
+            height,
+            pixels: fill.repeat(width * height),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Transparent outside the image.
+    pub fn pixel(&self, x: usize, y: usize) -> Rgba {
+        if x >= self.width || y >= self.height {
+            return TRANSPARENT;
+        }
+
+        let offset = (y * self.width + x) * 4;
+        std::array::from_fn(|channel| self.pixels[offset + channel])
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Rgba) {
+        self.fill_rect(x, y, 1, 1, color);
+    }
+
+    /// Fills a rectangle, clipped to the image.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgba) {
+        let right = (x + width).min(self.width);
+        let bottom = (y + height).min(self.height);
+
+        for row in y.min(bottom)..bottom {
+            let start = (row * self.width + x.min(right)) * 4;
+            let end = (row * self.width + right) * 4;
+
+            self.pixels[start..end]
+                .chunks_exact_mut(4)
+                .for_each(|pixel| pixel.copy_from_slice(&color));
+        }
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()
+    }
+
+    /// Binary `P6` PPM. The format has no alpha channel, so it is dropped.
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let rgb: Vec<u8> = self
+            .pixels
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+
+        writer.write_all(&rgb)
+    }
+}
@@ -0,0 +1,175 @@
+//! Raster rendering to PNG and GIF needs the `render` feature; palettes and
+//! [`svg`] only need `svg`, which `render` enables.
+
+#[cfg(feature = "render")]
+pub mod animation;
+#[cfg(feature = "render")]
+pub mod image;
+pub mod palette;
+pub mod svg;
+
+#[cfg(feature = "render")]
+use crate::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    types::BoundaryHandlingLattice,
+};
+
+#[cfg(feature = "render")]
+use self::{
+    image::ImageBuffer,
+    palette::{Palette, Rgba, TRANSPARENT},
+};
+
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GridLines {
+    color: Rgba,
+    thickness: usize,
+}
+
+/// Draws every cell as a `scale`×`scale` square, optionally separated and
+/// framed by grid lines.
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer {
+    scale: usize,
+    grid_lines: Option<GridLines>,
+}
+
+#[cfg(feature = "render")]
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            grid_lines: None,
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn with_grid_lines(mut self, color: Rgba, thickness: usize) -> Self {
+        self.grid_lines = (thickness > 0).then_some(GridLines { color, thickness });
+        self
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Pixel size of an image showing `columns`×`rows` cells.
+    pub fn image_size(&self, columns: usize, rows: usize) -> (usize, usize) {
+        let thickness = self.thickness();
+
+        (
+            columns * self.scale + (columns + 1) * thickness,
+            rows * self.scale + (rows + 1) * thickness,
+        )
+    }
+
+    /// Top-left pixel of the cell in the given column and row.
+    pub fn cell_origin(&self, column: usize, row: usize) -> (usize, usize) {
+        let thickness = self.thickness();
+        let step = self.scale + thickness;
+
+        (thickness + column * step, thickness + row * step)
+    }
+
+    pub fn render<L>(&self, lattice: &L, palette: &impl Palette<L::State>) -> ImageBuffer
+    where
+        L: BoundaryHandlingLattice<
+                Point = UniversalLatticePoint<2>,
+                Size = UniversalLatticeSize<2>,
+            >,
+    {
+        self.render_region(
+            lattice,
+            &UniversalLatticePoint::new([0, 0]),
+            &lattice.size(),
+            palette,
+        )
+    }
+
+    /// Only the `size` cells starting at `corner`. Cells outside the lattice
+    /// are looked up through its boundary handling.
+    pub fn render_region<L>(
+        &self,
+        lattice: &L,
+        corner: &UniversalLatticePoint<2>,
+        size: &UniversalLatticeSize<2>,
+        palette: &impl Palette<L::State>,
+    ) -> ImageBuffer
+    where
+        L: BoundaryHandlingLattice<
+                Point = UniversalLatticePoint<2>,
+                Size = UniversalLatticeSize<2>,
+            >,
+    {
+        let [left, top] = corner.values();
+
+        self.render_cells(size[0], size[1], |x, y| {
+            let point = UniversalLatticePoint::new([left + x as i128, top + y as i128]);
+            palette.color(&lattice.get_state(&point))
+        })
+    }
+
+    /// Space-time diagram of a one-dimensional automaton: generation `t`
+    /// becomes row `t`. Shorter generations leave the rest of their row
+    /// transparent.
+    pub fn render_space_time<L>(
+        &self,
+        generations: &[L],
+        palette: &impl Palette<L::State>,
+    ) -> ImageBuffer
+    where
+        L: BoundaryHandlingLattice<
+                Point = UniversalLatticePoint<1>,
+                Size = UniversalLatticeSize<1>,
+            >,
+    {
+        let columns = generations.iter().map(|lattice| lattice.size()[0]).max();
+
+        self.render_cells(columns.unwrap_or(0), generations.len(), |x, t| {
+            let lattice = &generations[t];
+
+            if x < lattice.size()[0] {
+                palette.color(&lattice.get_state(&UniversalLatticePoint::new([x as i128])))
+            } else {
+                TRANSPARENT
+            }
+        })
+    }
+
+    fn render_cells(
+        &self,
+        columns: usize,
+        rows: usize,
+        color: impl Fn(usize, usize) -> Rgba,
+    ) -> ImageBuffer {
+        let (width, height) = self.image_size(columns, rows);
+        let background = self.grid_lines.map_or(TRANSPARENT, |grid| grid.color);
+        let mut image = ImageBuffer::new(width, height, background);
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let (x, y) = self.cell_origin(column, row);
+                image.fill_rect(x, y, self.scale, self.scale, color(column, row));
+            }
+        }
+
+        image
+    }
+
+    fn thickness(&self) -> usize {
+        self.grid_lines.map_or(0, |grid| grid.thickness)
+    }
+}
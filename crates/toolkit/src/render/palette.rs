@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+/// Straight (non-premultiplied) 8-bit RGBA.
+pub type Rgba = [u8; 4];
+
+pub const TRANSPARENT: Rgba = [0, 0, 0, 0];
+pub const BLACK: Rgba = [0, 0, 0, 255];
+pub const WHITE: Rgba = [255, 255, 255, 255];
+
+pub trait Palette<S> {
+    fn color(&self, state: &S) -> Rgba;
+}
+
+impl<S, F: Fn(&S) -> Rgba> Palette<S> for F {
+    fn color(&self, state: &S) -> Rgba {
+        self(state)
+    }
+}
+
+/// Explicit color per state, `fallback` for every state not listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapPalette<S> {
+    colors: BTreeMap<S, Rgba>,
+    fallback: Rgba,
+}
+
+impl<S: Ord> MapPalette<S> {
+    pub fn new(fallback: Rgba) -> Self {
+        Self {
+            colors: BTreeMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn with_color(mut self, state: S, color: Rgba) -> Self {
+        self.colors.insert(state, color);
+        self
+    }
+
+    pub fn set_color(&mut self, state: S, color: Rgba) {
+        self.colors.insert(state, color);
+    }
+}
+
+impl<S: Ord> FromIterator<(S, Rgba)> for MapPalette<S> {
+    fn from_iter<I: IntoIterator<Item = (S, Rgba)>>(iter: I) -> Self {
+        Self {
+            colors: iter.into_iter().collect(),
+            fallback: TRANSPARENT,
+        }
+    }
+}
+
+impl<S: Ord> Palette<S> for MapPalette<S> {
+    fn color(&self, state: &S) -> Rgba {
+        self.colors.get(state).copied().unwrap_or(self.fallback)
+    }
+}
+
+/// Linear blend between two colors for continuous states, clamped to
+/// `[low, high]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientPalette {
+    from: Rgba,
+    to: Rgba,
+    low: f64,
+    high: f64,
+}
+
+impl GradientPalette {
+    pub fn new(from: Rgba, to: Rgba, low: f64, high: f64) -> Self {
+        Self {
+            from,
+            to,
+            low,
+            high,
+        }
+    }
+
+    pub fn sample(&self, value: f64) -> Rgba {
+        let span = self.high - self.low;
+        let t = if span.abs() > f64::EPSILON {
+            ((value - self.low) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        std::array::from_fn(|channel| {
+            let (from, to) = (self.from[channel] as f64, self.to[channel] as f64);
+            (from + (to - from) * t).round() as u8
+        })
+    }
+}
+
+impl Palette<f32> for GradientPalette {
+    fn color(&self, state: &f32) -> Rgba {
+        self.sample(*state as f64)
+    }
+}
+
+impl Palette<f64> for GradientPalette {
+    fn color(&self, state: &f64) -> Rgba {
+        self.sample(*state)
+    }
+}
@@ -0,0 +1,378 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    neighborhood::hexagonal::HexagonalNeighborhoodBuilder,
+    types::BoundaryHandlingLattice,
+};
+
+use super::palette::{Palette, Rgba, TRANSPARENT};
+
+/// Vector counterpart of [`super::Renderer`]. Cells of the same color are
+/// merged into a single path per color, square cells into maximal runs that
+/// are stacked into rectangles, hexagonal cells into horizontal runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgRenderer {
+    cell_size: f64,
+    grid_lines: Option<(Rgba, f64)>,
+    background: Rgba,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self {
+            cell_size: 10.0,
+            grid_lines: None,
+            background: TRANSPARENT,
+        }
+    }
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Side of a square cell, or the distance between the centers of
+    /// neighboring hexagonal cells.
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Lines between and around square cells; hexagonal output has none.
+    pub fn with_grid_lines(mut self, color: Rgba, width: f64) -> Self {
+        self.grid_lines = (width > 0.0).then_some((color, width));
+        self
+    }
+
+    pub fn with_background(mut self, background: Rgba) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    pub fn render<L>(&self, lattice: &L, palette: &impl Palette<L::State>) -> String
+    where
+        L: BoundaryHandlingLattice<
+            Point = UniversalLatticePoint<2>,
+            Size = UniversalLatticeSize<2>,
+        >,
+    {
+        self.render_region(
+            lattice,
+            &UniversalLatticePoint::new([0, 0]),
+            &lattice.size(),
+            palette,
+        )
+    }
+
+    /// Only the `size` cells starting at `corner`, see
+    /// [`super::Renderer::render_region`].
+    pub fn render_region<L>(
+        &self,
+        lattice: &L,
+        corner: &UniversalLatticePoint<2>,
+        size: &UniversalLatticeSize<2>,
+        palette: &impl Palette<L::State>,
+    ) -> String
+    where
+        L: BoundaryHandlingLattice<
+            Point = UniversalLatticePoint<2>,
+            Size = UniversalLatticeSize<2>,
+        >,
+    {
+        let [left, top] = corner.values();
+
+        self.render_squares(size[0], size[1], |x, y| {
+            let point = UniversalLatticePoint::new([left + x as i128, top + y as i128]);
+            palette.color(&lattice.get_state(&point))
+        })
+    }
+
+    /// Generation `t` becomes row `t`, as in
+    /// [`super::Renderer::render_space_time`].
+    pub fn render_space_time<L>(
+        &self,
+        generations: &[L],
+        palette: &impl Palette<L::State>,
+    ) -> String
+    where
+        L: BoundaryHandlingLattice<
+            Point = UniversalLatticePoint<1>,
+            Size = UniversalLatticeSize<1>,
+        >,
+    {
+        let columns = generations.iter().map(|lattice| lattice.size()[0]).max();
+
+        self.render_squares(columns.unwrap_or(0), generations.len(), |x, t| {
+            let lattice = &generations[t];
+
+            if x < lattice.size()[0] {
+                palette.color(&lattice.get_state(&UniversalLatticePoint::new([x as i128])))
+            } else {
+                TRANSPARENT
+            }
+        })
+    }
+
+    /// Pointy-top hexagons laid out like [`HexagonalNeighborhoodBuilder`],
+    /// odd rows shifted half a cell to the right.
+    pub fn render_hexagonal<L>(&self, lattice: &L, palette: &impl Palette<L::State>) -> String
+    where
+        L: BoundaryHandlingLattice<
+            Point = UniversalLatticePoint<2>,
+            Size = UniversalLatticeSize<2>,
+        >,
+    {
+        let [columns, rows] = lattice.size().values();
+        let half_width = 0.5 * self.cell_size;
+        let radius = self.cell_size / 3f64.sqrt();
+
+        let width = if rows > 1 {
+            (columns as f64 + 0.5) * self.cell_size
+        } else {
+            columns as f64 * self.cell_size
+        };
+        let height = if rows > 0 {
+            (rows - 1) as f64 * 1.5 * radius + 2.0 * radius
+        } else {
+            0.0
+        };
+
+        let mut paths = Paths::default();
+        for_each_run(
+            columns,
+            rows,
+            |x, y| {
+                palette
+                    .color(&lattice.get_state(&UniversalLatticePoint::new([x as i128, y as i128])))
+            },
+            |start, end, y, color| {
+                let data = paths.data(color);
+                let center = |x: usize| {
+                    let (cx, cy) =
+                        HexagonalNeighborhoodBuilder::center(&UniversalLatticePoint::new([
+                            x as i128, y as i128,
+                        ]));
+                    ((cx + 0.5) * self.cell_size, cy * self.cell_size + radius)
+                };
+
+                let (first_x, cy) = center(start);
+                let _ = write!(
+                    data,
+                    "M{} {}V{}",
+                    number(first_x - half_width),
+                    number(cy + 0.5 * radius),
+                    number(cy - 0.5 * radius),
+                );
+                for x in start..end {
+                    let (cx, _) = center(x);
+                    let _ = write!(
+                        data,
+                        "L{} {}L{} {}",
+                        number(cx),
+                        number(cy - radius),
+                        number(cx + half_width),
+                        number(cy - 0.5 * radius),
+                    );
+                }
+                let _ = write!(data, "V{}", number(cy + 0.5 * radius));
+                for x in (start..end).rev() {
+                    let (cx, _) = center(x);
+                    let _ = write!(
+                        data,
+                        "L{} {}L{} {}",
+                        number(cx),
+                        number(cy + radius),
+                        number(cx - half_width),
+                        number(cy + 0.5 * radius),
+                    );
+                }
+                data.push('Z');
+            },
+        );
+
+        self.document(width, height, 0.0, |svg| paths.write(svg))
+    }
+
+    fn render_squares(
+        &self,
+        columns: usize,
+        rows: usize,
+        color: impl Fn(usize, usize) -> Rgba,
+    ) -> String {
+        let size = self.cell_size;
+        let mut paths = Paths::default();
+
+        // Runs of the previous row that may still grow downwards, keyed by
+        // their extent and color, with the row they started in.
+        let mut open: BTreeMap<(usize, usize, Rgba), usize> = BTreeMap::new();
+        let close = |paths: &mut Paths, (start, end, color): (usize, usize, Rgba), top, bottom| {
+            let _ = write!(
+                paths.data(color),
+                "M{} {}h{}v{}h-{}Z",
+                number(start as f64 * size),
+                number(top as f64 * size),
+                number((end - start) as f64 * size),
+                number((bottom - top) as f64 * size),
+                number((end - start) as f64 * size),
+            );
+        };
+
+        for y in 0..rows {
+            let mut current = BTreeMap::new();
+            for_each_run(
+                columns,
+                1,
+                |x, _| color(x, y),
+                |start, end, _, color| {
+                    let top = open.remove(&(start, end, color)).unwrap_or(y);
+                    current.insert((start, end, color), top);
+                },
+            );
+
+            for (run, top) in std::mem::replace(&mut open, current) {
+                close(&mut paths, run, top, y);
+            }
+        }
+        for (run, top) in open {
+            close(&mut paths, run, top, rows);
+        }
+
+        let (width, height) = (columns as f64 * size, rows as f64 * size);
+        let padding = self
+            .grid_lines
+            .map_or(0.0, |(_, line_width)| line_width / 2.0);
+
+        self.document(width, height, padding, |svg| {
+            paths.write(svg);
+
+            if let Some((color, line_width)) = self.grid_lines {
+                let mut data = String::new();
+                for x in 0..=columns {
+                    let _ = write!(data, "M{} 0V{}", number(x as f64 * size), number(height));
+                }
+                for y in 0..=rows {
+                    let _ = write!(data, "M0 {}H{}", number(y as f64 * size), number(width));
+                }
+
+                let _ = writeln!(
+                    svg,
+                    r#"<path fill="none" stroke="{}"{} stroke-width="{}" d="{data}"/>"#,
+                    hex(color),
+                    opacity("stroke-opacity", color),
+                    number(line_width),
+                );
+            }
+        })
+    }
+
+    fn document(
+        &self,
+        width: f64,
+        height: f64,
+        padding: f64,
+        content: impl FnOnce(&mut String),
+    ) -> String {
+        let (outer_width, outer_height) = (width + 2.0 * padding, height + 2.0 * padding);
+        let mut svg = String::new();
+
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            number(outer_width),
+            number(outer_height),
+            number(-padding),
+            number(-padding),
+            number(outer_width),
+            number(outer_height),
+        );
+        if self.background[3] > 0 {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"{}/>"#,
+                number(-padding),
+                number(-padding),
+                number(outer_width),
+                number(outer_height),
+                hex(self.background),
+                opacity("fill-opacity", self.background),
+            );
+        }
+        content(&mut svg);
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+/// Path data per color, transparent cells dropped.
+#[derive(Default)]
+struct Paths(BTreeMap<Rgba, String>);
+
+impl Paths {
+    fn data(&mut self, color: Rgba) -> &mut String {
+        self.0.entry(color).or_default()
+    }
+
+    fn write(&self, svg: &mut String) {
+        for (color, data) in self.0.iter().filter(|(color, _)| color[3] > 0) {
+            let _ = writeln!(
+                svg,
+                r#"<path fill="{}"{} d="{data}"/>"#,
+                hex(*color),
+                opacity("fill-opacity", *color),
+            );
+        }
+    }
+}
+
+/// Calls `run` with `(start, end, row, color)` for every maximal horizontal
+/// run of equally colored cells.
+fn for_each_run(
+    columns: usize,
+    rows: usize,
+    color: impl Fn(usize, usize) -> Rgba,
+    mut run: impl FnMut(usize, usize, usize, Rgba),
+) {
+    for y in 0..rows {
+        let mut start = 0;
+
+        while start < columns {
+            let run_color = color(start, y);
+            let end = (start + 1..columns)
+                .find(|x| color(*x, y) != run_color)
+                .unwrap_or(columns);
+
+            run(start, end, y, run_color);
+            start = end;
+        }
+    }
+}
+
+fn hex(color: Rgba) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn opacity(attribute: &str, color: Rgba) -> String {
+    if color[3] == u8::MAX {
+        String::new()
+    } else {
+        format!(r#" {attribute}="{}""#, number(color[3] as f64 / 255.0))
+    }
+}
+
+/// At most three decimals, without trailing zeros.
+fn number(value: f64) -> String {
+    let formatted = format!("{value:.3}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" | "" => "0".to_string(),
+        other => other.to_string(),
+    }
+}
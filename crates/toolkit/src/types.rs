@@ -1,9 +1,20 @@
 // use std::ops::{Index, IndexMut};
+use std::cell::Cell;
+
+use crate::{
+    aliases::UniversalLatticePoint,
+    automaton::{
+        context::{CellContext, StepContext, StepRng},
+        pipeline::synchronous_update,
+        update_scheme::UpdateScheme,
+    },
+};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum BoundaryHandling {
     #[default]
@@ -55,6 +66,9 @@ where
 {
     type Size: Copy;
 
+    /// Number of axes of every point and size.
+    const DIMENSION: usize;
+
     fn from_states(states: Vec<Self::State>, size: Self::Size) -> Self;
     fn transform_point(&self, point: &Self::Point) -> Self::Point;
     fn set_boundary_handling(&mut self, boundary_handling: BoundaryHandling);
@@ -78,6 +92,11 @@ pub trait NeighborhoodBuilder<L: Lattice> {
     fn build_neighborhood(&self, point: &L::Point, lattice: &L) -> Self::Neighborhood;
 }
 
+/// Builders whose neighborhood is the same set of offsets around every cell.
+pub trait OffsetNeighborhoodBuilder<const D: usize> {
+    fn neighbor_offsets(&self) -> Vec<UniversalLatticePoint<D>>;
+}
+
 pub trait Rule {
     type State;
 
@@ -86,6 +105,51 @@ pub trait Rule {
         current_state: &Self::State,
         neighbors: &impl Neighborhood<State = Self::State>,
     ) -> Self::State;
+
+    fn apply_in_context(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+        _context: &CellContext,
+    ) -> Self::State {
+        self.apply(current_state, neighbors)
+    }
+}
+
+/// Rule that draws from a random stream. Its [`Rule::apply`] has no cell
+/// or generation to derive the stream from and always draws from the default
+/// [`CellContext`], so step it through an automaton that keeps a
+/// [`CellularAutomaton::step_context`].
+pub trait StochasticRule {
+    type State;
+
+    fn apply_with_rng(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+        rng: &mut StepRng,
+    ) -> Self::State;
+}
+
+impl<R: StochasticRule> Rule for R {
+    type State = R::State;
+
+    fn apply(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+    ) -> Self::State {
+        self.apply_in_context(current_state, neighbors, &CellContext::default())
+    }
+
+    fn apply_in_context(
+        &self,
+        current_state: &Self::State,
+        neighbors: &impl Neighborhood<State = Self::State>,
+        context: &CellContext,
+    ) -> Self::State {
+        self.apply_with_rng(current_state, neighbors, &mut context.rng())
+    }
 }
 
 pub trait CellularAutomaton {
@@ -99,24 +163,71 @@ pub trait CellularAutomaton {
     fn rule(&self) -> &Self::Rule;
     fn neighborhood_builder(&self) -> Self::NeighborhoodBuilder;
 
+    fn update_scheme(&self) -> UpdateScheme {
+        UpdateScheme::default()
+    }
+
+    /// Context that [`CellularAutomaton::step`] advances across calls.
+    /// Without one every step runs as generation 0 of the default seed, which
+    /// replays the same randomness each time.
+    fn step_context(&self) -> Option<&Cell<StepContext>> {
+        None
+    }
+
     fn step(&self, lattice: &mut Self::Lattice) {
-        let points = lattice.points();
+        match self.step_context() {
+            Some(cell) => {
+                let mut context = cell.get();
+                self.step_with_context(lattice, &mut context);
+                cell.set(context);
+            }
+            None => self.step_with_context(lattice, &mut StepContext::default()),
+        }
+    }
 
-        let mut new_states = Vec::with_capacity(points.len());
+    fn step_with_context(&self, lattice: &mut Self::Lattice, context: &mut StepContext) {
+        let scheme = self.update_scheme();
         let rule = self.rule();
         let builder = self.neighborhood_builder();
 
-        // TODO: iterate over all points based on size
-        for point in &points {
-            let neighborhood = builder.build_neighborhood(&point, lattice);
-            let current_state = lattice.get_state(&point);
+        if scheme.is_sequential() {
+            let points = lattice.points().into_iter().enumerate().collect();
 
-            let new_state = rule.apply(&current_state, &neighborhood);
-            new_states.push(new_state);
-        }
+            for (index, point) in scheme.order(points, context) {
+                let neighborhood = builder.build_neighborhood(&point, lattice);
+                let current_state = lattice.get_state(&point);
+
+                let new_state =
+                    rule.apply_in_context(&current_state, &neighborhood, &context.cell(index));
+                lattice.set_state(&point, &new_state);
+            }
 
-        for (point, new_state) in points.into_iter().zip(new_states) {
-            lattice.set_state(&point, &new_state);
+            context.advance();
+            return;
         }
+
+        let points = lattice.points();
+        let activations = scheme.activations(points.len(), context);
+
+        synchronous_update(rule, &builder, lattice, points, &activations, context);
+
+        context.advance();
+    }
+
+    /// Steps `generations` times with `context`, recording `observe` of the
+    /// lattice after every step.
+    fn run<T>(
+        &self,
+        lattice: &mut Self::Lattice,
+        context: &mut StepContext,
+        generations: usize,
+        mut observe: impl FnMut(&Self::Lattice) -> T,
+    ) -> Vec<T> {
+        (0..generations)
+            .map(|_| {
+                self.step_with_context(lattice, context);
+                observe(lattice)
+            })
+            .collect()
     }
 }
@@ -4,6 +4,7 @@ use wasm_bindgen::prelude::wasm_bindgen;
 use crate::{
     aliases::{UniversalLatticePoint, UniversalLatticeSize},
     lattice::universal_point::UniversalPoint,
+    types::Neighborhood,
 };
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -26,6 +27,33 @@ pub fn clamp_coordinate(coord: i128, size: usize) -> i128 {
     coord.clamp(0, max)
 }
 
+/// `probability` clamped to `0..=1`. NaN counts as `0`.
+pub fn clamp_probability(probability: f64) -> f64 {
+    if probability.is_nan() {
+        0.0
+    } else {
+        probability.clamp(0.0, 1.0)
+    }
+}
+
+/// `probability` unchanged if it lies in `0..=1`, otherwise an error naming
+/// the `field` it was read from.
+pub fn check_probability(field: &str, probability: f64) -> Result<f64, String> {
+    if (0.0..=1.0).contains(&probability) {
+        Ok(probability)
+    } else {
+        Err(format!("`{field}` must lie in 0..=1, found {probability}"))
+    }
+}
+
+/// Number of neighbors in `state`.
+pub fn count_state<S: PartialEq>(neighborhood: &impl Neighborhood<State = S>, state: &S) -> usize {
+    neighborhood
+        .iter_states()
+        .filter(|neighbor| *neighbor == state)
+        .count()
+}
+
 pub fn point_from_flat_index<const D: usize>(
     index: usize,
     size: UniversalLatticeSize<D>,
@@ -45,6 +73,26 @@ pub fn point_from_flat_index<const D: usize>(
     UniversalPoint::from_iter(coords.map(|coord| coord as i128))
 }
 
+/// Inverse of [`point_from_flat_index`] for points inside `size`.
+pub fn flat_index_from_point<const D: usize>(
+    point: &UniversalLatticePoint<D>,
+    size: UniversalLatticeSize<D>,
+) -> usize {
+    (0..D)
+        .rev()
+        .fold(0, |index, axis| index * size[axis] + point[axis] as usize)
+}
+
+/// `point` moved by `offset`, without any boundary handling.
+pub fn translate<const D: usize>(
+    point: &UniversalLatticePoint<D>,
+    offset: &UniversalLatticePoint<D>,
+) -> UniversalLatticePoint<D> {
+    let mut translated = *point;
+    (0..D).for_each(|axis| translated[axis] += offset[axis]);
+    translated
+}
+
 pub fn generate_points_until<const D: usize>(
     size: UniversalLatticeSize<D>,
 ) -> Vec<UniversalLatticePoint<D>> {
@@ -0,0 +1,63 @@
+#![cfg(feature = "render")]
+
+use std::time::Duration;
+
+use toolkit::render::{
+    animation::{Animation, AnimationError, LoopCount},
+    image::ImageBuffer,
+    palette::{BLACK, WHITE},
+};
+
+fn animation(frame_delay: Duration) -> Animation {
+    let mut animation = Animation::new(frame_delay, LoopCount::Finite(3));
+    animation.push_frame(ImageBuffer::new(2, 2, BLACK));
+    animation.push_frame(ImageBuffer::new(2, 2, WHITE));
+    animation
+}
+
+/// Frame delays of an encoded GIF in centiseconds.
+fn gif_delays(animation: &Animation) -> Vec<u16> {
+    let mut bytes = Vec::new();
+    animation.write_gif(&mut bytes).unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    delays
+}
+
+#[test]
+fn gif_delays_are_whole_centiseconds() {
+    assert_eq!(gif_delays(&animation(Duration::from_millis(250))), [25, 25]);
+    assert_eq!(
+        gif_delays(&animation(Duration::from_secs(1000))),
+        [u16::MAX; 2]
+    );
+}
+
+#[test]
+fn gif_delays_are_never_zero() {
+    for frame_delay in [Duration::ZERO, Duration::from_millis(9)] {
+        assert_eq!(gif_delays(&animation(frame_delay)), [1, 1]);
+    }
+}
+
+#[test]
+fn frames_must_share_a_size() {
+    let mut animation = animation(Duration::from_millis(100));
+    animation.push_frame(ImageBuffer::new(3, 2, BLACK));
+
+    assert!(matches!(
+        animation.write_gif(Vec::new()),
+        Err(AnimationError::FrameSizeMismatch)
+    ));
+    assert!(matches!(
+        Animation::new(Duration::ZERO, LoopCount::Infinite).write_apng(Vec::new()),
+        Err(AnimationError::NoFrames)
+    ));
+}
@@ -0,0 +1,77 @@
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    lattice::universal_lattice::UniversalLattice,
+    noise::{
+        fill_quantized,
+        fractal::Fractal,
+        open_simplex::OpenSimplexNoise,
+        perlin::PerlinNoise,
+        worley::{WorleyFeature, WorleyNoise},
+        Noise,
+    },
+    types::Lattice,
+};
+
+fn positions() -> impl Iterator<Item = [f64; 2]> {
+    (0..40).flat_map(|y| (0..40).map(move |x| [x as f64 * 0.173 - 3.0, y as f64 * 0.131 - 2.0]))
+}
+
+fn assert_bounded_and_continuous(noise: &impl Noise<2>) {
+    for position in positions() {
+        let value = noise.sample(position);
+        let nearby = noise.sample([position[0] + 1e-4, position[1] - 1e-4]);
+
+        assert!((-1.1..=1.1).contains(&value), "{value} at {position:?}");
+        assert!((value - nearby).abs() < 1e-2, "jump at {position:?}");
+    }
+}
+
+#[test]
+fn gradient_noises_are_bounded_and_continuous() {
+    assert_bounded_and_continuous(&PerlinNoise::new(1));
+    assert_bounded_and_continuous(&OpenSimplexNoise::new(2));
+    assert_bounded_and_continuous(&Fractal::new(PerlinNoise::new(3)).with_octaves(4));
+    assert_bounded_and_continuous(&WorleyNoise::new(4));
+}
+
+#[test]
+fn perlin_vanishes_on_integer_points() {
+    let noise = PerlinNoise::new(7);
+
+    for x in -3..3 {
+        for y in -3..3 {
+            assert!(Noise::<2>::sample(&noise, [x as f64, y as f64]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn noise_is_deterministic_per_seed() {
+    let sample = |seed| {
+        positions()
+            .map(|position| Noise::<2>::sample(&OpenSimplexNoise::new(seed), position))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(sample(5), sample(5));
+    assert_ne!(sample(5), sample(6));
+}
+
+#[test]
+fn worley_second_feature_is_farther() {
+    let first = WorleyNoise::new(9);
+    let second = WorleyNoise::new(9).with_feature(WorleyFeature::F2);
+
+    for position in positions() {
+        assert!(Noise::<2>::sample(&second, position) >= Noise::<2>::sample(&first, position));
+    }
+}
+
+#[test]
+fn quantized_fill_uses_every_level() {
+    let mut lattice = UniversalLattice::<2, u8>::from(UniversalLatticeSize::new([64, 64]));
+    fill_quantized(&mut lattice, &PerlinNoise::new(11), 0.1, &[0, 1, 2]);
+
+    let states = lattice.states();
+    assert!((0..3).all(|level| states.contains(&level)));
+}
@@ -0,0 +1,168 @@
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::{
+        npy::{
+            read_npy, read_npy_history, write_npy, write_npy_history, NpyError, MAX_HEADER_LENGTH,
+        },
+        universal_lattice::UniversalLattice,
+    },
+    types::{BoundaryHandlingLattice, Lattice},
+};
+
+/// 3 wide and 2 high, each cell holding `10 * y + x`.
+fn lattice(offset: u8) -> UniversalLattice<2, u8> {
+    let states = (0..2)
+        .flat_map(|y| (0..3).map(move |x| offset + 10 * y + x))
+        .collect();
+    UniversalLattice::from_states(states, UniversalLatticeSize::new([3, 2]))
+}
+
+fn header(bytes: &[u8]) -> &str {
+    let length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    std::str::from_utf8(&bytes[10..10 + length]).unwrap()
+}
+
+fn data(bytes: &[u8]) -> &[u8] {
+    let length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    &bytes[10 + length..]
+}
+
+/// Version 1.0 `.npy` bytes with the given header and data.
+fn npy(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn lattice_round_trips_as_rows() {
+    let lattice = lattice(0);
+    let mut bytes = Vec::new();
+    write_npy(&lattice, &mut bytes, |state| *state).unwrap();
+
+    assert!(header(&bytes).contains("'shape': (2, 3)"));
+    assert_eq!(data(&bytes), [0, 1, 2, 10, 11, 12]);
+
+    let read: UniversalLattice<2, u8> = read_npy(bytes.as_slice(), Some).unwrap();
+    assert_eq!(read.size(), lattice.size());
+    assert_eq!(read.states(), lattice.states());
+    assert_eq!(read.get_state(&UniversalLatticePoint::new([2, 1])), 12);
+}
+
+#[test]
+fn history_round_trips_with_time_first() {
+    let history = vec![lattice(0), lattice(100)];
+    let mut bytes = Vec::new();
+    write_npy_history(&history, &mut bytes, |state| *state).unwrap();
+
+    assert!(header(&bytes).contains("'shape': (2, 2, 3)"));
+    assert_eq!(
+        data(&bytes),
+        [0, 1, 2, 10, 11, 12, 100, 101, 102, 110, 111, 112]
+    );
+
+    let read: Vec<UniversalLattice<2, u8>> = read_npy_history(bytes.as_slice(), Some).unwrap();
+    assert_eq!(read.len(), 2);
+    for (read, written) in read.iter().zip(&history) {
+        assert_eq!(read.size(), written.size());
+        assert_eq!(read.states(), written.states());
+    }
+}
+
+#[test]
+fn mismatched_histories_are_rejected() {
+    let mut bytes = Vec::new();
+    assert_eq!(
+        write_npy_history::<UniversalLattice<2, u8>, u8>(&[], &mut bytes, |state| *state),
+        Err(NpyError::EmptyHistory)
+    );
+
+    let other = UniversalLattice::from(UniversalLatticeSize::new([2, 3]));
+    assert_eq!(
+        write_npy_history(&[lattice(0), other], &mut bytes, |state| *state),
+        Err(NpyError::ShapeMismatch {
+            expected: vec![2, 3],
+            found: vec![3, 2],
+        })
+    );
+}
+
+#[test]
+fn wrong_axis_counts_are_rejected() {
+    let mut bytes = Vec::new();
+    write_npy(&lattice(0), &mut bytes, |state| *state).unwrap();
+
+    assert_eq!(
+        read_npy::<UniversalLattice<3, u8>, u8>(bytes.as_slice(), Some).unwrap_err(),
+        NpyError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        }
+    );
+    assert_eq!(
+        read_npy_history::<UniversalLattice<2, u8>, u8>(bytes.as_slice(), Some).unwrap_err(),
+        NpyError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        }
+    );
+}
+
+#[test]
+fn malformed_arrays_are_rejected() {
+    let header = "{'descr': '|u1', 'fortran_order': False, 'shape': (2, 3), }";
+    assert_eq!(
+        read_npy::<UniversalLattice<2, u8>, u8>(npy(header, &[0; 5]).as_slice(), Some).unwrap_err(),
+        NpyError::Truncated
+    );
+    assert_eq!(
+        read_npy::<UniversalLattice<2, u16>, u16>(npy(header, &[0; 6]).as_slice(), Some)
+            .unwrap_err(),
+        NpyError::DtypeMismatch {
+            expected: "<u2".to_string(),
+            found: "|u1".to_string(),
+        }
+    );
+    assert_eq!(
+        read_npy::<UniversalLattice<2, u8>, u8>(
+            npy(header, &[0, 0, 9, 0, 0, 0]).as_slice(),
+            |value| (value < 2).then_some(value)
+        )
+        .unwrap_err(),
+        NpyError::InvalidValue(2)
+    );
+}
+
+#[test]
+fn empty_history_with_overflowing_extents_is_rejected() {
+    let extent = 1usize << (usize::BITS / 2);
+    let header =
+        format!("{{'descr': '|u1', 'fortran_order': False, 'shape': (0, {extent}, {extent}), }}");
+
+    assert!(matches!(
+        read_npy_history::<UniversalLattice<2, u8>, u8>(npy(&header, &[]).as_slice(), Some),
+        Err(NpyError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn oversized_headers_are_rejected() {
+    let mut bytes = b"\x93NUMPY\x02\x00".to_vec();
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+
+    assert!(matches!(
+        read_npy::<UniversalLattice<2, u8>, u8>(bytes.as_slice(), Some),
+        Err(NpyError::InvalidHeader(_))
+    ));
+
+    let header = format!(
+        "{{'descr': '|u1', 'fortran_order': False, 'shape': (2, 3), }}{}",
+        " ".repeat(MAX_HEADER_LENGTH)
+    );
+    assert!(matches!(
+        read_npy::<UniversalLattice<2, u8>, u8>(npy(&header, &[0; 6]).as_slice(), Some),
+        Err(NpyError::InvalidHeader(_))
+    ));
+}
@@ -0,0 +1,113 @@
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    analysis::regions::{flood_fill, label_regions, Connectivity},
+    lattice::universal_lattice::UniversalLattice,
+    types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
+};
+
+fn lattice(rows: &[&str]) -> UniversalLattice<2, bool> {
+    UniversalLattice::from_states(
+        rows.iter()
+            .flat_map(|row| row.chars().map(|cell| cell == '#'))
+            .collect(),
+        UniversalLatticeSize::new([rows[0].len(), rows.len()]),
+    )
+}
+
+#[test]
+fn connectivity_decides_diagonal_regions() {
+    let lattice = lattice(&["#...", ".#..", "..#.", "..#."]);
+
+    let von_neumann = label_regions(&lattice, Connectivity::VonNeumann, |cell| *cell);
+    let moore = label_regions(&lattice, Connectivity::Moore, |cell| *cell);
+
+    assert_eq!(von_neumann.len(), 3);
+    assert_eq!(moore.len(), 1);
+    assert_eq!(moore.regions()[0].area(), 4);
+    assert_eq!(
+        von_neumann.label(&UniversalLatticePoint::new([2, 3])),
+        von_neumann.label(&UniversalLatticePoint::new([2, 2]))
+    );
+}
+
+#[test]
+fn flood_fill_stays_in_one_state() {
+    let lattice = lattice(&["##..", ".#..", "..##"]);
+
+    let mut filled = flood_fill(
+        &lattice,
+        &UniversalLatticePoint::new([0, 0]),
+        Connectivity::VonNeumann,
+    );
+    filled.sort();
+
+    assert_eq!(
+        filled,
+        [[0, 0], [1, 0], [1, 1]].map(UniversalLatticePoint::new)
+    );
+    assert!(flood_fill(
+        &lattice,
+        &UniversalLatticePoint::new([9, 0]),
+        Connectivity::Moore
+    )
+    .is_empty());
+}
+
+#[test]
+fn regions_describe_their_shape() {
+    // An L of four cells and a lone cell in the corner.
+    let lattice = lattice(&[".....", ".#...", ".###.", "....#"]);
+    let labels = label_regions(&lattice, Connectivity::VonNeumann, |cell| *cell);
+    let regions = labels.regions();
+
+    assert_eq!(regions.len(), 2);
+
+    let l = &regions[0];
+    assert_eq!(l.label, 0);
+    assert_eq!(l.area(), 4);
+    assert_eq!(l.origin, UniversalLatticePoint::new([1, 1]));
+    assert_eq!(l.extent, UniversalLatticeSize::new([3, 2]));
+    assert_eq!(l.centroid, [1.75, 1.75]);
+    assert_eq!(l.perimeter, 10);
+
+    // Faces on the lattice edge count towards the perimeter.
+    let corner = &regions[1];
+    assert_eq!(corner.origin, UniversalLatticePoint::new([4, 3]));
+    assert_eq!(corner.extent, UniversalLatticeSize::new([1, 1]));
+    assert_eq!(corner.centroid, [4.0, 3.0]);
+    assert_eq!(corner.perimeter, 4);
+
+    assert_eq!(labels.label(&UniversalLatticePoint::new([3, 2])), Some(0));
+    assert_eq!(labels.label(&UniversalLatticePoint::new([4, 3])), Some(1));
+    assert_eq!(labels.label(&UniversalLatticePoint::new([0, 0])), None);
+}
+
+#[test]
+fn wrapped_regions_cross_the_edge() {
+    let mut lattice = lattice(&["#..#", "....", "....", "#..#"]);
+    assert_eq!(
+        label_regions(&lattice, Connectivity::VonNeumann, |cell| *cell).len(),
+        4
+    );
+
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    let labels = label_regions(&lattice, Connectivity::VonNeumann, |cell| *cell);
+    let region = &labels.regions()[0];
+
+    assert_eq!(labels.len(), 1);
+    assert_eq!(region.origin, UniversalLatticePoint::new([3, 3]));
+    assert_eq!(region.extent, UniversalLatticeSize::new([2, 2]));
+    assert_eq!(region.centroid, [3.5, 3.5]);
+    assert_eq!(region.perimeter, 8);
+}
+
+#[test]
+fn summaries_label_every_point() {
+    let lattice = lattice(&["#.", ".#"]);
+    let labels = label_regions(&lattice, Connectivity::VonNeumann, |cell| *cell);
+    let summary = labels.summarize(&lattice.points());
+
+    assert_eq!(summary.labels, [0, -1, -1, 1]);
+    assert_eq!(summary.regions[1].origin, [1, 1]);
+    assert_eq!(summary.regions[1].area, 1);
+}
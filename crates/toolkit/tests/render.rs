@@ -0,0 +1,139 @@
+#![cfg(feature = "render")]
+
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    render::{
+        image::ImageBuffer,
+        palette::{GradientPalette, MapPalette, Palette, BLACK, TRANSPARENT, WHITE},
+        Renderer,
+    },
+    types::{BoundaryHandling, BoundaryHandlingLattice},
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+fn palette() -> MapPalette<bool> {
+    MapPalette::new(TRANSPARENT)
+        .with_color(false, WHITE)
+        .with_color(true, BLACK)
+}
+
+fn lattice(rows: &[&str]) -> UniversalLattice<2, bool> {
+    let states = rows
+        .iter()
+        .flat_map(|row| row.chars().map(|cell| cell == '#'))
+        .collect();
+    UniversalLattice::from_states(
+        states,
+        UniversalLatticeSize::new([rows[0].len(), rows.len()]),
+    )
+}
+
+#[test]
+fn cells_are_scaled_and_framed_by_grid_lines() {
+    let renderer = Renderer::new().with_scale(2).with_grid_lines(RED, 1);
+    let image = renderer.render(&lattice(&["#..", "..#"]), &palette());
+
+    assert_eq!((image.width(), image.height()), renderer.image_size(3, 2));
+    assert_eq!((image.width(), image.height()), (10, 7));
+    assert_eq!(renderer.cell_origin(2, 1), (7, 4));
+
+    // Grid lines on the frame and between cells.
+    assert_eq!(image.pixel(0, 0), RED);
+    assert_eq!(image.pixel(3, 1), RED);
+    assert_eq!(image.pixel(9, 6), RED);
+
+    for (x, y) in [(1, 1), (2, 2)] {
+        assert_eq!(image.pixel(x, y), BLACK);
+    }
+    assert_eq!(image.pixel(4, 1), WHITE);
+    assert_eq!(image.pixel(8, 5), BLACK);
+    assert_eq!(image.pixel(10, 0), TRANSPARENT);
+}
+
+#[test]
+fn regions_follow_boundary_handling() {
+    let mut lattice = lattice(&["#..", "..."]);
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+
+    let renderer = Renderer::new();
+    let image = renderer.render_region(
+        &lattice,
+        &UniversalLatticePoint::new([2, 1]),
+        &UniversalLatticeSize::new([2, 2]),
+        &palette(),
+    );
+
+    assert_eq!((image.width(), image.height()), (2, 2));
+    assert_eq!(image.pixel(1, 1), BLACK);
+    assert_eq!(image.pixel(0, 0), WHITE);
+}
+
+#[test]
+fn space_time_rows_are_generations() {
+    let generations: Vec<UniversalLattice<1, bool>> = ["#.#", ".#"]
+        .iter()
+        .map(|cells| {
+            let states = cells.chars().map(|cell| cell == '#').collect();
+            UniversalLattice::from_states(states, UniversalLatticeSize::new([cells.len()]))
+        })
+        .collect();
+
+    let image = Renderer::new().render_space_time(&generations, &palette());
+
+    assert_eq!((image.width(), image.height()), (3, 2));
+    assert_eq!(
+        (0..3).map(|x| image.pixel(x, 0)).collect::<Vec<_>>(),
+        [BLACK, WHITE, BLACK]
+    );
+    assert_eq!(
+        (0..3).map(|x| image.pixel(x, 1)).collect::<Vec<_>>(),
+        [WHITE, BLACK, TRANSPARENT]
+    );
+}
+
+#[test]
+fn palettes_map_states_to_colors() {
+    let gradient = GradientPalette::new(BLACK, [200, 100, 0, 255], 0.0, 2.0);
+
+    assert_eq!(gradient.color(&1.0f64), [100, 50, 0, 255]);
+    assert_eq!(gradient.color(&-1.0f32), BLACK);
+    assert_eq!(gradient.color(&5.0f64), [200, 100, 0, 255]);
+
+    let map: MapPalette<u8> = [(1, RED)].into_iter().collect();
+    assert_eq!(map.color(&1), RED);
+    assert_eq!(map.color(&2), TRANSPARENT);
+    assert_eq!((|state: &u8| [*state; 4]).color(&3), [3; 4]);
+}
+
+#[test]
+fn png_round_trip() {
+    let mut image = ImageBuffer::new(3, 2, TRANSPARENT);
+    image.fill_rect(1, 0, 5, 1, RED);
+    image.set_pixel(0, 1, [1, 2, 3, 4]);
+
+    let mut bytes = Vec::new();
+    image.write_png(&mut bytes).unwrap();
+
+    let mut reader = png::Decoder::new(std::io::Cursor::new(bytes))
+        .read_info()
+        .unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+
+    assert_eq!((info.width, info.height), (3, 2));
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(&pixels[..info.buffer_size()], image.pixels());
+}
+
+#[test]
+fn ppm_drops_alpha() {
+    let mut image = ImageBuffer::new(2, 1, RED);
+    image.set_pixel(1, 0, [1, 2, 3, 0]);
+
+    let mut bytes = Vec::new();
+    image.write_ppm(&mut bytes).unwrap();
+
+    assert_eq!(bytes, b"P6\n2 1\n255\n\xff\x00\x00\x01\x02\x03");
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "serde")]
+
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::{UniversalLattice, MAX_DENSE_CELLS},
+    types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
+};
+
+fn round_trip(lattice: &UniversalLattice<2, u8>) -> (serde_json::Value, UniversalLattice<2, u8>) {
+    let json = serde_json::to_value(lattice).unwrap();
+    let decoded = serde_json::from_value(json.clone()).unwrap();
+    (json, decoded)
+}
+
+fn assert_same(left: &UniversalLattice<2, u8>, right: &UniversalLattice<2, u8>) {
+    assert_eq!(left.size(), right.size());
+    assert_eq!(left.boundary_handling(), right.boundary_handling());
+    assert_eq!(left.states(), right.states());
+}
+
+#[test]
+fn scattered_cells_round_trip_sparse() {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([100, 80]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    lattice.set_state(&UniversalLatticePoint::new([3, 4]), &2);
+    lattice.set_state(&UniversalLatticePoint::new([99, 79]), &1);
+
+    let (json, decoded) = round_trip(&lattice);
+
+    assert_eq!(
+        json["cells"]["sparse"],
+        serde_json::json!([[[3, 4], 2], [[99, 79], 1]])
+    );
+    assert_same(&lattice, &decoded);
+}
+
+#[test]
+fn filled_lattices_round_trip_dense() {
+    let states = (0..12 * 9).map(|index| (index / 20 % 3) as u8).collect();
+    let lattice = UniversalLattice::from_states(states, UniversalLatticeSize::new([12, 9]));
+
+    let (json, decoded) = round_trip(&lattice);
+
+    assert_eq!(json["cells"]["dense"][0], serde_json::json!([20, 0]));
+    assert_eq!(json["cells"]["dense"][1], serde_json::json!([20, 1]));
+    assert_same(&lattice, &decoded);
+}
+
+#[test]
+fn dense_runs_must_cover_the_lattice() {
+    let lattice = |cells: serde_json::Value| {
+        serde_json::from_value::<UniversalLattice<2, u8>>(serde_json::json!({
+            "size": [2, 2],
+            "boundary_handling": "Default",
+            "cells": { "dense": cells },
+        }))
+    };
+
+    assert!(lattice(serde_json::json!([[1, 0], [3, 1]])).is_ok());
+    assert!(lattice(serde_json::json!([[1, 0], [2, 1]])).is_err());
+    assert!(lattice(serde_json::json!([[1, 0], [usize::MAX, 1]])).is_err());
+}
+
+#[test]
+fn oversized_dense_runs_are_rejected() {
+    let decode = |cells: serde_json::Value| {
+        serde_json::from_value::<UniversalLattice<2, u8>>(serde_json::json!({
+            "size": [1_000_000_000u64, 1_000_000_000u64],
+            "boundary_handling": "Default",
+            "cells": { "dense": cells },
+        }))
+    };
+
+    let error = decode(serde_json::json!([[1_000_000_000_000_000_000u64, 1]])).unwrap_err();
+    assert!(error.to_string().contains(&MAX_DENSE_CELLS.to_string()));
+
+    // Runs of the default state cost nothing to decode.
+    let empty = decode(serde_json::json!([[1_000_000_000_000_000_000u64, 0]])).unwrap();
+    assert!(empty.sparse_points().is_empty());
+}
+
+#[test]
+fn sparse_cells_must_lie_inside_the_lattice() {
+    let decode = |cells: serde_json::Value| {
+        serde_json::from_value::<UniversalLattice<2, u8>>(serde_json::json!({
+            "size": [4, 3],
+            "boundary_handling": "Default",
+            "cells": { "sparse": cells },
+        }))
+    };
+
+    assert!(decode(serde_json::json!([[[3, 2], 1]])).is_ok());
+    assert!(decode(serde_json::json!([[[4, 2], 1]])).is_err());
+    assert!(decode(serde_json::json!([[[0, -1], 1]])).is_err());
+}
@@ -0,0 +1,154 @@
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::{
+        snapshot::{Snapshot, SnapshotError, SnapshotInfo, SNAPSHOT_VERSION},
+        universal_lattice::UniversalLattice,
+    },
+    types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
+};
+
+fn lattice() -> UniversalLattice<2, u8> {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([5, 3]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    lattice.set_state(&UniversalLatticePoint::new([1, 0]), &7);
+    lattice.set_state(&UniversalLatticePoint::new([4, 2]), &255);
+    lattice
+}
+
+fn save<L: Snapshot>(lattice: &L) -> Vec<u8>
+where
+    L::Point: Clone,
+{
+    let mut bytes = Vec::new();
+    lattice
+        .save_snapshot(&mut bytes, &SnapshotInfo::new("B3/S23", 42))
+        .unwrap();
+    bytes
+}
+
+/// Replaces the trailing CRC-32 so that only the edited field is wrong.
+fn reseal(bytes: &mut Vec<u8>) {
+    bytes.truncate(bytes.len() - 4);
+    let checksum = crc32fast::hash(bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+}
+
+fn load<L: Snapshot>(bytes: &[u8]) -> Result<L, SnapshotError>
+where
+    L::Point: Clone,
+{
+    L::load_snapshot(bytes).map(|(lattice, _)| lattice)
+}
+
+#[test]
+fn round_trip_keeps_states_and_info() {
+    let lattice = lattice();
+    let (loaded, info) =
+        UniversalLattice::<2, u8>::load_snapshot(save(&lattice).as_slice()).unwrap();
+
+    assert_eq!(info, SnapshotInfo::new("B3/S23", 42));
+    assert_eq!(loaded.size(), lattice.size());
+    assert_eq!(loaded.boundary_handling(), BoundaryHandling::Wrap);
+    assert_eq!(loaded.states(), lattice.states());
+    assert_eq!(loaded.sparse_points().len(), 2);
+}
+
+#[test]
+fn dimensions_must_match() {
+    let bytes = save(&lattice());
+
+    assert_eq!(
+        load::<UniversalLattice<1, u8>>(&bytes).unwrap_err(),
+        SnapshotError::DimensionMismatch {
+            expected: 1,
+            found: 2
+        }
+    );
+    assert_eq!(
+        load::<UniversalLattice<3, u8>>(&bytes).unwrap_err(),
+        SnapshotError::DimensionMismatch {
+            expected: 3,
+            found: 2
+        }
+    );
+}
+
+#[test]
+fn state_widths_must_match() {
+    assert_eq!(
+        load::<UniversalLattice<2, u16>>(&save(&lattice())).unwrap_err(),
+        SnapshotError::StateWidthMismatch {
+            expected: 2,
+            found: 1
+        }
+    );
+}
+
+#[test]
+fn foreign_data_is_not_a_snapshot() {
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(b"P6\n1 1\n255\n\0\0\0").unwrap_err(),
+        SnapshotError::NotASnapshot
+    );
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(b"").unwrap_err(),
+        SnapshotError::NotASnapshot
+    );
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let mut bytes = save(&lattice());
+    bytes[4..6].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(&bytes).unwrap_err(),
+        SnapshotError::UnsupportedVersion(SNAPSHOT_VERSION + 1)
+    );
+}
+
+#[test]
+fn flipped_bits_fail_the_checksum() {
+    let mut bytes = save(&lattice());
+    let middle = bytes.len() / 2;
+    bytes[middle] ^= 0x10;
+
+    assert!(matches!(
+        load::<UniversalLattice<2, u8>>(&bytes).unwrap_err(),
+        SnapshotError::ChecksumMismatch { .. }
+    ));
+}
+
+#[test]
+fn inconsistent_contents_are_corrupted() {
+    // Truncated states.
+    let mut bytes = save(&lattice());
+    bytes.drain(bytes.len() - 10..bytes.len() - 4);
+    reseal(&mut bytes);
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(&bytes).unwrap_err(),
+        SnapshotError::Corrupted
+    );
+
+    // A header cut short.
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(&save(&lattice())[..6]).unwrap_err(),
+        SnapshotError::Corrupted
+    );
+
+    // A byte that is not a valid `bool`.
+    assert_eq!(
+        load::<UniversalLattice<2, bool>>(&save(&lattice())).unwrap_err(),
+        SnapshotError::Corrupted
+    );
+
+    // An unknown boundary handling; it follows the magic, version,
+    // dimension and two 8-byte extents.
+    let mut bytes = save(&lattice());
+    bytes[4 + 2 + 2 + 16] = 9;
+    reseal(&mut bytes);
+    assert_eq!(
+        load::<UniversalLattice<2, u8>>(&bytes).unwrap_err(),
+        SnapshotError::InvalidBoundaryHandling(9)
+    );
+}
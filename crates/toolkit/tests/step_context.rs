@@ -0,0 +1,84 @@
+use std::cell::Cell;
+
+use rand::Rng;
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    automaton::context::{StepContext, StepRng},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    types::{CellularAutomaton, Lattice, Neighborhood, StochasticRule},
+};
+
+/// Replaces every cell with a fresh draw.
+struct Noise;
+
+impl StochasticRule for Noise {
+    type State = u64;
+
+    fn apply_with_rng(
+        &self,
+        _: &u64,
+        _: &impl Neighborhood<State = u64>,
+        rng: &mut StepRng,
+    ) -> u64 {
+        rng.gen()
+    }
+}
+
+struct NoiseAutomaton {
+    context: Option<Cell<StepContext>>,
+}
+
+impl CellularAutomaton for NoiseAutomaton {
+    type Lattice = UniversalLattice<1, u64>;
+    type Rule = Noise;
+    type NeighborhoodBuilder = VonNeumannNeighborhoodBuilder;
+
+    fn rule(&self) -> &Noise {
+        &Noise
+    }
+
+    fn neighborhood_builder(&self) -> VonNeumannNeighborhoodBuilder {
+        VonNeumannNeighborhoodBuilder
+    }
+
+    fn step_context(&self) -> Option<&Cell<StepContext>> {
+        self.context.as_ref()
+    }
+}
+
+fn two_steps(automaton: &NoiseAutomaton) -> (Vec<u64>, Vec<u64>) {
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([8]));
+
+    automaton.step(&mut lattice);
+    let first = lattice.states();
+    automaton.step(&mut lattice);
+
+    (first, lattice.states())
+}
+
+#[test]
+fn step_advances_the_kept_context() {
+    let automaton = NoiseAutomaton {
+        context: Some(Cell::new(StepContext::new(3))),
+    };
+    let (first, second) = two_steps(&automaton);
+
+    assert_ne!(first, second);
+    assert_eq!(automaton.step_context().unwrap().get().generation(), 2);
+
+    // The draws are those of generations 0 and 1 of the kept seed.
+    let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([8]));
+    let mut context = StepContext::new(3);
+    automaton.step_with_context(&mut lattice, &mut context);
+    assert_eq!(lattice.states(), first);
+    automaton.step_with_context(&mut lattice, &mut context);
+    assert_eq!(lattice.states(), second);
+}
+
+#[test]
+fn step_without_a_context_replays_generation_zero() {
+    let (first, second) = two_steps(&NoiseAutomaton { context: None });
+
+    assert_eq!(first, second);
+}
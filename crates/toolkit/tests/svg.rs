@@ -0,0 +1,99 @@
+#![cfg(feature = "svg")]
+
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    lattice::universal_lattice::UniversalLattice,
+    render::{
+        palette::{MapPalette, BLACK, TRANSPARENT},
+        svg::SvgRenderer,
+    },
+    types::BoundaryHandlingLattice,
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+fn palette() -> MapPalette<u8> {
+    MapPalette::new(TRANSPARENT)
+        .with_color(1, BLACK)
+        .with_color(2, RED)
+}
+
+fn lattice(rows: &[&str]) -> UniversalLattice<2, u8> {
+    let states = rows
+        .iter()
+        .flat_map(|row| row.bytes().map(|cell| cell - b'0'))
+        .collect();
+    UniversalLattice::from_states(
+        states,
+        UniversalLatticeSize::new([rows[0].len(), rows.len()]),
+    )
+}
+
+#[test]
+fn equal_runs_merge_into_rectangles() {
+    let svg = SvgRenderer::new().render(&lattice(&["110", "112", "000"]), &palette());
+
+    assert_eq!(
+        svg,
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="30" viewBox="0 0 30 30">"#,
+            "\n",
+            r##"<path fill="#000000" d="M0 0h20v20h-20Z"/>"##,
+            "\n",
+            r##"<path fill="#ff0000" d="M20 10h10v10h-10Z"/>"##,
+            "\n</svg>\n",
+        )
+    );
+}
+
+#[test]
+fn grid_lines_pad_the_view_box() {
+    let svg = SvgRenderer::new()
+        .with_cell_size(4.0)
+        .with_grid_lines(RED, 1.0)
+        .with_background([255, 255, 255, 128])
+        .render(&lattice(&["10"]), &palette());
+    let lines: Vec<&str> = svg.lines().collect();
+
+    assert_eq!(
+        lines,
+        [
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="9" height="5" viewBox="-0.5 -0.5 9 5">"#,
+            r##"<rect x="-0.5" y="-0.5" width="9" height="5" fill="#ffffff" fill-opacity="0.502"/>"##,
+            r##"<path fill="#000000" d="M0 0h4v4h-4Z"/>"##,
+            r##"<path fill="none" stroke="#ff0000" stroke-width="1" d="M0 0V4M4 0V4M8 0V4M0 0H8M0 4H8"/>"##,
+            "</svg>",
+        ]
+    );
+}
+
+#[test]
+fn space_time_rows_are_generations() {
+    let generations: Vec<UniversalLattice<1, u8>> = [vec![1, 2], vec![1]]
+        .into_iter()
+        .map(|states| {
+            let size = UniversalLatticeSize::new([states.len()]);
+            UniversalLattice::from_states(states, size)
+        })
+        .collect();
+
+    let svg = SvgRenderer::new()
+        .with_cell_size(1.0)
+        .render_space_time(&generations, &palette());
+
+    assert!(svg.contains(r#"width="2" height="2""#));
+    assert!(svg.contains(r##"<path fill="#000000" d="M0 0h1v2h-1Z"/>"##));
+    assert!(svg.contains(r##"<path fill="#ff0000" d="M1 0h1v1h-1Z"/>"##));
+}
+
+#[test]
+fn hexagonal_rows_are_offset() {
+    let svg = SvgRenderer::new().render_hexagonal(&lattice(&["11", "11"]), &palette());
+
+    // Two and a half cells wide, two hexagons high with a quarter overlap.
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="25" height="20.207" viewBox="0 0 25 20.207">"#
+    ));
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert_eq!(svg.matches('M').count(), 2);
+}
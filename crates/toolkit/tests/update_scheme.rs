@@ -0,0 +1,137 @@
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    automaton::{context::StepContext, update_scheme::UpdateScheme},
+    lattice::universal_lattice::UniversalLattice,
+    neighborhood::von_neumann::VonNeumannNeighborhoodBuilder,
+    types::{CellularAutomaton, Lattice, Neighborhood, Rule},
+};
+
+#[test]
+fn probabilistic_alpha_is_clamped() {
+    let context = StepContext::new(7);
+
+    for (alpha, expected) in [
+        (f64::NAN, false),
+        (f64::NEG_INFINITY, false),
+        (-0.5, false),
+        (1.5, true),
+        (f64::INFINITY, true),
+    ] {
+        assert_eq!(
+            UpdateScheme::Probabilistic(alpha).activations(16, &context),
+            vec![expected; 16],
+            "alpha = {alpha}"
+        );
+    }
+}
+
+/// Each cell becomes one more than its western neighbor, so the result shows
+/// which cells were already updated when a cell was visited.
+struct Counter;
+
+impl Rule for Counter {
+    type State = u32;
+
+    fn apply(&self, _: &u32, neighbors: &impl Neighborhood<State = u32>) -> u32 {
+        neighbors.iter_states().next().copied().unwrap_or_default() + 1
+    }
+}
+
+struct CounterAutomaton(UpdateScheme);
+
+impl CellularAutomaton for CounterAutomaton {
+    type Lattice = UniversalLattice<1, u32>;
+    type Rule = Counter;
+    type NeighborhoodBuilder = VonNeumannNeighborhoodBuilder;
+
+    fn rule(&self) -> &Counter {
+        &Counter
+    }
+
+    fn neighborhood_builder(&self) -> VonNeumannNeighborhoodBuilder {
+        VonNeumannNeighborhoodBuilder
+    }
+
+    fn update_scheme(&self) -> UpdateScheme {
+        self.0
+    }
+}
+
+fn points(count: usize) -> Vec<usize> {
+    (0..count).collect()
+}
+
+fn sorted(mut points: Vec<usize>) -> Vec<usize> {
+    points.sort_unstable();
+    points
+}
+
+#[test]
+fn random_sequential_visits_every_cell_once() {
+    let mut context = StepContext::new(5);
+    let first = UpdateScheme::RandomSequential.order(points(64), &context);
+    context.advance();
+    let second = UpdateScheme::RandomSequential.order(points(64), &context);
+
+    assert_eq!(sorted(first.clone()), points(64));
+    assert_eq!(sorted(second.clone()), points(64));
+    assert_ne!(first, points(64));
+    assert_ne!(first, second);
+}
+
+#[test]
+fn cyclic_repeats_one_order_per_seed() {
+    let mut context = StepContext::new(5);
+    let first = UpdateScheme::Cyclic.order(points(64), &context);
+    context.advance();
+
+    assert_eq!(sorted(first.clone()), points(64));
+    assert_ne!(first, points(64));
+    assert_eq!(UpdateScheme::Cyclic.order(points(64), &context), first);
+    assert_ne!(
+        UpdateScheme::Cyclic.order(points(64), &StepContext::new(6)),
+        first
+    );
+}
+
+#[test]
+fn orders_replay_from_the_same_seed() {
+    for scheme in [UpdateScheme::RandomSequential, UpdateScheme::Cyclic] {
+        let context = StepContext::with_generation(9, 3);
+        assert_eq!(
+            scheme.order(points(32), &context),
+            scheme.order(points(32), &StepContext::with_generation(9, 3))
+        );
+    }
+
+    let alpha = UpdateScheme::Probabilistic(0.5);
+    assert_eq!(
+        alpha.activations(32, &StepContext::new(9)),
+        alpha.activations(32, &StepContext::new(9))
+    );
+}
+
+#[test]
+fn line_by_line_updates_in_lattice_order() {
+    assert_eq!(
+        UpdateScheme::LineByLine.order(points(8), &StepContext::new(5)),
+        points(8)
+    );
+
+    let step = |scheme| {
+        let mut lattice = UniversalLattice::from(UniversalLatticeSize::new([6]));
+        CounterAutomaton(scheme).step_with_context(&mut lattice, &mut StepContext::new(5));
+        lattice.states()
+    };
+
+    // Every cell sees its already updated western neighbor.
+    assert_eq!(step(UpdateScheme::LineByLine), [1, 2, 3, 4, 5, 6]);
+    assert_eq!(step(UpdateScheme::Synchronous), [1; 6]);
+
+    // Each cell is still updated exactly once, just in a different order.
+    let random = step(UpdateScheme::RandomSequential);
+    assert_eq!(random[0], 1);
+    assert!(random
+        .windows(2)
+        .all(|pair| pair[1] == 1 || pair[1] == pair[0] + 1));
+}
@@ -0,0 +1,12 @@
+use toolkit::{aliases::UniversalLatticePoint, utils::translate};
+
+#[test]
+fn translate_adds_offsets_per_axis() {
+    assert_eq!(
+        translate(
+            &UniversalLatticePoint::new([3, -2, 7]),
+            &UniversalLatticePoint::new([-1, 1, 0])
+        ),
+        UniversalLatticePoint::new([2, -1, 7])
+    );
+}
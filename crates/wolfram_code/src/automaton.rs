@@ -1,10 +1,11 @@
 use crate::{rule::WolframCodeRule, state::WolframCodeState};
 #[cfg(feature = "wasm")]
-use lattice_wrapper_macros::define_lattice_wrapper;
-use lattice_wrapper_macros::{define_point_wrapper, define_size_wrapper};
+use lattice_wrapper_macros::{define_lattice_wrapper, define_point_wrapper, define_size_wrapper};
 use toolkit::{
+    automaton::{second_order::SecondOrderAutomaton, update_scheme::UpdateScheme},
     lattice::universal_lattice::UniversalLattice,
-    neighborhood::nearest::NearestNeighborhoodBuilder1, types::CellularAutomaton,
+    neighborhood::nearest::NearestNeighborhoodBuilder1,
+    types::CellularAutomaton,
 };
 
 #[cfg(feature = "wasm")]
@@ -17,20 +18,36 @@ use toolkit::{
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct WolframCodeAutomaton {
     rule: WolframCodeRule,
+    update_scheme: UpdateScheme,
 }
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl WolframCodeAutomaton {
     #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(rule: WolframCodeRule) -> Self {
-        Self { rule }
+        Self {
+            rule,
+            update_scheme: UpdateScheme::default(),
+        }
+    }
+}
+
+impl WolframCodeAutomaton {
+    pub fn with_update_scheme(mut self, update_scheme: UpdateScheme) -> Self {
+        self.update_scheme = update_scheme;
+        self
+    }
+
+    pub fn set_update_scheme(&mut self, update_scheme: UpdateScheme) {
+        self.update_scheme = update_scheme;
     }
 }
 
-use toolkit::prelude::*;
+pub type WolframCodeSecondOrderAutomaton = SecondOrderAutomaton<WolframCodeAutomaton>;
 
 impl CellularAutomaton for WolframCodeAutomaton {
     type Lattice = UniversalLattice<1, WolframCodeState>;
@@ -45,24 +62,8 @@ impl CellularAutomaton for WolframCodeAutomaton {
         NearestNeighborhoodBuilder1::new(1)
     }
 
-    fn step(&self, lattice: &mut Self::Lattice) {
-        let points = lattice.points();
-
-        let mut new_states = Vec::with_capacity(points.len());
-        let rule = self.rule();
-        let builder = self.neighborhood_builder();
-
-        for point in &points {
-            let neighborhood = builder.build_neighborhood(&point, lattice);
-            let current_state = lattice.get_state(&point);
-
-            let new_state = rule.apply(&current_state, &neighborhood);
-            new_states.push(new_state);
-        }
-
-        for (point, new_state) in points.into_iter().zip(new_states) {
-            lattice.set_state(&point, &new_state);
-        }
+    fn update_scheme(&self) -> UpdateScheme {
+        self.update_scheme
     }
 }
 
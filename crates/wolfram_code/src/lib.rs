@@ -1,6 +1,8 @@
 pub mod state;
 pub mod rule;
 pub mod automaton;
+#[cfg(feature = "render")]
+pub mod render;
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -1,23 +1,36 @@
-use toolkit::{aliases::UniversalLatticePoint, lattice::universal_lattice::UniversalLattice};
+#[cfg(feature = "render")]
+use std::{fs::File, io::BufWriter};
+
+#[cfg(feature = "render")]
+use toolkit::render::{Renderer, svg::SvgRenderer};
+use toolkit::{
+    aliases::{UniversalLatticePoint, UniversalLatticeSize},
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+};
+#[cfg(feature = "render")]
+use wolfram_code::render::{default_palette, space_time_diagram, space_time_svg};
 use wolfram_code::{
-    automaton::{WolframCodeAutomaton, WolframCodeLatticeSize},
-    rule::WolframCodeRule,
-    state::WolframCodeState,
+    automaton::WolframCodeAutomaton, rule::WolframCodeRule, state::WolframCodeState,
 };
 
-use toolkit::prelude::*;
-
 pub fn main() {
     let ca = WolframCodeAutomaton::new(WolframCodeRule::new(99));
-    let mut l = UniversalLattice::<1, WolframCodeState>::from_states(
-        vec![WolframCodeState::Dead],
-        WolframCodeLatticeSize::new([10].to_vec()).into(),
-    );
+    let mut l = UniversalLattice::<1, WolframCodeState>::from(UniversalLatticeSize::new([10]));
+
+    let len = l.size()[0];
+    let center = len / 2;
 
-    let center = *l.size().get(0).unwrap() / 2;
-    let len = *l.size().get(0).unwrap();
+    l.set_state(
+        &UniversalLatticePoint::new([center as i128]),
+        &WolframCodeState::Alive,
+    );
 
-    l.set_state(&UniversalLatticePoint::new([center as i128]), &WolframCodeState::Alive);
+    // An optional argument names a PNG or SVG file for the space-time diagram.
+    #[cfg(feature = "render")]
+    if let Some(path) = std::env::args().nth(1) {
+        write_diagram(&ca, &l, &path);
+    }
 
     for _ in 0..30 {
         for x in 0..len {
@@ -34,3 +47,23 @@ pub fn main() {
         println!()
     }
 }
+
+#[cfg(feature = "render")]
+fn write_diagram(
+    ca: &WolframCodeAutomaton,
+    initial: &UniversalLattice<1, WolframCodeState>,
+    path: &str,
+) {
+    if path.ends_with(".svg") {
+        let renderer = SvgRenderer::new().with_cell_size(8.0);
+        let svg = space_time_svg(ca, initial, 29, &renderer, &default_palette());
+
+        std::fs::write(path, svg).expect("diagram can be written");
+    } else {
+        let renderer = Renderer::new().with_scale(8);
+        let image = space_time_diagram(ca, initial, 29, &renderer, &default_palette());
+        let file = BufWriter::new(File::create(path).expect("output file can be created"));
+
+        image.write_png(file).expect("diagram can be encoded");
+    }
+}
@@ -0,0 +1,59 @@
+use toolkit::{
+    lattice::universal_lattice::UniversalLattice,
+    prelude::*,
+    render::{
+        Renderer,
+        image::ImageBuffer,
+        palette::{BLACK, MapPalette, Palette, WHITE},
+        svg::SvgRenderer,
+    },
+};
+
+use crate::{automaton::WolframCodeAutomaton, state::WolframCodeState};
+
+/// Black cells on white, as in *A New Kind of Science*.
+pub fn default_palette() -> MapPalette<WolframCodeState> {
+    MapPalette::new(WHITE)
+        .with_color(WolframCodeState::Dead, WHITE)
+        .with_color(WolframCodeState::Alive, BLACK)
+}
+
+/// Runs `generations` steps from `initial` and draws one image row per
+/// generation, the initial state included.
+pub fn space_time_diagram(
+    automaton: &WolframCodeAutomaton,
+    initial: &UniversalLattice<1, WolframCodeState>,
+    generations: usize,
+    renderer: &Renderer,
+    palette: &impl Palette<WolframCodeState>,
+) -> ImageBuffer {
+    renderer.render_space_time(&history(automaton, initial, generations), palette)
+}
+
+/// SVG document of the same diagram as [`space_time_diagram`].
+pub fn space_time_svg(
+    automaton: &WolframCodeAutomaton,
+    initial: &UniversalLattice<1, WolframCodeState>,
+    generations: usize,
+    renderer: &SvgRenderer,
+    palette: &impl Palette<WolframCodeState>,
+) -> String {
+    renderer.render_space_time(&history(automaton, initial, generations), palette)
+}
+
+fn history(
+    automaton: &WolframCodeAutomaton,
+    initial: &UniversalLattice<1, WolframCodeState>,
+    generations: usize,
+) -> Vec<UniversalLattice<1, WolframCodeState>> {
+    let mut lattice = initial.clone();
+    let mut history = Vec::with_capacity(generations + 1);
+
+    history.push(lattice.clone());
+    for _ in 0..generations {
+        automaton.step(&mut lattice);
+        history.push(lattice.clone());
+    }
+
+    history
+}
@@ -28,6 +28,31 @@ impl WolframCodeRule {
 
         Self { transform_map: map }
     }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn code(&self) -> u8 {
+        self.transform_map
+            .iter()
+            .filter(|(_, result)| **result)
+            .fold(0, |code, ((left, middle, right), _)| {
+                code | 1 << ((*left as u8) << 2 | (*middle as u8) << 1 | *right as u8)
+            })
+    }
+}
+
+/// Serialized as its Wolfram code.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WolframCodeRule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.code())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WolframCodeRule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Self::new)
+    }
 }
 
 impl Rule for WolframCodeRule {
@@ -1,7 +1,10 @@
+use toolkit::lattice::snapshot::SnapshotState;
+
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum WolframCodeState {
     #[default]
@@ -27,3 +30,19 @@ impl From<bool> for WolframCodeState {
         }
     }
 }
+
+impl SnapshotState for WolframCodeState {
+    const WIDTH: usize = 1;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(Self::Dead),
+            [1] => Some(Self::Alive),
+            _ => None,
+        }
+    }
+}
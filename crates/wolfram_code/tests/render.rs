@@ -0,0 +1,46 @@
+#![cfg(feature = "render")]
+
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    lattice::universal_lattice::UniversalLattice,
+    render::{
+        Renderer,
+        palette::{BLACK, WHITE},
+    },
+    types::BoundaryHandlingLattice,
+};
+use wolfram_code::{
+    automaton::WolframCodeAutomaton,
+    render::{default_palette, space_time_diagram},
+    rule::WolframCodeRule,
+    state::WolframCodeState,
+};
+
+#[test]
+fn rule_90_draws_a_sierpinski_triangle() {
+    let states = (0..7).map(|x| (x == 3).into()).collect();
+    let initial: UniversalLattice<1, WolframCodeState> =
+        UniversalLattice::from_states(states, UniversalLatticeSize::new([7]));
+    let automaton = WolframCodeAutomaton::new(WolframCodeRule::new(90));
+
+    let image = space_time_diagram(
+        &automaton,
+        &initial,
+        3,
+        &Renderer::new(),
+        &default_palette(),
+    );
+    let rows: Vec<String> = (0..image.height())
+        .map(|y| {
+            (0..image.width())
+                .map(|x| match image.pixel(x, y) {
+                    BLACK => '#',
+                    WHITE => '.',
+                    _ => '?',
+                })
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(rows, ["...#...", "..#.#..", ".#...#.", "#.#.#.#"]);
+}
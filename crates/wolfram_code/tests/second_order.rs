@@ -0,0 +1,55 @@
+use toolkit::{
+    aliases::UniversalLatticeSize,
+    lattice::universal_lattice::UniversalLattice,
+    types::{BoundaryHandling, BoundaryHandlingLattice, Lattice},
+};
+use wolfram_code::{
+    automaton::{WolframCodeAutomaton, WolframCodeSecondOrderAutomaton},
+    rule::WolframCodeRule,
+    state::WolframCodeState,
+};
+
+fn lattice(cells: &str) -> UniversalLattice<1, WolframCodeState> {
+    let states = cells.chars().map(|cell| (cell == '#').into()).collect();
+    let mut lattice =
+        UniversalLattice::from_states(states, UniversalLatticeSize::new([cells.len()]));
+    lattice.set_boundary_handling(BoundaryHandling::Wrap);
+    lattice
+}
+
+#[test]
+fn step_back_undoes_step() {
+    let initial = lattice("..#.##...#..###.#...");
+    let previous = lattice("#...#..##.....#..#..");
+    let mut automaton = WolframCodeSecondOrderAutomaton::new(
+        WolframCodeAutomaton::new(WolframCodeRule::new(30)),
+        previous.clone(),
+    );
+
+    let mut current = initial.clone();
+    for _ in 0..25 {
+        automaton.step(&mut current);
+    }
+    assert_ne!(current.states(), initial.states());
+
+    for _ in 0..25 {
+        automaton.step_back(&mut current);
+    }
+    assert_eq!(current.states(), initial.states());
+    assert_eq!(automaton.previous().states(), previous.states());
+}
+
+#[test]
+fn step_xors_previous_generation() {
+    let mut automaton = WolframCodeSecondOrderAutomaton::new(
+        WolframCodeAutomaton::new(WolframCodeRule::new(90)),
+        lattice("#......"),
+    );
+
+    let mut current = lattice("...#...");
+    automaton.step(&mut current);
+
+    // Rule 90 gives `..#.#..`, the previous generation flips the first cell.
+    assert_eq!(current.states(), lattice("#.#.#..").states());
+    assert_eq!(automaton.previous().states(), lattice("...#...").states());
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "serde")]
+
+use wolfram_code::rule::WolframCodeRule;
+
+#[test]
+fn rule_is_its_code() {
+    let rule = WolframCodeRule::new(110);
+
+    assert_eq!(serde_json::to_string(&rule).unwrap(), "110");
+    assert_eq!(
+        serde_json::from_str::<WolframCodeRule>("110")
+            .unwrap()
+            .code(),
+        110
+    );
+    assert!(serde_json::from_str::<WolframCodeRule>("256").is_err());
+}